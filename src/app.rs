@@ -14,14 +14,25 @@
 
 // TUI application state management
 
-use crate::{fragmap::FragMap, repo::ConflictState, CommitInfo};
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    fragmap::FragMap,
+    merge3::{ConflictRegion, MergeChunk},
+    range_diff::RangeDiffEntry,
+    repo::{CommitMetadata, ConflictSide, ConflictState, MergeStrategy, PlanStep, PushedScope},
+    session::{EditSession, SessionOp},
+    stack_compare::{StackChange, StackRow},
+    todo::TodoCommand,
+    CommitInfo,
+};
 
 /// Result of a view module's `handle_key` function.
 ///
 /// Pure state mutations (scroll, selection, mode transitions) are applied
 /// directly to `AppState` inside the handler. Side effects that require git
 /// operations or terminal access are returned here for `main.rs` to execute.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppAction {
     /// Fully handled, no side effects needed.
     Handled,
@@ -29,6 +40,12 @@ pub enum AppAction {
     Quit,
     /// Reload commits from the repository.
     ReloadCommits,
+    /// Recompute the fragmap after the commits were reordered in place.
+    RefreshFragmap,
+    /// Sequence editor: write the arranged todo list back for git.
+    WriteTodo,
+    /// Sequence editor: leave without writing, making git abort the rebase.
+    AbortTodo,
     /// Begin the split flow: get head_oid, count results, confirm if large.
     PrepareSplit {
         strategy: SplitStrategy,
@@ -50,10 +67,50 @@ pub enum AppAction {
         commit_oid: String,
         head_oid: String,
     },
+    /// Begin dropping every commit already present upstream: get head_oid,
+    /// then show confirmation.
+    PrepareDropUpstreamedConfirm {
+        commit_oids: Vec<String>,
+        commit_summaries: Vec<String>,
+    },
+    /// Execute a confirmed drop of all upstreamed commits.
+    ExecuteDropUpstreamed {
+        commit_oids: Vec<String>,
+        head_oid: String,
+    },
     /// Continue a rebase after the user resolved merge conflicts.
     RebaseContinue(ConflictState),
+    /// Skip the conflicting commit and go on with the remaining ones.
+    RebaseSkip(ConflictState),
     /// Abort a rebase that hit conflicts.
     RebaseAbort(ConflictState),
+    /// Open the in-TUI resolution view for the first conflicting file.
+    PrepareConflictResolve(ConflictState),
+    /// Edit the selected conflict region of the resolution view in the
+    /// editor.
+    EditConflictRegion,
+    /// Write the resolved file of the resolution view and stage it.
+    WriteResolution,
+    /// Simulate a rewrite and show the resulting stack before confirming it.
+    Preview(PreviewOp),
+    /// Merge the conflicting files again with `strategy` and return to the
+    /// conflict dialog, or to continuing when all of them resolved.
+    RetryConflict {
+        strategy: MergeStrategy,
+        conflict_state: ConflictState,
+    },
+    /// Resolve a conflicting file by taking one side whole.
+    TakeConflictSide {
+        path: String,
+        side: ConflictSide,
+        conflict_state: ConflictState,
+    },
+    /// Show the changes of `to_oid` in the diff tool, against `from_oid`
+    /// when given and against its parent otherwise.
+    RunDifftool {
+        from_oid: Option<String>,
+        to_oid: String,
+    },
     /// Launch the merge tool for conflicting files.
     RunMergetool {
         files: Vec<String>,
@@ -64,6 +121,19 @@ pub enum AppAction {
         commit_oid: String,
         current_message: String,
     },
+    /// Open the messages of all listed commits in one editor buffer.
+    PrepareBulkReword,
+    /// Apply the edited messages from a bulk reword, as `(oid, message)`
+    /// pairs ordered oldest first.
+    ApplyBulkReword { messages: Vec<(String, String)> },
+    /// Open the stack as a `git rebase -i` style todo list in the editor.
+    PrepareEditPlan,
+    /// Run an edited todo plan; `first_oid` is the oldest commit it
+    /// rewrites.
+    ExecutePlan {
+        steps: Vec<PlanStep>,
+        first_oid: String,
+    },
     /// Start the squash/fixup flow: user picked source and target.
     /// When `is_fixup` is true the target's message is kept as-is (no editor).
     PrepareSquash {
@@ -73,6 +143,141 @@ pub enum AppAction {
         target_message: String,
         is_fixup: bool,
     },
+    /// Recompute the fragmap of the edit session's virtual stack.
+    RefreshSession,
+    /// Ask for a new message for a commit of the edit session and queue
+    /// the reword.
+    QueueReword {
+        commit_oid: String,
+        current_message: String,
+    },
+    /// Ask for the combined message of a squash and queue it in the edit
+    /// session.
+    QueueSquash {
+        source_oid: String,
+        target_oid: String,
+        source_message: String,
+        target_message: String,
+    },
+    /// Compare the commit list captured at startup with the current one.
+    ShowRangeDiff,
+    /// Compare the commit list with the pushed version of the branch.
+    ComparePushed,
+    /// Open the metadata editor for a commit (author suggestions need the
+    /// repository).
+    PrepareMetadataEdit { commit_oid: String },
+    /// Rewrite a commit with edited author, date and trailers.
+    ApplyMetadata {
+        commit_oid: String,
+        metadata: CommitMetadata,
+    },
+    /// The user confirmed rewriting pushed commits; run the wrapped action
+    /// without asking again.
+    ConfirmedPushed(Box<AppAction>),
+}
+
+impl AppAction {
+    /// For actions that rewrite history, the OID of the oldest commit that
+    /// gets rewritten. Every commit from there up to HEAD changes identity.
+    pub fn rewrite_start(&self) -> Option<&str> {
+        match self {
+            AppAction::PrepareSplit { commit_oid, .. }
+            | AppAction::PrepareDropConfirm { commit_oid, .. }
+            | AppAction::PrepareReword { commit_oid, .. }
+            | AppAction::PrepareMetadataEdit { commit_oid } => Some(commit_oid),
+            AppAction::ExecutePlan { first_oid, .. } => Some(first_oid),
+            AppAction::PrepareSquash { target_oid, .. } => Some(target_oid),
+            AppAction::ApplyBulkReword { messages } => {
+                messages.first().map(|(oid, _)| oid.as_str())
+            }
+            AppAction::PrepareDropUpstreamedConfirm { commit_oids, .. } => {
+                commit_oids.first().map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A rewrite that can be simulated from its confirmation dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreviewOp {
+    Drop {
+        commit_oid: String,
+        head_oid: String,
+    },
+    DropMany {
+        commit_oids: Vec<String>,
+        head_oid: String,
+    },
+    Squash {
+        source_oid: String,
+        target_oid: String,
+    },
+    Split {
+        strategy: SplitStrategy,
+        commit_oid: String,
+    },
+}
+
+impl PreviewOp {
+    /// Short description of the operation for the preview footer.
+    pub fn label(&self) -> String {
+        let short = |oid: &str| oid.chars().take(8).collect::<String>();
+        match self {
+            PreviewOp::Drop { commit_oid, .. } => format!("drop {}", short(commit_oid)),
+            PreviewOp::DropMany { commit_oids, .. } => {
+                format!("drop {} upstreamed commit(s)", commit_oids.len())
+            }
+            PreviewOp::Squash {
+                source_oid,
+                target_oid,
+            } => format!("squash {} into {}", short(source_oid), short(target_oid)),
+            PreviewOp::Split {
+                strategy,
+                commit_oid,
+            } => format!(
+                "split {} {}",
+                short(commit_oid),
+                strategy.label().to_lowercase()
+            ),
+        }
+    }
+}
+
+/// A simulated stack to show in place of the branch's commits.
+#[derive(Debug, Clone)]
+pub struct StackPreview {
+    pub label: String,
+    /// Commits of the resulting stack, oldest first. After a conflict the
+    /// conflicting commit and the ones it holds up follow as they are now.
+    pub commits: Vec<CommitInfo>,
+    pub fragmap: Option<FragMap>,
+    /// The commit the operation would stop at with a conflict.
+    pub conflicting_oid: Option<String>,
+    pub conflicting_files: Vec<String>,
+    /// Commits after the conflict that the operation would not reach.
+    pub unapplied_oids: HashSet<String>,
+}
+
+/// State of the preview view, which shows the simulated commits in the
+/// commit list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewState {
+    pub label: String,
+    pub conflicting_oid: Option<String>,
+    pub conflicting_files: Vec<String>,
+    pub unapplied_oids: HashSet<String>,
+    /// The dialog the preview was opened from, returned to when closed.
+    pub return_mode: AppMode,
+}
+
+/// The branch's commits, fragmap and selection, set aside while a preview
+/// or an edit session shows other commits in their place.
+#[derive(Debug, Clone)]
+pub struct SavedStack {
+    pub commits: Vec<CommitInfo>,
+    pub fragmap: Option<FragMap>,
+    pub selection_index: usize,
 }
 
 /// Split strategy options.
@@ -107,6 +312,15 @@ impl SplitStrategy {
     }
 }
 
+/// Strategies a conflicting step can be retried with; retrying without one
+/// would only conflict again.
+pub const RETRY_STRATEGIES: [MergeStrategy; 4] = [
+    MergeStrategy::Ours,
+    MergeStrategy::Theirs,
+    MergeStrategy::IgnoreAllSpace,
+    MergeStrategy::IgnoreSpaceChange,
+];
+
 /// Application display mode.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppMode {
@@ -120,12 +334,41 @@ pub enum AppMode {
     SplitConfirm(PendingSplit),
     /// Confirmation dialog before dropping a commit.
     DropConfirm(PendingDrop),
+    /// Confirmation dialog before dropping all commits already upstream.
+    DropUpstreamedConfirm(PendingDropUpstreamed),
+    /// Confirmation dialog before an operation rewrites pushed commits.
+    PushedConfirm(PendingPushed),
     /// Waiting for the user to resolve merge conflicts that arose during a
     /// rebase operation. Enter continues, Esc aborts the entire operation.
     RebaseConflict(ConflictState),
+    /// Merge strategy selection for retrying a conflict; carries the
+    /// conflict to return to and the highlighted option index.
+    StrategySelect {
+        conflict: ConflictState,
+        strategy_index: usize,
+    },
     /// Squash/fixup target selection: user picks which commit to squash the source into.
     /// When `is_fixup` is true the target's message is kept as-is (no editor).
     SquashSelect { source_index: usize, is_fixup: bool },
+    /// Range-diff between the commits at startup and the current commits;
+    /// carries the index of the selected entry in `AppState::range_diff`.
+    RangeDiff { selection: usize },
+    /// Local stack versus its pushed version; carries the selected row in
+    /// `AppState::stack_compare` and whether its diff is shown instead of
+    /// the two-column summary.
+    StackCompare { selection: usize, detail: bool },
+    /// Metadata editor dialog for author, author date and trailers.
+    MetadataEdit(MetadataEditState),
+    /// Resolving the conflict regions of one file in the TUI.
+    ConflictResolve(ConflictResolveState),
+    /// Simulated result of a rewrite, shown in the commit list before the
+    /// user confirms it.
+    Preview(Box<PreviewState>),
+    /// Arranging a `git rebase -i` todo list as its sequence editor; the
+    /// command of each commit is in `AppState::todo_commands`.
+    SequenceEdit,
+    /// Queuing operations on the virtual stack of `AppState::session`.
+    EditSession,
     /// Help dialog overlay; carries the mode to return to when closed.
     Help(Box<AppMode>),
 }
@@ -136,14 +379,26 @@ impl AppMode {
     pub fn background(&self) -> Option<AppMode> {
         match self {
             AppMode::CommitList | AppMode::CommitDetail => None,
-            AppMode::SquashSelect { .. } => None,
+            AppMode::RangeDiff { .. } | AppMode::StackCompare { .. } => None,
+            AppMode::SquashSelect { .. } | AppMode::SequenceEdit | AppMode::EditSession => None,
+            AppMode::ConflictResolve(_) | AppMode::Preview(_) => None,
             AppMode::SplitSelect { .. }
             | AppMode::SplitConfirm(_)
             | AppMode::DropConfirm(_)
-            | AppMode::RebaseConflict(_) => Some(AppMode::CommitList),
+            | AppMode::DropUpstreamedConfirm(_)
+            | AppMode::PushedConfirm(_)
+            | AppMode::MetadataEdit(_)
+            | AppMode::RebaseConflict(_)
+            | AppMode::StrategySelect { .. } => Some(AppMode::CommitList),
             AppMode::Help(prev) => Some(prev.as_ref().clone()),
         }
     }
+
+    /// Whether the mode edits text, so keys should be read as characters
+    /// rather than commands.
+    pub fn takes_text_input(&self) -> bool {
+        matches!(self, AppMode::MetadataEdit(_))
+    }
 }
 
 /// Data retained while the user is shown the large-split confirmation dialog.
@@ -163,6 +418,266 @@ pub struct PendingDrop {
     pub head_oid: String,
 }
 
+/// Data retained while the user is shown the drop-upstreamed confirmation
+/// dialog. `commit_oids` and `commit_summaries` are parallel, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDropUpstreamed {
+    pub commit_oids: Vec<String>,
+    pub commit_summaries: Vec<String>,
+    pub head_oid: String,
+}
+
+/// Data retained while the user is asked to confirm rewriting pushed commits.
+/// `commit_summaries` lists the pushed commits that would be rewritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPushed {
+    pub action: Box<AppAction>,
+    pub commit_summaries: Vec<String>,
+}
+
+/// Fields of the metadata editor: author and date, then one row per
+/// trailer. `focus` indexes the rows, `cursor` is a character position in
+/// the focused row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataEditState {
+    pub commit_oid: String,
+    /// The message without its trailer block; trailers are re-appended.
+    pub body: String,
+    /// `Name <email>`.
+    pub author: String,
+    /// `YYYY-MM-DD HH:MM:SS ±HHMM`.
+    pub date: String,
+    /// `Token: value` lines.
+    pub trailers: Vec<String>,
+    pub focus: usize,
+    pub cursor: usize,
+    /// Identities offered by Tab in the author and identity trailer rows.
+    pub suggestions: Vec<String>,
+    /// Position in `suggestions` of the last completion.
+    pub suggestion_index: Option<usize>,
+}
+
+/// The conflict regions of one file being resolved in the TUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictResolveState {
+    /// The conflict dialog to return to.
+    pub conflict: ConflictState,
+    pub path: String,
+    pub chunks: Vec<MergeChunk>,
+    /// The selected conflict region, counting conflict regions only.
+    pub selected: usize,
+}
+
+impl ConflictResolveState {
+    pub fn new(conflict: ConflictState, path: String, chunks: Vec<MergeChunk>) -> Self {
+        Self {
+            conflict,
+            path,
+            chunks,
+            selected: 0,
+        }
+    }
+
+    /// The conflict regions, in file order.
+    pub fn regions(&self) -> impl Iterator<Item = &ConflictRegion> {
+        self.chunks.iter().filter_map(|chunk| match chunk {
+            MergeChunk::Conflict(region) => Some(region),
+            MergeChunk::Resolved(_) => None,
+        })
+    }
+
+    pub fn region_count(&self) -> usize {
+        self.regions().count()
+    }
+
+    pub fn unresolved_count(&self) -> usize {
+        self.regions().filter(|r| r.resolution.is_none()).count()
+    }
+
+    /// Index in `chunks` of the selected conflict region.
+    pub fn selected_chunk(&self) -> Option<usize> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| matches!(chunk, MergeChunk::Conflict(_)))
+            .nth(self.selected)
+            .map(|(index, _)| index)
+    }
+
+    pub fn selected_region_mut(&mut self) -> Option<&mut ConflictRegion> {
+        let index = self.selected_chunk()?;
+        match &mut self.chunks[index] {
+            MergeChunk::Conflict(region) => Some(region),
+            MergeChunk::Resolved(_) => None,
+        }
+    }
+}
+
+/// Rows of the metadata editor before the trailers.
+pub const METADATA_FIXED_ROWS: usize = 2;
+
+impl MetadataEditState {
+    /// Start editing `commit`, offering `suggestions` for identities.
+    pub fn new(commit: &CommitInfo, suggestions: Vec<String>) -> Self {
+        let (body, trailers) = crate::message::split_trailers(&commit.message);
+        let author = match (&commit.author, &commit.author_email) {
+            (Some(name), Some(email)) => format!("{name} <{email}>"),
+            _ => String::new(),
+        };
+        let date = commit
+            .author_date
+            .map(crate::metadata::format_date)
+            .unwrap_or_default();
+        let cursor = author.chars().count();
+        Self {
+            commit_oid: commit.oid.clone(),
+            body,
+            author,
+            date,
+            trailers,
+            focus: 0,
+            cursor,
+            suggestions,
+            suggestion_index: None,
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        METADATA_FIXED_ROWS + self.trailers.len()
+    }
+
+    /// The text of the focused row.
+    pub fn field(&self) -> &str {
+        match self.focus {
+            0 => &self.author,
+            1 => &self.date,
+            n => &self.trailers[n - METADATA_FIXED_ROWS],
+        }
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        match self.focus {
+            0 => &mut self.author,
+            1 => &mut self.date,
+            n => &mut self.trailers[n - METADATA_FIXED_ROWS],
+        }
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.field()
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.field().len(), |(i, _)| i)
+    }
+
+    /// Move the focus by `delta` rows, putting the cursor at the end.
+    pub fn move_focus(&mut self, delta: isize) {
+        let max = self.row_count() - 1;
+        self.focus = self.focus.saturating_add_signed(delta).min(max);
+        self.cursor = self.field().chars().count();
+        self.suggestion_index = None;
+    }
+
+    /// Move the cursor by `delta` characters within the focused row.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let len = self.field().chars().count();
+        self.cursor = self.cursor.saturating_add_signed(delta).min(len);
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let at = self.byte_index(self.cursor);
+        self.field_mut().insert(at, c);
+        self.cursor += 1;
+        self.suggestion_index = None;
+    }
+
+    /// Delete the character before the cursor.
+    pub fn delete_backward(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let at = self.byte_index(self.cursor);
+        self.field_mut().remove(at);
+        self.suggestion_index = None;
+    }
+
+    /// Delete the character under the cursor.
+    pub fn delete_forward(&mut self) {
+        if self.cursor < self.field().chars().count() {
+            let at = self.byte_index(self.cursor);
+            self.field_mut().remove(at);
+            self.suggestion_index = None;
+        }
+    }
+
+    /// Append an empty trailer row and focus it.
+    pub fn add_trailer(&mut self) {
+        self.trailers.push(String::new());
+        self.focus = self.row_count() - 1;
+        self.cursor = 0;
+        self.suggestion_index = None;
+    }
+
+    /// Remove the focused trailer row. Author and date cannot be removed.
+    pub fn remove_trailer(&mut self) {
+        if self.focus < METADATA_FIXED_ROWS {
+            return;
+        }
+        self.trailers.remove(self.focus - METADATA_FIXED_ROWS);
+        self.move_focus(0);
+    }
+
+    /// Replace the identity in the focused row with the next suggestion.
+    /// In a trailer row the token is kept (`Co-authored-by: <identity>`);
+    /// a row without a token becomes a `Co-authored-by` trailer.
+    pub fn complete(&mut self) {
+        if self.focus == 1 || self.suggestions.is_empty() {
+            return;
+        }
+        let next = self
+            .suggestion_index
+            .map_or(0, |i| (i + 1) % self.suggestions.len());
+        let identity = self.suggestions[next].clone();
+        let text = if self.focus == 0 {
+            identity
+        } else {
+            let token = match self.field().split_once(':') {
+                Some((token, _)) if !token.trim().is_empty() => token.trim().to_string(),
+                _ => "Co-authored-by".to_string(),
+            };
+            format!("{token}: {identity}")
+        };
+        *self.field_mut() = text;
+        self.cursor = self.field().chars().count();
+        self.suggestion_index = Some(next);
+    }
+
+    /// Validate the fields and build the new metadata, or describe the
+    /// first invalid field.
+    pub fn to_metadata(&self) -> Result<CommitMetadata, String> {
+        let (author_name, author_email) = crate::metadata::parse_identity(&self.author)
+            .ok_or_else(|| "Author must look like 'Name <email>'".to_string())?;
+        let (author_time, author_offset_minutes) = crate::metadata::parse_date(&self.date)
+            .ok_or_else(|| "Date must look like 'YYYY-MM-DD HH:MM:SS +HHMM'".to_string())?;
+        if let Some(bad) = self
+            .trailers
+            .iter()
+            .map(|t| t.trim())
+            .find(|t| !t.is_empty() && !crate::message::is_trailer_line(t))
+        {
+            return Err(format!("Not a trailer: '{bad}'"));
+        }
+        Ok(CommitMetadata {
+            author_name,
+            author_email,
+            author_time,
+            author_offset_minutes,
+            message: crate::message::join_trailers(&self.body, &self.trailers),
+        })
+    }
+}
+
 /// Application state for the TUI.
 ///
 /// Manages the overall state of the interactive terminal interface,
@@ -177,6 +692,43 @@ pub struct AppState {
     /// The reference OID (merge-base) used when the session started.
     /// Stored here so 'u' update can rescan from HEAD down to the same base.
     pub reference_oid: String,
+    /// The commit-ish the reference point was derived from (given on the
+    /// command line or inferred), shown in the footer. Empty when unknown.
+    pub reference_name: String,
+    /// The plain merge-base with `reference_name` when `reference_oid` is a
+    /// fork point (--fork-point) that differs from it, because the
+    /// reference was rewritten. Explained in the footer and help.
+    pub fork_point_merge_base: Option<String>,
+    /// OIDs of commits whose patch is already present on the reference
+    /// branch (same patch-id), marked in the commit list like `git cherry`.
+    pub upstreamed_oids: HashSet<String>,
+    /// Which remote-tracking refs make a commit count as pushed
+    /// (`tailor.protectPushed`). `None` disables the protection.
+    pub protect_pushed: Option<PushedScope>,
+    /// OIDs of commits already published to a remote; rewriting them asks
+    /// for confirmation first.
+    pub pushed_oids: HashSet<String>,
+    /// Commit marked with Space; the diff tool then compares it with the
+    /// selected commit instead of showing the selected commit alone.
+    pub marked_oid: Option<String>,
+    /// Lint warnings per commit OID; commits without warnings are absent.
+    pub lint_warnings: HashMap<String, Vec<String>>,
+    /// Todo command per commit OID in sequence editor mode; commits
+    /// without an entry are picked.
+    pub todo_commands: HashMap<String, TodoCommand>,
+    /// The regular commits as they were when the session started, used as
+    /// the "before" side of the range-diff view.
+    pub original_commits: Vec<CommitInfo>,
+    /// The branch's own stack while a preview or an edit session is shown.
+    pub saved_stack: Option<SavedStack>,
+    /// Operations queued in an edit session, applied together on confirm.
+    pub session: Option<EditSession>,
+    /// Entries shown by the range-diff view (computed when it is opened).
+    pub range_diff: Vec<RangeDiffEntry>,
+    /// Rows shown by the pushed-stack comparison (computed when it is opened).
+    pub stack_compare: Vec<StackRow>,
+    /// Name of the remote-tracking ref compared against, e.g. `origin/topic`.
+    pub stack_compare_remote: String,
     /// Optional fragmap visualization data.
     /// None if fragmap computation failed or was not performed.
     pub fragmap: Option<FragMap>,
@@ -208,6 +760,20 @@ impl AppState {
             reverse: false,
             full_fragmap: false,
             reference_oid: String::new(),
+            reference_name: String::new(),
+            fork_point_merge_base: None,
+            upstreamed_oids: HashSet::new(),
+            protect_pushed: Some(PushedScope::AnyRemote),
+            pushed_oids: HashSet::new(),
+            marked_oid: None,
+            lint_warnings: HashMap::new(),
+            todo_commands: HashMap::new(),
+            original_commits: Vec::new(),
+            saved_stack: None,
+            session: None,
+            range_diff: Vec::new(),
+            stack_compare: Vec::new(),
+            stack_compare_remote: String::new(),
             fragmap: None,
             fragmap_scroll_offset: 0,
             mode: AppMode::CommitList,
@@ -230,6 +796,20 @@ impl AppState {
             reverse: false,
             full_fragmap: false,
             reference_oid: String::new(),
+            reference_name: String::new(),
+            fork_point_merge_base: None,
+            upstreamed_oids: HashSet::new(),
+            protect_pushed: Some(PushedScope::AnyRemote),
+            pushed_oids: HashSet::new(),
+            marked_oid: None,
+            lint_warnings: HashMap::new(),
+            todo_commands: HashMap::new(),
+            original_commits: Vec::new(),
+            saved_stack: None,
+            session: None,
+            range_diff: Vec::new(),
+            stack_compare: Vec::new(),
+            stack_compare_remote: String::new(),
             fragmap: None,
             fragmap_scroll_offset: 0,
             mode: AppMode::CommitList,
@@ -334,6 +914,131 @@ impl AppState {
         self.mode = AppMode::CommitList;
     }
 
+    /// Show a simulated stack in the commit list, setting the branch's
+    /// commits aside until [`AppState::leave_preview`]. The conflicting
+    /// commit is selected, or the newest one when there is no conflict.
+    pub fn enter_preview(&mut self, preview: StackPreview) {
+        let selection = preview
+            .conflicting_oid
+            .as_ref()
+            .and_then(|oid| preview.commits.iter().position(|c| &c.oid == oid))
+            .unwrap_or(preview.commits.len().saturating_sub(1));
+        self.saved_stack = Some(SavedStack {
+            commits: std::mem::replace(&mut self.commits, preview.commits),
+            fragmap: std::mem::replace(&mut self.fragmap, preview.fragmap),
+            selection_index: std::mem::replace(&mut self.selection_index, selection),
+        });
+        self.fragmap_scroll_offset = 0;
+        let return_mode = std::mem::replace(&mut self.mode, AppMode::CommitList);
+        self.mode = AppMode::Preview(Box::new(PreviewState {
+            label: preview.label,
+            conflicting_oid: preview.conflicting_oid,
+            conflicting_files: preview.conflicting_files,
+            unapplied_oids: preview.unapplied_oids,
+            return_mode,
+        }));
+    }
+
+    /// Put the branch's commits back and return to the dialog the preview
+    /// was opened from.
+    pub fn leave_preview(&mut self) {
+        let AppMode::Preview(state) = std::mem::replace(&mut self.mode, AppMode::CommitList) else {
+            return;
+        };
+        if let Some(saved) = self.saved_stack.take() {
+            self.commits = saved.commits;
+            self.fragmap = saved.fragmap;
+            self.selection_index = saved.selection_index;
+        }
+        self.fragmap_scroll_offset = 0;
+        self.mode = state.return_mode;
+    }
+
+    /// Start an edit session on the commit list. The branch's stack is set
+    /// aside until the session is applied or discarded.
+    pub fn start_edit_session(&mut self) {
+        let session = EditSession::new(self.commits.clone());
+        self.saved_stack = Some(SavedStack {
+            commits: self.commits.clone(),
+            fragmap: self.fragmap.clone(),
+            selection_index: self.selection_index,
+        });
+        self.session = Some(session);
+        self.mode = AppMode::EditSession;
+    }
+
+    /// Queue `op` in the edit session and show the new virtual stack,
+    /// keeping the selected commit selected while it is still listed.
+    /// Returns whether the operation was queued.
+    pub fn queue_session_op(&mut self, op: SessionOp) -> bool {
+        let Some(session) = &mut self.session else {
+            return false;
+        };
+        if let Err(e) = session.apply(op) {
+            self.set_error_message(e.to_string());
+            return false;
+        }
+        self.show_session_stack();
+        true
+    }
+
+    /// Take back the last operation of the edit session.
+    pub fn undo_session_op(&mut self) -> bool {
+        let undone = self.session.as_mut().and_then(EditSession::undo);
+        if undone.is_none() {
+            self.set_error_message("Nothing to undo");
+            return false;
+        }
+        self.show_session_stack();
+        true
+    }
+
+    fn show_session_stack(&mut self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let selected = self
+            .commits
+            .get(self.selection_index)
+            .map(|c| c.oid.clone());
+        self.commits = session.commits();
+        self.selection_index = selected
+            .and_then(|oid| self.commits.iter().position(|c| c.oid == oid))
+            .unwrap_or(self.selection_index)
+            .min(self.commits.len().saturating_sub(1));
+    }
+
+    /// Leave the edit session and put the branch's stack back.
+    pub fn discard_edit_session(&mut self) {
+        self.session = None;
+        if let Some(saved) = self.saved_stack.take() {
+            self.commits = saved.commits;
+            self.fragmap = saved.fragmap;
+            self.selection_index = saved.selection_index;
+        }
+        self.fragmap_scroll_offset = 0;
+        self.mode = AppMode::CommitList;
+    }
+
+    /// Leave the edit session and return the plan that applies it in one
+    /// rebase, or `Handled` when it would change nothing.
+    pub fn finish_edit_session(&mut self) -> AppAction {
+        let Some(session) = self.session.clone() else {
+            return AppAction::Handled;
+        };
+        self.discard_edit_session();
+        match session.first_rewritten() {
+            Some(first_oid) => AppAction::ExecutePlan {
+                steps: session.plan(),
+                first_oid: first_oid.to_string(),
+            },
+            None => {
+                self.set_success_message("Nothing to apply");
+                AppAction::Handled
+            }
+        }
+    }
+
     /// Enter the drop confirmation dialog.
     pub fn enter_drop_confirm(
         &mut self,
@@ -353,11 +1058,143 @@ impl AppState {
         self.mode = AppMode::CommitList;
     }
 
+    /// Enter the drop-upstreamed confirmation dialog.
+    pub fn enter_drop_upstreamed_confirm(
+        &mut self,
+        commit_oids: Vec<String>,
+        commit_summaries: Vec<String>,
+        head_oid: String,
+    ) {
+        self.mode = AppMode::DropUpstreamedConfirm(PendingDropUpstreamed {
+            commit_oids,
+            commit_summaries,
+            head_oid,
+        });
+    }
+
+    /// Summaries of the pushed commits that rewriting from `oldest_oid` up to
+    /// HEAD would change, oldest first.
+    pub fn pushed_commits_from(&self, oldest_oid: &str) -> Vec<String> {
+        let Some(start) = self.commits.iter().position(|c| c.oid == oldest_oid) else {
+            return Vec::new();
+        };
+        self.commits[start..]
+            .iter()
+            .filter(|c| self.pushed_oids.contains(&c.oid))
+            .map(|c| c.summary.clone())
+            .collect()
+    }
+
+    /// Enter the pushed-commit confirmation dialog for `action`.
+    pub fn enter_pushed_confirm(&mut self, action: AppAction, commit_summaries: Vec<String>) {
+        self.mode = AppMode::PushedConfirm(PendingPushed {
+            action: Box::new(action),
+            commit_summaries,
+        });
+    }
+
+    /// Cancel the pushed-commit confirmation and return to CommitList.
+    pub fn cancel_pushed_confirm(&mut self) {
+        self.mode = AppMode::CommitList;
+    }
+
+    /// Show the range-diff view with the given entries, selecting the first
+    /// entry that is not unchanged.
+    pub fn enter_range_diff(&mut self, entries: Vec<RangeDiffEntry>) {
+        let selection = entries
+            .iter()
+            .position(|e| e.status != crate::range_diff::PairStatus::Same)
+            .unwrap_or(0);
+        self.range_diff = entries;
+        self.detail_scroll_offset = 0;
+        self.mode = AppMode::RangeDiff { selection };
+    }
+
+    /// Move the range-diff selection by `delta` entries, clamped to bounds.
+    pub fn range_diff_move(&mut self, delta: isize) {
+        if let AppMode::RangeDiff { selection } = &mut self.mode {
+            let max = self.range_diff.len().saturating_sub(1);
+            let new = selection.saturating_add_signed(delta).min(max);
+            if new != *selection {
+                *selection = new;
+                self.detail_scroll_offset = 0;
+            }
+        }
+    }
+
+    /// Leave the range-diff view and return to CommitList.
+    pub fn close_range_diff(&mut self) {
+        self.range_diff.clear();
+        self.mode = AppMode::CommitList;
+    }
+
+    /// Show the pushed-stack comparison, selecting the first row that
+    /// differs.
+    pub fn enter_stack_compare(&mut self, remote: String, rows: Vec<StackRow>) {
+        let selection = rows
+            .iter()
+            .position(|r| r.change != StackChange::Unchanged || r.reordered)
+            .unwrap_or(0);
+        self.stack_compare = rows;
+        self.stack_compare_remote = remote;
+        self.detail_scroll_offset = 0;
+        self.mode = AppMode::StackCompare {
+            selection,
+            detail: false,
+        };
+    }
+
+    /// Move the stack-compare selection by `delta` rows, clamped to bounds.
+    pub fn stack_compare_move(&mut self, delta: isize) {
+        if let AppMode::StackCompare { selection, .. } = &mut self.mode {
+            let max = self.stack_compare.len().saturating_sub(1);
+            *selection = selection.saturating_add_signed(delta).min(max);
+        }
+    }
+
+    /// Toggle between the summary and the diff of the selected row.
+    pub fn toggle_stack_compare_detail(&mut self) {
+        if let AppMode::StackCompare { detail, .. } = &mut self.mode {
+            *detail = !*detail;
+            self.detail_scroll_offset = 0;
+        }
+    }
+
+    /// Leave the stack comparison and return to CommitList.
+    pub fn close_stack_compare(&mut self) {
+        self.stack_compare.clear();
+        self.mode = AppMode::CommitList;
+    }
+
+    /// Open the metadata editor.
+    pub fn enter_metadata_edit(&mut self, state: MetadataEditState) {
+        self.mode = AppMode::MetadataEdit(state);
+    }
+
+    /// Close the metadata editor without changes.
+    pub fn cancel_metadata_edit(&mut self) {
+        self.mode = AppMode::CommitList;
+    }
+
     /// Enter the rebase-conflict resolution dialog.
-    pub fn enter_rebase_conflict(&mut self, state: ConflictState) {
+    ///
+    /// The fragmap still describes the commits from before the operation,
+    /// so it tells which of their code regions the conflict involves.
+    pub fn enter_rebase_conflict(&mut self, mut state: ConflictState) {
+        if state.conflict_clusters.is_empty() {
+            if let Some(fragmap) = &self.fragmap {
+                state.conflict_clusters = fragmap
+                    .conflict_clusters(&state.conflicting_commit_oid, &state.conflicting_files);
+            }
+        }
         self.mode = AppMode::RebaseConflict(state);
     }
 
+    /// Enter the conflict resolution view for one file.
+    pub fn enter_conflict_resolve(&mut self, state: ConflictResolveState) {
+        self.mode = AppMode::ConflictResolve(state);
+    }
+
     /// Enter split strategy selection mode.
     /// Only allowed for real commits (not staged/unstaged synthetic rows).
     pub fn enter_split_select(&mut self) {
@@ -395,9 +1232,14 @@ impl AppState {
         };
     }
 
-    /// Cancel squash selection and return to CommitList.
+    /// Cancel squash selection and return to CommitList, or to the edit
+    /// session it was started from.
     pub fn cancel_squash_select(&mut self) {
-        self.mode = AppMode::CommitList;
+        self.mode = if self.session.is_some() {
+            AppMode::EditSession
+        } else {
+            AppMode::CommitList
+        };
     }
 
     /// Set a success status message (shown with green background).
@@ -445,6 +1287,42 @@ impl AppState {
         }
     }
 
+    /// Open the merge strategy selection for retrying the conflict shown in
+    /// the conflict dialog.
+    pub fn enter_strategy_select(&mut self) {
+        if let AppMode::RebaseConflict(state) = &self.mode {
+            self.mode = AppMode::StrategySelect {
+                conflict: state.clone(),
+                strategy_index: 0,
+            };
+        }
+    }
+
+    /// Move merge strategy selection up.
+    pub fn strategy_select_up(&mut self) {
+        if let AppMode::StrategySelect { strategy_index, .. } = &mut self.mode {
+            *strategy_index = strategy_index.saturating_sub(1);
+        }
+    }
+
+    /// Move merge strategy selection down.
+    pub fn strategy_select_down(&mut self) {
+        if let AppMode::StrategySelect { strategy_index, .. } = &mut self.mode {
+            if *strategy_index < RETRY_STRATEGIES.len() - 1 {
+                *strategy_index += 1;
+            }
+        }
+    }
+
+    /// Get the currently selected merge strategy.
+    pub fn selected_merge_strategy(&self) -> MergeStrategy {
+        if let AppMode::StrategySelect { strategy_index, .. } = self.mode {
+            RETRY_STRATEGIES[strategy_index]
+        } else {
+            RETRY_STRATEGIES[0]
+        }
+    }
+
     /// Toggle between CommitList and CommitDetail modes.
     pub fn toggle_detail_view(&mut self) {
         let new_mode = match &self.mode {
@@ -454,13 +1332,83 @@ impl AppState {
             | AppMode::SplitSelect { .. }
             | AppMode::SplitConfirm(_)
             | AppMode::DropConfirm(_)
+            | AppMode::DropUpstreamedConfirm(_)
+            | AppMode::PushedConfirm(_)
             | AppMode::RebaseConflict(_)
-            | AppMode::SquashSelect { .. } => return,
+            | AppMode::StrategySelect { .. }
+            | AppMode::SquashSelect { .. }
+            | AppMode::RangeDiff { .. }
+            | AppMode::StackCompare { .. }
+            | AppMode::MetadataEdit(_)
+            | AppMode::ConflictResolve(_)
+            | AppMode::Preview(_)
+            | AppMode::SequenceEdit
+            | AppMode::EditSession => return,
         };
         self.mode = new_mode;
         self.detail_scroll_offset = 0;
     }
 
+    /// Mark the selected commit for comparison, or unmark it when it is
+    /// already marked.
+    pub fn toggle_mark(&mut self) {
+        let Some(commit) = self.commits.get(self.selection_index) else {
+            return;
+        };
+        if self.marked_oid.as_deref() == Some(commit.oid.as_str()) {
+            self.marked_oid = None;
+        } else {
+            self.marked_oid = Some(commit.oid.clone());
+        }
+    }
+
+    /// The diff tool action for the selected commit: the range between the
+    /// marked and the selected commit when another commit is marked, the
+    /// selected commit alone otherwise.
+    pub fn difftool_action(&self) -> AppAction {
+        let selected = &self.commits[self.selection_index];
+        let marked = self
+            .marked_oid
+            .as_ref()
+            .and_then(|oid| self.commits.iter().position(|c| &c.oid == oid))
+            .filter(|&i| i != self.selection_index);
+        match marked {
+            // Commits are ordered oldest first.
+            Some(i) if i < self.selection_index => AppAction::RunDifftool {
+                from_oid: Some(self.commits[i].oid.clone()),
+                to_oid: selected.oid.clone(),
+            },
+            Some(i) => AppAction::RunDifftool {
+                from_oid: Some(selected.oid.clone()),
+                to_oid: self.commits[i].oid.clone(),
+            },
+            None => AppAction::RunDifftool {
+                from_oid: None,
+                to_oid: selected.oid.clone(),
+            },
+        }
+    }
+
+    /// Swap the selected commit with the next newer (`newer`) or older
+    /// one, keeping it selected. Returns whether anything moved.
+    pub fn move_selected_commit(&mut self, newer: bool) -> bool {
+        let from = self.selection_index;
+        let to = if newer {
+            from + 1
+        } else {
+            match from.checked_sub(1) {
+                Some(to) => to,
+                None => return false,
+            }
+        };
+        if to >= self.commits.len() {
+            return false;
+        }
+        self.commits.swap(from, to);
+        self.selection_index = to;
+        true
+    }
+
     /// Show help dialog, saving current mode to return to later.
     pub fn show_help(&mut self) {
         if !matches!(self.mode, AppMode::Help(_)) {
@@ -515,6 +1463,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pushed_commits_from_includes_descendants() {
+        let mut app = AppState::new();
+        app.commits = vec![
+            create_test_commit("abc123", "First"),
+            create_test_commit("def456", "Second"),
+            create_test_commit("ghi789", "Third"),
+        ];
+        app.pushed_oids.insert("def456".to_string());
+
+        assert_eq!(app.pushed_commits_from("abc123"), vec!["Second"]);
+        assert_eq!(app.pushed_commits_from("def456"), vec!["Second"]);
+        assert!(app.pushed_commits_from("ghi789").is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_start_of_squash_is_target() {
+        let action = AppAction::PrepareSquash {
+            source_oid: "def456".to_string(),
+            target_oid: "abc123".to_string(),
+            source_message: "Second".to_string(),
+            target_message: "First".to_string(),
+            is_fixup: false,
+        };
+        assert_eq!(action.rewrite_start(), Some("abc123"));
+        assert_eq!(AppAction::ReloadCommits.rewrite_start(), None);
+    }
+
+    #[test]
+    fn test_difftool_action_orders_marked_range_oldest_first() {
+        let mut app = AppState::new();
+        app.commits = vec![
+            create_test_commit("abc123", "First"),
+            create_test_commit("def456", "Second"),
+            create_test_commit("ghi789", "Third"),
+        ];
+        app.selection_index = 1;
+        assert_eq!(
+            app.difftool_action(),
+            AppAction::RunDifftool {
+                from_oid: None,
+                to_oid: "def456".to_string(),
+            }
+        );
+
+        app.toggle_mark();
+        app.selection_index = 0;
+        assert_eq!(
+            app.difftool_action(),
+            AppAction::RunDifftool {
+                from_oid: Some("abc123".to_string()),
+                to_oid: "def456".to_string(),
+            }
+        );
+
+        app.selection_index = 1;
+        app.toggle_mark();
+        assert_eq!(app.marked_oid, None);
+    }
+
     #[test]
     fn test_move_up_with_empty_list() {
         let mut app = AppState::new();
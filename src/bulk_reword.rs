@@ -0,0 +1,191 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Edit the messages of many commits in one editor buffer.
+//
+// The buffer lists each commit's message after a separator line naming the
+// commit:
+//
+//   ==== commit <oid> ====
+//   <message>
+//
+// Comment lines before the first separator are instructions and are
+// ignored. Everything between two separators is the message; messages that
+// were edited are cleaned up like any other edited message.
+
+use crate::{message::MessageOptions, CommitInfo};
+use std::collections::HashMap;
+
+const SEPARATOR_PREFIX: &str = "==== commit ";
+const SEPARATOR_SUFFIX: &str = " ====";
+
+const HEADER: &str = "\
+Edit the commit messages below and close the editor to apply them.
+Each message follows its \"==== commit <oid> ====\" line; keep those
+lines as they are. Commits whose message is unchanged are not touched.";
+
+fn separator(oid: &str) -> String {
+    format!("{SEPARATOR_PREFIX}{oid}{SEPARATOR_SUFFIX}")
+}
+
+fn parse_separator(line: &str) -> Option<&str> {
+    line.strip_prefix(SEPARATOR_PREFIX)?
+        .strip_suffix(SEPARATOR_SUFFIX)
+        .map(str::trim)
+}
+
+fn short(oid: &str) -> String {
+    oid.chars().take(10).collect()
+}
+
+/// Build the editor buffer for `commits`, in the given order.
+pub fn format_buffer(commits: &[CommitInfo], options: &MessageOptions) -> String {
+    let mut buffer = options.comment_lines(HEADER);
+    for commit in commits {
+        buffer.push('\n');
+        buffer.push_str(&separator(&commit.oid));
+        buffer.push('\n');
+        buffer.push_str(commit.message.trim_end());
+        buffer.push('\n');
+    }
+    buffer
+}
+
+/// Parse an edited buffer back into `(oid, new message)` pairs for the
+/// commits whose message changed, in the order of `commits`.
+///
+/// Edited messages are cleaned up with `options`. Every problem is
+/// reported, one entry per commit where possible: unknown, repeated or
+/// missing separators and empty messages.
+pub fn parse_buffer(
+    text: &str,
+    commits: &[CommitInfo],
+    options: &MessageOptions,
+) -> Result<Vec<(String, String)>, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+    for line in text.lines() {
+        if let Some(oid) = parse_separator(line) {
+            sections.push((oid.to_string(), Vec::new()));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() && !line.starts_with(options.comment.as_str()) {
+            errors.push(format!("text before the first commit: '{}'", line.trim()));
+        }
+    }
+
+    let mut edited: HashMap<&str, String> = HashMap::new();
+    for (oid, lines) in &sections {
+        if !commits.iter().any(|c| &c.oid == oid) {
+            errors.push(format!("{}: not one of the listed commits", short(oid)));
+            continue;
+        }
+        let message = lines.join("\n");
+        if edited.insert(oid.as_str(), message).is_some() {
+            errors.push(format!("{}: listed more than once", short(oid)));
+        }
+    }
+
+    let mut changes = Vec::new();
+    for commit in commits {
+        match edited.get(commit.oid.as_str()) {
+            None => errors.push(format!("{}: separator line missing", short(&commit.oid))),
+            Some(message) if message.trim() == commit.message.trim() => {}
+            Some(message) => match options.cleanup(message) {
+                cleaned if cleaned.trim().is_empty() => {
+                    errors.push(format!("{}: empty message", short(&commit.oid)))
+                }
+                cleaned => changes.push((commit.oid.clone(), cleaned)),
+            },
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(changes)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_buffer_yields_no_changes() {
+        let commits = vec![
+            CommitInfo::with_message("aaa", "Add a\n\nBody.\n"),
+            CommitInfo::with_message("bbb", "Add b\n"),
+        ];
+        let buffer = format_buffer(&commits, &MessageOptions::default());
+        assert_eq!(
+            parse_buffer(&buffer, &commits, &MessageOptions::default()),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn edited_messages_are_returned_in_commit_order() {
+        let commits = vec![
+            CommitInfo::with_message("aaa", "Add a\n"),
+            CommitInfo::with_message("bbb", "Add b\n"),
+        ];
+        let buffer = format_buffer(&commits, &MessageOptions::default())
+            .replace("Add b", "Add b file")
+            .replace("Add a", "Add a file\n\nWith a body.");
+        assert_eq!(
+            parse_buffer(&buffer, &commits, &MessageOptions::default()),
+            Ok(vec![
+                (
+                    "aaa".to_string(),
+                    "Add a file\n\nWith a body.\n".to_string()
+                ),
+                ("bbb".to_string(), "Add b file\n".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn problems_are_reported_per_commit() {
+        let commits = vec![
+            CommitInfo::with_message("aaa", "Add a\n"),
+            CommitInfo::with_message("bbb", "Add b\n"),
+            CommitInfo::with_message("ccc", "Add c\n"),
+        ];
+        let buffer = "==== commit aaa ====\n\n\
+                      ==== commit bbb ====\nAdd b\n\
+                      ==== commit bbb ====\nAdd b\n\
+                      ==== commit ddd ====\nAdd d\n";
+        assert_eq!(
+            parse_buffer(buffer, &commits, &MessageOptions::default()),
+            Err(vec![
+                "bbb: listed more than once".to_string(),
+                "ddd: not one of the listed commits".to_string(),
+                "aaa: empty message".to_string(),
+                "ccc: separator line missing".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn unknown_non_ascii_oid_is_reported() {
+        let commits = vec![CommitInfo::with_message("aaa", "Add a\n")];
+        let buffer = "==== commit aaa ====\nAdd a\n\
+                      ==== commit aaaaaaaaaé ====\nAdd e\n";
+        assert_eq!(
+            parse_buffer(buffer, &commits, &MessageOptions::default()),
+            Err(vec!["aaaaaaaaaé: not one of the listed commits".to_string()])
+        );
+    }
+}
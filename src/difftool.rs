@@ -0,0 +1,213 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Open a commit, or the difference between two commits, in the user-configured
+// diff tool.
+//
+// Git's diff tool contract:
+//   - `diff.tool` names the tool (falling back to `merge.tool`), or
+//     `diff.guitool` when `difftool.guiDefault` asks for the GUI tool
+//   - `difftool.<name>.cmd` is an optional shell command for that tool;
+//     otherwise a built-in command is used, run from `difftool.<name>.path`
+//     when set
+//   - The shell command has access to $LOCAL (old side), $REMOTE (new side)
+//     and $MERGED / $BASE (the path of the file in the repository)
+//   - The tool is run once per changed file; with `difftool.trustExitCode`
+//     a non-zero exit status stops the remaining files
+//
+// Like the merge tool, we suspend the TUI, write both blobs to temp files
+// named with the original extension, run the tool via `sh -c`, then restore.
+
+use crate::{
+    mergetool::{shell_quote, tool_name},
+    repo::{config_bool, FileBlobs, GitRepo},
+};
+use anyhow::{Context, Result};
+use crossterm::{execute, terminal};
+use std::io::Write as _;
+use std::path::Path;
+
+/// A diff tool and how to run it, resolved from git config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffTool {
+    pub name: String,
+    /// Shell command using $LOCAL, $REMOTE and $MERGED.
+    pub cmd: String,
+    /// Stop at the first file the tool exits with a non-zero status for.
+    pub trust_exit_code: bool,
+}
+
+/// Resolve the configured diff tool.
+///
+/// Lookup order:
+/// 1. `diff.guitool` / `diff.tool` as for merge tools, then `merge.tool`
+/// 2. `difftool.<name>.cmd` → custom shell command for that tool
+/// 3. Built-in patterns for the tools git knows, run from
+///    `difftool.<name>.path` when set
+///
+/// Returns `None` when no tool is configured or the named tool is not
+/// recognised and has no custom cmd.
+pub fn resolve_diff_tool(repo: &impl GitRepo) -> Option<DiffTool> {
+    let name = tool_name(repo, "diff").or_else(|| tool_name(repo, "merge"))?;
+
+    let cmd = match repo.get_config_string(&format!("difftool.{name}.cmd")) {
+        Some(cmd) => cmd.trim().to_string(),
+        None => {
+            let (program, args) = builtin(&name)?;
+            match repo.get_config_string(&format!("difftool.{name}.path")) {
+                Some(path) => format!("{} {args}", shell_quote(path.trim())),
+                None => format!("{program} {args}"),
+            }
+        }
+    };
+    let trust_exit_code = repo
+        .get_config_string("difftool.trustExitCode")
+        .is_some_and(|v| config_bool(&v));
+    Some(DiffTool {
+        name,
+        cmd,
+        trust_exit_code,
+    })
+}
+
+/// Program and arguments of the diff tools built into git, as listed by
+/// `git difftool --tool-help`.
+fn builtin(name: &str) -> Option<(&'static str, &'static str)> {
+    Some(match name {
+        "araxis" => ("compare", "-wait -2 $LOCAL $REMOTE"),
+        "bc" | "bc3" | "bc4" => ("bcompare", "$LOCAL $REMOTE"),
+        "codecompare" => ("CodeCompare", "-W $LOCAL $REMOTE"),
+        "deltawalker" => ("DeltaWalker", "-nosplash $LOCAL $REMOTE"),
+        "diffmerge" => ("diffmerge", "$LOCAL $REMOTE"),
+        "diffuse" => ("diffuse", "$LOCAL $REMOTE"),
+        "ecmerge" => ("ecmerge", "--default --mode=diff2 $LOCAL $REMOTE"),
+        "emerge" => ("emacs", "-f emerge-files-command $LOCAL $REMOTE"),
+        "examdiff" => ("ExamDiff", "$LOCAL $REMOTE -nh"),
+        "guiffy" => ("guiffy", "$LOCAL $REMOTE"),
+        "vimdiff" | "vimdiff1" | "vimdiff2" | "vimdiff3" => ("vim", "-R -d $LOCAL $REMOTE"),
+        "gvimdiff" | "gvimdiff1" | "gvimdiff2" | "gvimdiff3" => ("gvim", "-f -R -d $LOCAL $REMOTE"),
+        "nvimdiff" | "nvimdiff1" | "nvimdiff2" | "nvimdiff3" => ("nvim", "-R -d $LOCAL $REMOTE"),
+        "kdiff3" => (
+            "kdiff3",
+            "--L1 \"$MERGED (A)\" --L2 \"$MERGED (B)\" $LOCAL $REMOTE",
+        ),
+        "kompare" => ("kompare", "$LOCAL $REMOTE"),
+        "meld" => ("meld", "$LOCAL $REMOTE"),
+        "opendiff" => ("opendiff", "$LOCAL $REMOTE"),
+        "p4merge" => ("p4merge", "$LOCAL $REMOTE"),
+        "smerge" => ("smerge", "mergetool $LOCAL $REMOTE"),
+        "tkdiff" => ("tkdiff", "$LOCAL $REMOTE"),
+        "winmerge" => ("WinMergeU", "-u -e $LOCAL $REMOTE"),
+        "xxdiff" => ("xxdiff", "$LOCAL $REMOTE"),
+        _ => return None,
+    })
+}
+
+/// Show the changes of `to_oid` in the configured diff tool: against
+/// `from_oid` when given, otherwise against its first parent.
+///
+/// Suspends the TUI before the first tool invocation and restores it after
+/// the last one, even when a tool fails.
+///
+/// Returns `true` when the tool was run, or `false` when no diff tool is
+/// configured (so the caller can show a hint).
+pub fn run_difftool(repo: &impl GitRepo, from_oid: Option<&str>, to_oid: &str) -> Result<bool> {
+    let Some(tool) = resolve_diff_tool(repo) else {
+        return Ok(false);
+    };
+
+    let files = repo.diff_file_blobs(from_oid, to_oid)?;
+    if files.is_empty() {
+        return Ok(true);
+    }
+
+    // Suspend the TUI before handing the terminal to the diff tool.
+    terminal::disable_raw_mode().context("failed to disable raw mode")?;
+    let _ = execute!(std::io::stdout(), terminal::LeaveAlternateScreen);
+
+    let result = run_for_all_files(&tool, &files);
+
+    // Restore the TUI unconditionally so the app is never left in a broken state.
+    let _ = terminal::enable_raw_mode();
+    let _ = execute!(std::io::stdout(), terminal::EnterAlternateScreen);
+
+    result?;
+    Ok(true)
+}
+
+/// Lower-level entry point that runs the tool against every file without
+/// touching the TUI. Exposed for integration tests.
+///
+/// Use [`run_difftool`] from application code — it handles TUI suspend/restore.
+#[doc(hidden)]
+pub fn run_for_all_files(tool: &DiffTool, files: &[FileBlobs]) -> Result<()> {
+    for file in files {
+        run_tool_for_file(tool, file)
+            .with_context(|| format!("diff tool failed on '{}'", file.path))?;
+    }
+    Ok(())
+}
+
+fn run_tool_for_file(tool: &DiffTool, file: &FileBlobs) -> Result<()> {
+    // Keep the original extension so tools can apply syntax highlighting.
+    let ext = Path::new(&file.path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{e}"))
+        .unwrap_or_default();
+
+    let mut local_tmp = tempfile::Builder::new()
+        .suffix(&format!(".LOCAL{ext}"))
+        .tempfile()
+        .context("failed to create LOCAL temp file")?;
+    let mut remote_tmp = tempfile::Builder::new()
+        .suffix(&format!(".REMOTE{ext}"))
+        .tempfile()
+        .context("failed to create REMOTE temp file")?;
+
+    local_tmp
+        .write_all(&file.old)
+        .context("failed to write LOCAL temp file")?;
+    remote_tmp
+        .write_all(&file.new)
+        .context("failed to write REMOTE temp file")?;
+
+    // Flush before the child process opens the files.
+    local_tmp
+        .flush()
+        .context("failed to flush LOCAL temp file")?;
+    remote_tmp
+        .flush()
+        .context("failed to flush REMOTE temp file")?;
+
+    // Run via shell so $LOCAL / $REMOTE / $MERGED expand from the env vars.
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(&tool.cmd)
+        .env("LOCAL", local_tmp.path())
+        .env("REMOTE", remote_tmp.path())
+        .env("MERGED", &file.path)
+        .env("BASE", &file.path)
+        .status()
+        .context("failed to launch diff tool")?;
+
+    // Temp files stay alive until the tool has exited.
+    drop(local_tmp);
+    drop(remote_tmp);
+
+    if tool.trust_exit_code && !status.success() {
+        anyhow::bail!("diff tool exited with {status}");
+    }
+    Ok(())
+}
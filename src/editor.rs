@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::message::MessageOptions;
 use crate::repo::GitRepo;
 
 /// Resolve the editor command to use for editing commit messages.
@@ -40,7 +41,20 @@ fn resolve_editor(repo: &impl GitRepo) -> String {
     "vi".to_string()
 }
 
-/// Open `message` in the configured editor and return the edited result.
+/// Open a commit message buffer (see [`MessageOptions::editor_text`]) in the
+/// configured editor and return the message cleaned up according to
+/// `options`. An empty result means the user aborted.
+pub fn edit_message_in_editor(
+    repo: &impl GitRepo,
+    text: &str,
+    options: &MessageOptions,
+) -> anyhow::Result<String> {
+    let edited = edit_text_in_editor(repo, text)?;
+    Ok(options.cleanup(&edited))
+}
+
+/// Open `text` in the configured editor and return the file as the user
+/// left it.
 ///
 /// Suspends the TUI (disables raw mode, leaves the alternate screen) before
 /// launching the editor, then restores it unconditionally before returning.
@@ -49,14 +63,14 @@ fn resolve_editor(repo: &impl GitRepo) -> String {
 ///
 /// The editor command may include arguments (e.g. `"emacs -nw"`) — they are
 /// split on whitespace and forwarded before the temp-file path.
-pub fn edit_message_in_editor(repo: &impl GitRepo, message: &str) -> anyhow::Result<String> {
+pub fn edit_text_in_editor(repo: &impl GitRepo, text: &str) -> anyhow::Result<String> {
     use anyhow::Context;
     use crossterm::{execute, terminal};
     use std::io::Write as _;
 
     let mut tmpfile =
         tempfile::NamedTempFile::new().context("failed to create temp file for commit message")?;
-    write!(tmpfile, "{message}").context("failed to write commit message to temp file")?;
+    write!(tmpfile, "{text}").context("failed to write commit message to temp file")?;
 
     let editor_cmd = resolve_editor(repo);
     let mut parts = editor_cmd.split_whitespace();
@@ -84,7 +98,5 @@ pub fn edit_message_in_editor(repo: &impl GitRepo, message: &str) -> anyhow::Res
         anyhow::bail!("editor exited with {status}");
     }
 
-    let edited =
-        std::fs::read_to_string(tmpfile.path()).context("failed to read edited commit message")?;
-    Ok(edited.trim().to_string() + "\n")
+    std::fs::read_to_string(tmpfile.path()).context("failed to read edited commit message")
 }
@@ -32,9 +32,43 @@ pub enum KeyCommand {
     Squash,
     Fixup,
     Reword,
+    BulkReword,
     Drop,
+    DropUpstreamed,
     Mergetool,
+    Difftool,
+    /// Mark the selected commit as the other end of a comparison.
+    ToggleMark,
     Update,
+    RangeDiff,
+    ComparePushed,
+    EditMetadata,
+    EditPlan,
+    /// Start an edit session that queues operations and applies them once.
+    EditSession,
+    /// Move the selected commit one row up or down (sequence editor).
+    MoveCommitUp,
+    MoveCommitDown,
+    /// Resolve the selected conflict region with our, their or both sides.
+    TakeOurs,
+    TakeTheirs,
+    TakeBoth,
+    /// Simulate the operation of a dialog and show the resulting stack.
+    Preview,
+    /// Retry the conflicting step with a merge strategy.
+    RetryStrategy,
+    /// Text input: insert a character.
+    InsertChar(char),
+    /// Text input: delete the character before the cursor.
+    DeleteBackward,
+    /// Text input: delete the character under the cursor.
+    DeleteForward,
+    /// Text input: add a row (e.g. a trailer).
+    AddField,
+    /// Text input: remove the focused row.
+    RemoveField,
+    /// Text input: complete the focused field from suggestions.
+    Complete,
     Quit,
     Confirm,
     None,
@@ -71,9 +105,25 @@ pub fn parse_key(event: Event) -> KeyCommand {
                 KeyCode::Char('s') => KeyCommand::Squash,
                 KeyCode::Char('f') => KeyCommand::Fixup,
                 KeyCode::Char('r') => KeyCommand::Reword,
+                KeyCode::Char('R') => KeyCommand::BulkReword,
                 KeyCode::Char('d') => KeyCommand::Drop,
+                KeyCode::Char('D') => KeyCommand::DropUpstreamed,
                 KeyCode::Char('m') => KeyCommand::Mergetool,
+                KeyCode::Char('v') => KeyCommand::Difftool,
+                KeyCode::Char(' ') => KeyCommand::ToggleMark,
                 KeyCode::Char('u') => KeyCommand::Update,
+                KeyCode::Char('c') => KeyCommand::RangeDiff,
+                KeyCode::Char('C') => KeyCommand::ComparePushed,
+                KeyCode::Char('a') => KeyCommand::EditMetadata,
+                KeyCode::Char('e') => KeyCommand::EditPlan,
+                KeyCode::Char('B') => KeyCommand::EditSession,
+                KeyCode::Char('K') => KeyCommand::MoveCommitUp,
+                KeyCode::Char('J') => KeyCommand::MoveCommitDown,
+                KeyCode::Char('o') => KeyCommand::TakeOurs,
+                KeyCode::Char('t') => KeyCommand::TakeTheirs,
+                KeyCode::Char('b') => KeyCommand::TakeBoth,
+                KeyCode::Char('x') => KeyCommand::RetryStrategy,
+                KeyCode::Char('P') => KeyCommand::Preview,
                 KeyCode::Esc | KeyCode::Char('q') => KeyCommand::Quit,
                 _ => KeyCommand::None,
             };
@@ -82,6 +132,42 @@ pub fn parse_key(event: Event) -> KeyCommand {
     KeyCommand::None
 }
 
+/// Parse a terminal event for a text-input dialog.
+///
+/// Printable characters are inserted rather than interpreted as commands;
+/// arrows move, Enter confirms, Esc cancels, Tab completes, Ctrl+N adds and
+/// Ctrl+D removes a row.
+pub fn parse_text_key(event: Event) -> KeyCommand {
+    if let Event::Key(KeyEvent {
+        code,
+        kind,
+        modifiers,
+        ..
+    }) = event
+    {
+        if kind == event::KeyEventKind::Press {
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            return match code {
+                KeyCode::Char('n') if ctrl => KeyCommand::AddField,
+                KeyCode::Char('d') if ctrl => KeyCommand::RemoveField,
+                KeyCode::Char(_) if ctrl => KeyCommand::None,
+                KeyCode::Char(c) => KeyCommand::InsertChar(c),
+                KeyCode::Backspace => KeyCommand::DeleteBackward,
+                KeyCode::Delete => KeyCommand::DeleteForward,
+                KeyCode::Tab => KeyCommand::Complete,
+                KeyCode::Up => KeyCommand::MoveUp,
+                KeyCode::Down => KeyCommand::MoveDown,
+                KeyCode::Left => KeyCommand::ScrollLeft,
+                KeyCode::Right => KeyCommand::ScrollRight,
+                KeyCode::Enter => KeyCommand::Confirm,
+                KeyCode::Esc => KeyCommand::Quit,
+                _ => KeyCommand::None,
+            };
+        }
+    }
+    KeyCommand::None
+}
+
 // Re-export commonly used types for convenience
 pub use crossterm::event::KeyCode;
 pub use crossterm::event::KeyModifiers;
@@ -595,6 +595,17 @@ impl FragMap {
             .any(|c| self.matrix[a][c] != TouchKind::None && self.matrix[b][c] != TouchKind::None)
     }
 
+    /// Clusters `commit_oid` touches in any of `paths`: the code regions a
+    /// conflict in those files while applying the commit can be in.
+    pub fn conflict_clusters(&self, commit_oid: &str, paths: &[String]) -> Vec<SpanCluster> {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.commit_oids.iter().any(|oid| oid == commit_oid))
+            .filter(|cluster| cluster.spans.iter().any(|span| paths.contains(&span.path)))
+            .cloned()
+            .collect()
+    }
+
     /// Determine the overall squash relationship between two commits.
     ///
     /// Examines every cluster both commits touch:
@@ -2314,4 +2325,32 @@ mod tests {
         let fm = build_fragmap(&commits, true);
         assert_eq!(fm.commits.len(), 2);
     }
+
+    #[test]
+    fn conflict_clusters_limited_to_commit_and_paths() {
+        let commits = vec![
+            make_commit_diff(
+                "c1",
+                vec![make_file_diff(Some("f.rs"), Some("f.rs"), 10, 2, 10, 2)],
+            ),
+            make_commit_diff(
+                "c2",
+                vec![make_file_diff(Some("g.rs"), Some("g.rs"), 1, 1, 1, 1)],
+            ),
+            make_commit_diff(
+                "c3",
+                vec![
+                    make_file_diff(Some("f.rs"), Some("f.rs"), 10, 2, 10, 2),
+                    make_file_diff(Some("h.rs"), Some("h.rs"), 1, 1, 1, 1),
+                ],
+            ),
+        ];
+        let fm = build_fragmap(&commits, true);
+
+        let clusters = fm.conflict_clusters("c3", &["f.rs".to_string()]);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].spans[0].path, "f.rs");
+        assert!(clusters[0].commit_oids.contains(&"c1".to_string()));
+        assert!(fm.conflict_clusters("c3", &["g.rs".to_string()]).is_empty());
+    }
 }
@@ -0,0 +1,167 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Run git hooks at the points where rewrites create commits.
+//
+// Git's hook contract, as far as it concerns us:
+//   - hooks live in `core.hooksPath` or `$GIT_DIR/hooks` and run only when
+//     the file is executable
+//   - they run from the top of the working tree
+//   - `prepare-commit-msg <file> [<source> [<sha>]]` and `commit-msg <file>`
+//     may rewrite the message file; a non-zero exit aborts the commit
+//   - `pre-commit` takes no arguments and checks the index, which
+//     `GIT_INDEX_FILE` may point elsewhere; a non-zero exit aborts the commit
+//   - `post-rewrite <command>` reads "<old> <new>" lines on stdin and its
+//     exit status is ignored
+//
+// Hook output is captured rather than shown, since the TUI owns the terminal.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// The hooks directory of one repository.
+#[derive(Debug, Clone)]
+pub struct Hooks {
+    dir: PathBuf,
+    workdir: PathBuf,
+}
+
+impl Hooks {
+    /// Locate the hooks directory: `hooks_path` (the `core.hooksPath`
+    /// value, relative paths taken from `workdir`) or `git_dir/hooks`.
+    pub fn new(git_dir: &Path, workdir: &Path, hooks_path: Option<&str>) -> Self {
+        let dir = match hooks_path.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+                (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+                _ => workdir.join(path),
+            },
+            None => git_dir.join("hooks"),
+        };
+        Hooks {
+            dir,
+            workdir: workdir.to_path_buf(),
+        }
+    }
+
+    /// Path of the named hook if it exists and is executable.
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        let path = self.dir.join(name);
+        is_executable(&path).then_some(path)
+    }
+
+    /// Run a hook that takes no message, such as `pre-commit`. Returns
+    /// `false` when the hook is not installed.
+    pub fn run(&self, name: &str, args: &[&str], stdin: Option<&str>) -> Result<bool> {
+        let Some(path) = self.find(name) else {
+            return Ok(false);
+        };
+        self.execute(name, &path, args, stdin, None)?;
+        Ok(true)
+    }
+
+    /// Run a hook with `GIT_INDEX_FILE` set to `index_file`, so that it
+    /// sees that index rather than the repository's. Returns `false` when
+    /// the hook is not installed.
+    pub fn run_on_index(&self, name: &str, index_file: &Path) -> Result<bool> {
+        let Some(path) = self.find(name) else {
+            return Ok(false);
+        };
+        self.execute(name, &path, &[], None, Some(index_file))?;
+        Ok(true)
+    }
+
+    /// Run a message hook (`prepare-commit-msg`, `commit-msg`) on `message`
+    /// and return the message as the hook left it. `args` follow the
+    /// message file path.
+    pub fn run_message_hook(&self, name: &str, message: &str, args: &[&str]) -> Result<String> {
+        let Some(path) = self.find(name) else {
+            return Ok(message.to_string());
+        };
+
+        let mut file = tempfile::NamedTempFile::new()
+            .with_context(|| format!("failed to create message file for {name} hook"))?;
+        file.write_all(message.as_bytes())?;
+        file.flush()?;
+
+        let file_arg = file.path().to_string_lossy().into_owned();
+        let mut all_args = vec![file_arg.as_str()];
+        all_args.extend_from_slice(args);
+        self.execute(name, &path, &all_args, None, None)?;
+
+        std::fs::read_to_string(file.path())
+            .with_context(|| format!("failed to read message back from {name} hook"))
+    }
+
+    fn execute(
+        &self,
+        name: &str,
+        path: &Path,
+        args: &[&str],
+        stdin: Option<&str>,
+        index_file: Option<&Path>,
+    ) -> Result<()> {
+        let mut command = Command::new(path);
+        if let Some(index_file) = index_file {
+            command.env("GIT_INDEX_FILE", index_file);
+        }
+        let mut child = command
+            .args(args)
+            .current_dir(&self.workdir)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("failed to run {name} hook: {e}"))?;
+        if let Some(input) = stdin {
+            // A hook may exit without reading its input; that is not an error.
+            let _ = child
+                .stdin
+                .take()
+                .expect("stdin is piped")
+                .write_all(input.as_bytes());
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match stderr
+                .lines()
+                .chain(stdout.lines())
+                .find(|l| !l.trim().is_empty())
+            {
+                Some(line) => bail!("{name} hook failed: {}", line.trim()),
+                None => bail!("{name} hook failed with {}", output.status),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
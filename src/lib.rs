@@ -15,11 +15,24 @@
 // Core library for git-tailor
 
 pub mod app;
+pub mod bulk_reword;
+pub mod difftool;
 pub mod editor;
 pub mod event;
 pub mod fragmap;
+pub mod hooks;
+pub mod lint;
+pub mod merge3;
 pub mod mergetool;
+pub mod message;
+pub mod metadata;
+pub mod range_diff;
 pub mod repo;
+pub mod rerere;
+pub mod session;
+pub mod signing;
+pub mod stack_compare;
+pub mod todo;
 pub mod views;
 
 /// Represents commit metadata extracted from git repository.
@@ -49,6 +62,26 @@ pub struct CommitInfo {
     pub commit_date: Option<time::OffsetDateTime>,
 }
 
+#[cfg(test)]
+impl CommitInfo {
+    /// A commit with just an OID and a message, for unit tests.
+    pub(crate) fn with_message(oid: &str, message: &str) -> Self {
+        CommitInfo {
+            oid: oid.to_string(),
+            summary: message.lines().next().unwrap_or("").to_string(),
+            author: None,
+            author_email: None,
+            date: None,
+            author_date: None,
+            committer: None,
+            committer_email: None,
+            commit_date: None,
+            parent_oids: Vec::new(),
+            message: message.to_string(),
+        }
+    }
+}
+
 /// The kind of change a diff line represents.
 ///
 /// When Git compares two versions of a file, each line in the output falls
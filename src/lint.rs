@@ -0,0 +1,246 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Commit message lint rules.
+//
+// Rules are read from git config, falling back to the `[lint]` table of a
+// `.git-tailor.toml` at the top of the working tree:
+//
+//   git config                      .git-tailor.toml
+//   tailor.lint.maxSubjectLength    max_subject_length = 72
+//   tailor.lint.blankSecondLine     blank_second_line = true
+//   tailor.lint.conventional        conventional = true
+//   tailor.lint.conventionalTypes   conventional_types = ["feat", "fix"]
+//
+// With nothing configured no rule is active.
+
+use crate::repo::GitRepo;
+use std::collections::HashMap;
+
+/// Name of the per-repository settings file.
+pub const SETTINGS_FILE: &str = ".git-tailor.toml";
+
+/// Active lint rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    /// Longest allowed subject line, in characters.
+    pub max_subject_length: Option<usize>,
+    /// Require the line after the subject to be empty.
+    pub blank_second_line: bool,
+    /// Require `type(scope)!: description` subjects.
+    pub conventional: bool,
+    /// Allowed conventional-commit types; empty allows any type.
+    pub conventional_types: Vec<String>,
+}
+
+impl LintConfig {
+    /// Read the rules for `repo`. Git config values win over the settings
+    /// file; values that do not parse are ignored.
+    pub fn from_repo(repo: &impl GitRepo) -> Self {
+        let file = repo
+            .workdir()
+            .and_then(|dir| std::fs::read_to_string(dir.join(SETTINGS_FILE)).ok())
+            .map(|text| parse_toml_table(&text, "lint"))
+            .unwrap_or_default();
+        let value = |config_key: &str, file_key: &str| {
+            repo.get_config_string(&format!("tailor.lint.{config_key}"))
+                .or_else(|| file.get(file_key).cloned())
+        };
+
+        LintConfig {
+            max_subject_length: value("maxSubjectLength", "max_subject_length")
+                .and_then(|v| v.trim().parse().ok()),
+            blank_second_line: value("blankSecondLine", "blank_second_line")
+                .is_some_and(|v| parse_bool(&v)),
+            conventional: value("conventional", "conventional").is_some_and(|v| parse_bool(&v)),
+            conventional_types: value("conventionalTypes", "conventional_types")
+                .map(|v| {
+                    v.split(',')
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether any rule is active.
+    pub fn is_enabled(&self) -> bool {
+        self.max_subject_length.is_some() || self.blank_second_line || self.conventional
+    }
+
+    /// Check a commit message and describe each violated rule.
+    pub fn check(&self, message: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut lines = message.lines();
+        let subject = lines.next().unwrap_or("");
+
+        if let Some(max) = self.max_subject_length {
+            let length = subject.chars().count();
+            if length > max {
+                warnings.push(format!("Subject is {length} characters, limit is {max}"));
+            }
+        }
+        if self.blank_second_line && lines.next().is_some_and(|l| !l.trim().is_empty()) {
+            warnings.push("Second line is not blank".to_string());
+        }
+        if self.conventional {
+            match conventional_type(subject) {
+                None => warnings.push("Subject is not 'type(scope): description'".to_string()),
+                Some(kind)
+                    if !self.conventional_types.is_empty()
+                        && !self.conventional_types.iter().any(|t| t == kind) =>
+                {
+                    warnings.push(format!(
+                        "Type '{kind}' is not one of: {}",
+                        self.conventional_types.join(", ")
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        warnings
+    }
+}
+
+/// The type of a conventional-commit subject (`feat(parser)!: add x`), or
+/// `None` when the subject does not follow the format.
+fn conventional_type(subject: &str) -> Option<&str> {
+    let (prefix, description) = subject.split_once(": ")?;
+    if description.trim().is_empty() {
+        return None;
+    }
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let kind = match prefix.split_once('(') {
+        Some((kind, scope)) => {
+            let scope = scope.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            kind
+        }
+        None => prefix,
+    };
+    let valid = !kind.is_empty()
+        && kind
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    valid.then_some(kind)
+}
+
+fn parse_bool(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "on" | "1"
+    )
+}
+
+/// Read the `key = value` pairs of one table of a simple TOML file.
+///
+/// Only what the settings file needs is understood: strings, integers,
+/// booleans and single-line arrays of strings, which are returned joined
+/// with commas.
+fn parse_toml_table(text: &str, table: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut in_table = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_table = name.trim() == table;
+            continue;
+        }
+        if !in_table {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(items) => items
+                .split(',')
+                .map(|item| item.trim().trim_matches('"'))
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>()
+                .join(","),
+            None => value.trim_matches('"').to_string(),
+        };
+        values.insert(key.trim().to_string(), value);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_rules() -> LintConfig {
+        LintConfig {
+            max_subject_length: Some(30),
+            blank_second_line: true,
+            conventional: true,
+            conventional_types: vec!["feat".to_string(), "fix".to_string()],
+        }
+    }
+
+    #[test]
+    fn clean_message_passes() {
+        assert!(all_rules()
+            .check("fix(parser): handle EOF\n\nBody.\n")
+            .is_empty());
+        assert!(LintConfig::default()
+            .check("anything goes\nhere")
+            .is_empty());
+    }
+
+    #[test]
+    fn each_rule_reports_a_warning() {
+        assert_eq!(
+            all_rules().check("docs: explain the parser in detail\nno blank line\n"),
+            vec![
+                "Subject is 34 characters, limit is 30",
+                "Second line is not blank",
+                "Type 'docs' is not one of: feat, fix",
+            ]
+        );
+        assert_eq!(
+            all_rules().check("Fix the parser\n"),
+            vec!["Subject is not 'type(scope): description'"]
+        );
+    }
+
+    #[test]
+    fn conventional_subjects() {
+        assert_eq!(conventional_type("feat!: drop v1 API"), Some("feat"));
+        assert_eq!(conventional_type("feat(api)!: drop v1"), Some("feat"));
+        assert_eq!(conventional_type("Feat: x"), None);
+        assert_eq!(conventional_type("feat(): x"), None);
+        assert_eq!(conventional_type("feat:x"), None);
+    }
+
+    #[test]
+    fn toml_table_values() {
+        let text = "[other]\nmax_subject_length = 10\n\n[lint]\n\
+                    max_subject_length = 72 \n\
+                    conventional = true\n\
+                    conventional_types = [\"feat\", \"fix\"]\n";
+        let values = parse_toml_table(text, "lint");
+        assert_eq!(values["max_subject_length"], "72");
+        assert_eq!(values["conventional"], "true");
+        assert_eq!(values["conventional_types"], "feat,fix");
+    }
+}
@@ -14,16 +14,27 @@
 
 // TUI application entry point
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use git_tailor::repo::{Git2Repo, GitRepo, RebaseOutcome};
+use git_tailor::repo::{Git2Repo, GitRepo, MergeStrategy, PlanStep, PushedScope, RebaseOutcome};
 use git_tailor::{
-    app::{AppAction, AppMode, AppState, SplitStrategy},
-    editor, event, fragmap, mergetool, views, CommitDiff, CommitInfo,
+    app::{
+        AppAction, AppMode, AppState, ConflictResolveState, MetadataEditState, PreviewOp,
+        SplitStrategy, StackPreview,
+    },
+    bulk_reword, difftool, editor, event, fragmap,
+    lint::LintConfig,
+    merge3::{self, MergeChunk, Resolution},
+    mergetool,
+    message::MessageOptions,
+    metadata, range_diff,
+    session::{EditSession, SessionOp},
+    stack_compare::{self, StackCommit, StackRow},
+    todo, views, CommitDiff, CommitInfo,
 };
 use ratatui::{
     backend::CrosstermBackend,
@@ -33,14 +44,20 @@ use ratatui::{
     widgets::Paragraph,
     Terminal,
 };
+use std::collections::HashSet;
 use std::io;
+use std::path::{Path, PathBuf};
 
 /// Interactive TUI for working with Git commits.
 #[derive(Parser)]
 #[command(name = "gt")]
 struct Cli {
     /// A commit-ish to use as the base reference (branch, tag, or hash).
-    commit_ish: String,
+    ///
+    /// When omitted, the current branch's upstream is used. If there is no
+    /// upstream, falls back to `origin/HEAD`, then the local branch named by
+    /// `init.defaultBranch`, then the `tailor.base` git config value.
+    commit_ish: Option<String>,
 
     /// Display commits in reverse order (HEAD at top).
     #[arg(short, long)]
@@ -54,6 +71,42 @@ struct Cli {
     /// the cluster layout.
     #[arg(short = 'f', long)]
     full: bool,
+
+    /// Use the fork point instead of the plain merge-base as reference.
+    ///
+    /// Like `git merge-base --fork-point`, consults the reflog of the base
+    /// reference to find where the branch really forked. This avoids picking
+    /// up upstream commits that were rewritten after the branch was created.
+    /// Falls back to the merge-base when no fork point can be determined.
+    #[arg(long)]
+    fork_point: bool,
+
+    /// Skip the pre-commit and commit-msg hooks when creating commits.
+    #[arg(long)]
+    no_verify: bool,
+
+    /// Use the author date as committer date of rewritten commits instead
+    /// of the current time, like `git rebase --committer-date-is-author-date`.
+    #[arg(long)]
+    committer_date_is_author_date: bool,
+
+    /// Settle conflicting hunks of replayed commits automatically, like
+    /// `git rebase -X`: ours, theirs, ignore-all-space (or
+    /// ignore-whitespace) or ignore-space-change.
+    #[arg(short = 'X', long, value_name = "OPTION", value_parser = parse_strategy_option)]
+    strategy_option: Option<MergeStrategy>,
+
+    /// Act as the sequence editor of `git rebase -i`: arrange the todo
+    /// list in FILE with the fragmap and write it back for git to run.
+    ///
+    /// Use as `GIT_SEQUENCE_EDITOR="gt --sequence-editor"`. Enter writes
+    /// the todo list; quitting without writing makes git abort the rebase.
+    #[arg(long, value_name = "FILE")]
+    sequence_editor: Option<PathBuf>,
+}
+
+fn parse_strategy_option(option: &str) -> Result<MergeStrategy, String> {
+    MergeStrategy::from_option(option).ok_or_else(|| format!("unknown strategy option '{option}'"))
 }
 
 /// Compute fragmap from a list of regular commits plus any pre-computed extra diffs.
@@ -85,28 +138,19 @@ fn compute_fragmap(
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let git_repo = Git2Repo::open(std::env::current_dir()?)?;
-    let reference_oid = git_repo.find_reference_point(&cli.commit_ish)?;
-    let head_oid = git_repo.head_oid()?;
-
-    let commits = git_repo.list_commits(&head_oid, &reference_oid)?;
-
-    // Exclude the merge-base commit - it's shared with the target branch
-    // and must not be modified (squashed, moved, or split)
-    let commits: Vec<CommitInfo> = commits
-        .into_iter()
-        .filter(|c| c.oid != reference_oid)
-        .collect();
-
-    // Handle edge case: HEAD is at merge-base (no commits on current branch)
-    if commits.is_empty() {
-        eprintln!(
-            "No commits to display: HEAD is at the merge-base with '{}'",
-            cli.commit_ish
-        );
-        eprintln!("The current branch has no commits beyond the common ancestor.");
+    let mut git_repo = Git2Repo::open(std::env::current_dir()?)?;
+    git_repo.set_verify(!cli.no_verify);
+    git_repo.set_committer_date_is_author_date(cli.committer_date_is_author_date);
+    git_repo.set_merge_strategy(cli.strategy_option.unwrap_or_default());
+    let app = match &cli.sequence_editor {
+        Some(path) => sequence_editor_app(&git_repo, path, &cli)?,
+        None => commit_list_app(&git_repo, &cli)?,
+    };
+    let Some(mut app) = app else {
         return Ok(());
-    }
+    };
+    // Set when the user leaves the sequence editor without writing.
+    let mut todo_aborted = false;
 
     enable_raw_mode()?;
     let mut stderr = io::stderr();
@@ -114,28 +158,6 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stderr);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = AppState::with_commits(commits);
-    app.reverse = cli.reverse;
-    app.reference_oid = reference_oid;
-
-    // Append staged/unstaged working-tree changes as synthetic rows at the
-    // bottom of the commit list (newest position). Recompute fragmap with
-    // the extra diffs so their hunk overlap with commits is visible.
-    let mut extra_diffs: Vec<CommitDiff> = Vec::new();
-    if let Some(d) = git_repo.staged_diff() {
-        extra_diffs.push(d);
-    }
-    if let Some(d) = git_repo.unstaged_diff() {
-        extra_diffs.push(d);
-    }
-    let n_regular = app.commits.len();
-    for d in &extra_diffs {
-        app.commits.push(d.commit.clone());
-    }
-    app.full_fragmap = cli.full;
-    app.fragmap = compute_fragmap(&git_repo, &app.commits[..n_regular], &extra_diffs, cli.full);
-    app.selection_index = select_initial_index(&app.commits);
-
     loop {
         terminal.draw(|frame| {
             let mode = app.mode.clone();
@@ -143,7 +165,11 @@ fn main() -> Result<()> {
         })?;
 
         let event = event::read()?;
-        let action = event::parse_key(event);
+        let action = if app.mode.takes_text_input() {
+            event::parse_text_key(event)
+        } else {
+            event::parse_key(event)
+        };
 
         app.clear_status_message();
 
@@ -155,16 +181,137 @@ fn main() -> Result<()> {
             AppMode::SplitSelect { .. } => views::split_select::handle_key(action, &mut app),
             AppMode::SplitConfirm(_) => views::split_select::handle_confirm_key(action, &mut app),
             AppMode::DropConfirm(_) => views::drop::handle_confirm_key(action, &mut app),
+            AppMode::DropUpstreamedConfirm(_) => {
+                views::drop::handle_upstreamed_confirm_key(action, &mut app)
+            }
+            AppMode::PushedConfirm(_) => views::pushed::handle_confirm_key(action, &mut app),
+            AppMode::RangeDiff { .. } => views::range_diff::handle_key(action, &mut app),
+            AppMode::StackCompare { .. } => views::stack_compare::handle_key(action, &mut app),
             AppMode::RebaseConflict(_) => views::conflict::handle_conflict_key(action, &mut app),
+            AppMode::StrategySelect { .. } => views::strategy_select::handle_key(action, &mut app),
+            AppMode::Preview(_) => views::preview::handle_key(action, &mut app),
             AppMode::SquashSelect { .. } => views::squash_select::handle_key(action, &mut app),
+            AppMode::MetadataEdit(_) => views::metadata::handle_key(action, &mut app),
+            AppMode::SequenceEdit => views::sequence_edit::handle_key(action, &mut app),
+            AppMode::EditSession => views::edit_session::handle_key(action, &mut app),
+            AppMode::ConflictResolve(_) => views::conflict_resolve::handle_key(action, &mut app),
             AppMode::Help(_) => views::help::handle_key(action, &mut app),
         };
 
+        let Some(result) = confirm_or_run(&mut app, result) else {
+            continue;
+        };
+
         // Handle side effects that require git operations or terminal access.
         match result {
             AppAction::Handled => {}
+            // Unwrapped above.
+            AppAction::ConfirmedPushed(_) => {}
             AppAction::Quit => app.should_quit = true,
             AppAction::ReloadCommits => reload_commits(&git_repo, &mut app),
+            AppAction::RefreshFragmap => {
+                app.fragmap = compute_fragmap(&git_repo, &app.commits, &[], app.full_fragmap);
+            }
+            AppAction::WriteTodo => {
+                let Some(path) = &cli.sequence_editor else {
+                    continue;
+                };
+                match todo::write_todo(&app.commits, &app.todo_commands) {
+                    Ok(text) => match std::fs::write(path, text) {
+                        Ok(()) => app.should_quit = true,
+                        Err(e) => app.set_error_message(format!("Failed to write todo: {e}")),
+                    },
+                    Err(e) => app.set_error_message(e),
+                }
+            }
+            AppAction::AbortTodo => {
+                todo_aborted = true;
+                app.should_quit = true;
+            }
+            AppAction::RefreshSession => refresh_session_fragmap(&git_repo, &mut app),
+            AppAction::QueueReword {
+                commit_oid,
+                current_message,
+            } => {
+                let options = MessageOptions::from_repo(&git_repo, Some(&current_message));
+                let edited = git_repo
+                    .prepare_commit_message(
+                        &options.editor_text(&current_message),
+                        "commit",
+                        Some(&commit_oid),
+                    )
+                    .and_then(|prepared| {
+                        editor::edit_message_in_editor(&git_repo, &prepared, &options)
+                    });
+                terminal.clear()?;
+                match edited {
+                    Err(e) => app.set_error_message(format!("Reword failed: {e}")),
+                    Ok(message) if message.trim().is_empty() => {
+                        app.set_error_message("Reword aborted: empty commit message")
+                    }
+                    Ok(message) if message.trim_end() == current_message.trim_end() => {}
+                    Ok(message) => {
+                        app.queue_session_op(SessionOp::Reword {
+                            oid: commit_oid,
+                            message,
+                        });
+                    }
+                }
+            }
+            AppAction::QueueSquash {
+                source_oid,
+                target_oid,
+                source_message,
+                target_message,
+            } => {
+                let options = MessageOptions::from_repo(&git_repo, None);
+                let combined = options.squash_message(&[&target_message, &source_message]);
+                let edited = git_repo
+                    .prepare_commit_message(&options.editor_text(&combined), "squash", None)
+                    .and_then(|prepared| {
+                        editor::edit_message_in_editor(&git_repo, &prepared, &options)
+                    });
+                terminal.clear()?;
+                match edited {
+                    Err(e) => app.set_error_message(format!("Squash failed: {e}")),
+                    Ok(message) if message.trim().is_empty() => {
+                        app.set_error_message("Squash aborted: empty commit message")
+                    }
+                    Ok(message) => {
+                        let op = SessionOp::Squash {
+                            source: source_oid,
+                            target: target_oid,
+                            message,
+                        };
+                        if app.queue_session_op(op) {
+                            refresh_session_fragmap(&git_repo, &mut app);
+                        }
+                    }
+                }
+            }
+            AppAction::ShowRangeDiff => {
+                let current: Vec<CommitInfo> = app
+                    .commits
+                    .iter()
+                    .filter(|c| c.oid != "staged" && c.oid != "unstaged")
+                    .cloned()
+                    .collect();
+                let diffs = |commits: &[CommitInfo]| {
+                    commits
+                        .iter()
+                        .map(|c| git_repo.commit_diff(&c.oid))
+                        .collect::<Result<Vec<CommitDiff>>>()
+                };
+                match diffs(&app.original_commits).and_then(|old| Ok((old, diffs(&current)?))) {
+                    Ok((old, new)) => app.enter_range_diff(range_diff::range_diff(&old, &new)),
+                    Err(e) => app.set_error_message(format!("Range diff failed: {e}")),
+                }
+            }
+            AppAction::ComparePushed => match compare_with_pushed(&git_repo, &app) {
+                Ok(Some((remote, rows))) => app.enter_stack_compare(remote, rows),
+                Ok(None) => app.set_error_message("Branch has no pushed version to compare with"),
+                Err(e) => app.set_error_message(format!("Compare failed: {e}")),
+            },
             AppAction::PrepareSplit {
                 strategy,
                 commit_oid,
@@ -234,6 +381,35 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            AppAction::PrepareDropUpstreamedConfirm {
+                commit_oids,
+                commit_summaries,
+            } => {
+                let head_oid = match git_repo.head_oid() {
+                    Ok(oid) => oid,
+                    Err(e) => {
+                        app.set_error_message(format!("Failed to get HEAD: {e}"));
+                        continue;
+                    }
+                };
+                app.enter_drop_upstreamed_confirm(commit_oids, commit_summaries, head_oid);
+            }
+            AppAction::ExecuteDropUpstreamed {
+                commit_oids,
+                head_oid,
+            } => match git_repo.drop_commits(&commit_oids, &head_oid) {
+                Ok(RebaseOutcome::Complete) => {
+                    reload_commits(&git_repo, &mut app);
+                    let count = commit_oids.len();
+                    app.set_success_message(format!("Dropped {count} upstreamed commit(s)"));
+                }
+                Ok(RebaseOutcome::Conflict(state)) => {
+                    app.enter_rebase_conflict(*state);
+                }
+                Err(e) => {
+                    app.set_error_message(format!("Drop failed: {e}"));
+                }
+            },
             AppAction::RebaseContinue(state) => {
                 // Squash-time tree conflict: the user has resolved the
                 // combined tree. Open the editor for the commit message,
@@ -249,13 +425,19 @@ fn main() -> Result<()> {
                     if !conflict_files.is_empty() {
                         app.enter_rebase_conflict(git_tailor::repo::ConflictState {
                             conflicting_files: conflict_files,
+                            conflict_kinds: git_repo.read_conflict_kinds(),
                             still_unresolved: true,
                             ..state
                         });
                         continue;
                     }
 
-                    let editor_result = editor::edit_message_in_editor(&git_repo, &combined);
+                    let options = MessageOptions::from_repo(&git_repo, None);
+                    let editor_result = git_repo
+                        .prepare_commit_message(&options.editor_text(&combined), "squash", None)
+                        .and_then(|prepared| {
+                            editor::edit_message_in_editor(&git_repo, &prepared, &options)
+                        });
                     terminal.clear()?;
                     match editor_result {
                         Err(e) => {
@@ -272,10 +454,9 @@ fn main() -> Result<()> {
                             let saved_index = app.selection_index;
                             match git_repo.squash_finalize(&ctx_clone, &msg, &original_oid) {
                                 Ok(RebaseOutcome::Complete) => {
-                                    reload_commits(&git_repo, &mut app);
+                                    reload_after_rewrite(&git_repo, &mut app, "Commits squashed");
                                     app.selection_index =
                                         saved_index.min(app.commits.len().saturating_sub(1));
-                                    app.set_success_message("Commits squashed");
                                 }
                                 Ok(RebaseOutcome::Conflict(new_state)) => {
                                     app.enter_rebase_conflict(*new_state);
@@ -292,10 +473,10 @@ fn main() -> Result<()> {
                 let saved_index = app.selection_index;
                 match git_repo.rebase_continue(&state) {
                     Ok(RebaseOutcome::Complete) => {
-                        reload_commits(&git_repo, &mut app);
-                        app.selection_index = saved_index.min(app.commits.len().saturating_sub(1));
                         let label = state.operation_label.to_lowercase();
-                        app.set_success_message(format!("Commit {label} complete"));
+                        let message = format!("Commit {label} complete");
+                        reload_after_rewrite(&git_repo, &mut app, &message);
+                        app.selection_index = saved_index.min(app.commits.len().saturating_sub(1));
                     }
                     Ok(RebaseOutcome::Conflict(new_state)) => {
                         app.enter_rebase_conflict(*new_state);
@@ -305,6 +486,28 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            AppAction::RebaseSkip(state) => {
+                let saved_index = app.selection_index;
+                match git_repo.rebase_skip(&state) {
+                    Ok(RebaseOutcome::Complete) => {
+                        let label = state.operation_label.to_lowercase();
+                        let short = state
+                            .conflicting_commit_oid
+                            .get(..10)
+                            .unwrap_or(&state.conflicting_commit_oid);
+                        let message = format!("Commit {label} complete, skipped {short}");
+                        reload_after_rewrite(&git_repo, &mut app, &message);
+                        app.selection_index = saved_index.min(app.commits.len().saturating_sub(1));
+                    }
+                    Ok(RebaseOutcome::Conflict(new_state)) => {
+                        app.enter_rebase_conflict(*new_state);
+                    }
+                    Err(e) => {
+                        app.enter_rebase_conflict(state);
+                        app.set_error_message(format!("Skip failed: {e}"));
+                    }
+                }
+            }
             AppAction::RebaseAbort(state) => match git_repo.rebase_abort(&state) {
                 Ok(()) => {
                     reload_commits(&git_repo, &mut app);
@@ -315,6 +518,60 @@ fn main() -> Result<()> {
                     app.set_error_message(format!("Abort failed: {e}"));
                 }
             },
+            AppAction::PrepareConflictResolve(conflict) => {
+                let Some(path) = conflict
+                    .conflicting_files
+                    .iter()
+                    .find(|path| conflict.kind(path).is_text())
+                    .cloned()
+                else {
+                    app.set_error_message("No text conflicts to resolve here");
+                    continue;
+                };
+                match merge3::merge_index_stages(&git_repo, &path) {
+                    Err(e) => app.set_error_message(format!("Cannot resolve here: {e}")),
+                    Ok(chunks) => {
+                        let state = ConflictResolveState::new(conflict, path, chunks);
+                        if state.region_count() > 0 {
+                            app.enter_conflict_resolve(state);
+                        } else {
+                            // Line by line the sides do not overlap.
+                            write_resolution(&git_repo, &mut app, &state);
+                        }
+                    }
+                }
+            }
+            AppAction::EditConflictRegion => {
+                let AppMode::ConflictResolve(ref state) = app.mode else {
+                    continue;
+                };
+                let Some(text) = state.selected_chunk().and_then(|i| match &state.chunks[i] {
+                    MergeChunk::Conflict(region) => Some(region.marked_text()),
+                    MergeChunk::Resolved(_) => None,
+                }) else {
+                    continue;
+                };
+                let editor_result = editor::edit_text_in_editor(&git_repo, &text);
+                terminal.clear()?;
+                match editor_result.map(|text| merge3::split_lines(&text)) {
+                    Err(e) => app.set_error_message(format!("Editor error: {e}")),
+                    Ok(lines) if merge3::has_conflict_markers(&lines) => {
+                        app.set_error_message("Conflict markers left in the edited region")
+                    }
+                    Ok(lines) => {
+                        if let AppMode::ConflictResolve(ref mut state) = app.mode {
+                            if let Some(region) = state.selected_region_mut() {
+                                region.resolution = Some(Resolution::Edited(lines));
+                            }
+                        }
+                    }
+                }
+            }
+            AppAction::WriteResolution => {
+                if let AppMode::ConflictResolve(state) = app.mode.clone() {
+                    write_resolution(&git_repo, &mut app, &state);
+                }
+            }
             AppAction::RunMergetool {
                 files,
                 conflict_state,
@@ -326,6 +583,7 @@ fn main() -> Result<()> {
                         let new_files = git_repo.read_conflicting_files();
                         app.mode = AppMode::RebaseConflict(git_tailor::repo::ConflictState {
                             conflicting_files: new_files,
+                            conflict_kinds: git_repo.read_conflict_kinds(),
                             still_unresolved: false,
                             ..conflict_state
                         });
@@ -343,6 +601,73 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            AppAction::TakeConflictSide {
+                path,
+                side,
+                conflict_state,
+            } => match git_repo.take_conflict_side(&path, side) {
+                Ok(()) => {
+                    app.enter_rebase_conflict(git_tailor::repo::ConflictState {
+                        conflicting_files: git_repo.read_conflicting_files(),
+                        conflict_kinds: git_repo.read_conflict_kinds(),
+                        still_unresolved: false,
+                        ..conflict_state
+                    });
+                    app.set_success_message(format!("Resolved {path}"));
+                }
+                Err(e) => app.set_error_message(format!("Failed to resolve {path}: {e}")),
+            },
+            AppAction::Preview(op) => match preview_stack(&git_repo, &app, &op) {
+                Ok(preview) => app.enter_preview(preview),
+                Err(e) => app.set_error_message(format!("Preview failed: {e}")),
+            },
+            AppAction::RetryConflict {
+                strategy,
+                conflict_state,
+            } => {
+                let result = git_repo.retry_conflicts(strategy);
+                app.enter_rebase_conflict(git_tailor::repo::ConflictState {
+                    conflicting_files: git_repo.read_conflicting_files(),
+                    conflict_kinds: git_repo.read_conflict_kinds(),
+                    still_unresolved: false,
+                    ..conflict_state
+                });
+                let left = match &app.mode {
+                    AppMode::RebaseConflict(state) => state.conflicting_files.len(),
+                    _ => 0,
+                };
+                match result {
+                    Ok(resolved) if resolved.is_empty() => app.set_error_message(format!(
+                        "{} did not resolve any conflicts",
+                        strategy.label()
+                    )),
+                    Ok(_) if left == 0 => app.set_success_message(format!(
+                        "Resolved with {} — press Enter to continue",
+                        strategy.label()
+                    )),
+                    Ok(resolved) => app.set_success_message(format!(
+                        "{} resolved {} file(s), {left} still conflicting",
+                        strategy.label(),
+                        resolved.len()
+                    )),
+                    Err(e) => app.set_error_message(format!("Failed to retry merge: {e}")),
+                }
+            }
+            AppAction::RunDifftool { from_oid, to_oid } => {
+                let result = difftool::run_difftool(&git_repo, from_oid.as_deref(), &to_oid);
+                terminal.clear()?;
+                match result {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        app.set_error_message(
+                            "No diff tool configured (set diff.tool in git config)",
+                        );
+                    }
+                    Err(e) => {
+                        app.set_error_message(format!("Diff tool failed: {e}"));
+                    }
+                }
+            }
             AppAction::PrepareReword {
                 commit_oid,
                 current_message,
@@ -354,16 +679,31 @@ fn main() -> Result<()> {
                         continue;
                     }
                 };
-                let editor_result = editor::edit_message_in_editor(&git_repo, &current_message);
+                let options = MessageOptions::from_repo(&git_repo, Some(&current_message));
+                let prepared = match git_repo.prepare_commit_message(
+                    &options.editor_text(&current_message),
+                    "commit",
+                    Some(&commit_oid),
+                ) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        app.set_error_message(format!("Reword failed: {e}"));
+                        continue;
+                    }
+                };
+                let editor_result = editor::edit_message_in_editor(&git_repo, &prepared, &options);
                 terminal.clear()?;
                 match editor_result {
                     Err(e) => app.set_error_message(format!("Editor error: {e}")),
-                    Ok(new_message) if new_message == current_message => {}
+                    Ok(new_message) if new_message.trim().is_empty() => {
+                        app.set_error_message("Reword aborted: empty commit message")
+                    }
+                    Ok(new_message) if new_message.trim_end() == current_message.trim_end() => {}
                     Ok(new_message) => {
                         let saved_index = app.selection_index;
                         match git_repo.reword_commit(&commit_oid, &new_message, &head_oid) {
                             Ok(()) => {
-                                reload_commits(&git_repo, &mut app);
+                                reload_after_rewrite(&git_repo, &mut app, "Commit reworded");
                                 app.selection_index =
                                     saved_index.min(app.commits.len().saturating_sub(1));
                             }
@@ -372,6 +712,133 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            AppAction::PrepareBulkReword => {
+                let commits: Vec<CommitInfo> = app
+                    .commits
+                    .iter()
+                    .filter(|c| c.oid != "staged" && c.oid != "unstaged")
+                    .cloned()
+                    .collect();
+                let all_messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+                let options = MessageOptions::from_repo(&git_repo, Some(&all_messages.join("\n")));
+                let buffer = bulk_reword::format_buffer(&commits, &options);
+                let editor_result = editor::edit_text_in_editor(&git_repo, &buffer);
+                terminal.clear()?;
+                let edited = match editor_result {
+                    Ok(edited) => edited,
+                    Err(e) => {
+                        app.set_error_message(format!("Editor error: {e}"));
+                        continue;
+                    }
+                };
+                match bulk_reword::parse_buffer(&edited, &commits, &options) {
+                    Err(errors) => {
+                        app.set_error_message(format!("Reword failed: {}", errors.join("; ")))
+                    }
+                    Ok(messages) if messages.is_empty() => {}
+                    Ok(messages) => {
+                        // The changed commits are only known now, so the
+                        // pushed check happens here rather than up front.
+                        let action = AppAction::ApplyBulkReword { messages };
+                        if let Some(AppAction::ApplyBulkReword { messages }) =
+                            confirm_or_run(&mut app, action)
+                        {
+                            apply_bulk_reword(&git_repo, &mut app, &messages);
+                        }
+                    }
+                }
+            }
+            AppAction::ApplyBulkReword { messages } => {
+                apply_bulk_reword(&git_repo, &mut app, &messages);
+            }
+            AppAction::PrepareEditPlan => {
+                let commits = app.commits.clone();
+                let comment = MessageOptions::from_repo(&git_repo, None).comment;
+                let todo_text = todo::format_todo(&commits, &comment);
+                let editor_result = editor::edit_text_in_editor(&git_repo, &todo_text);
+                terminal.clear()?;
+                let edited = match editor_result {
+                    Ok(edited) => edited,
+                    Err(e) => {
+                        app.set_error_message(format!("Editor error: {e}"));
+                        continue;
+                    }
+                };
+                let lines = match todo::parse_todo(&edited, &commits, &comment) {
+                    Ok(lines) => lines,
+                    Err(errors) => {
+                        app.set_error_message(format!("Plan failed: {}", errors.join("; ")));
+                        continue;
+                    }
+                };
+                if lines.is_empty() {
+                    app.set_error_message("Plan aborted: nothing to do");
+                    continue;
+                }
+                if todo::is_unchanged(&lines, &commits) {
+                    continue;
+                }
+                let first_oid = match todo::first_rewritten(&lines, &commits) {
+                    Some(oid) => oid.to_string(),
+                    // Only exec lines were appended after the stack.
+                    None => commits[commits.len() - 1].oid.clone(),
+                };
+                let options = MessageOptions::from_repo(&git_repo, None);
+                let plan = todo::build_plan(&lines, &commits, &options, |message, source, oid| {
+                    let options = MessageOptions::from_repo(&git_repo, Some(message));
+                    let prepared = git_repo.prepare_commit_message(
+                        &options.editor_text(message),
+                        source,
+                        oid,
+                    )?;
+                    let edited = editor::edit_message_in_editor(&git_repo, &prepared, &options);
+                    terminal.clear()?;
+                    edited
+                });
+                let steps = match plan {
+                    Ok(steps) => steps,
+                    Err(e) => {
+                        app.set_error_message(format!("Plan aborted: {e}"));
+                        continue;
+                    }
+                };
+                let action = AppAction::ExecutePlan { steps, first_oid };
+                if let Some(AppAction::ExecutePlan { steps, .. }) = confirm_or_run(&mut app, action)
+                {
+                    execute_plan(&git_repo, &mut app, &steps);
+                }
+            }
+            AppAction::ExecutePlan { steps, .. } => {
+                execute_plan(&git_repo, &mut app, &steps);
+            }
+            AppAction::PrepareMetadataEdit { commit_oid } => {
+                let Some(commit) = app.commits.iter().find(|c| c.oid == commit_oid) else {
+                    continue;
+                };
+                let suggestions = metadata::author_suggestions(&git_repo, &app.commits);
+                let state = MetadataEditState::new(commit, suggestions);
+                app.enter_metadata_edit(state);
+            }
+            AppAction::ApplyMetadata {
+                commit_oid,
+                metadata,
+            } => {
+                let head_oid = match git_repo.head_oid() {
+                    Ok(oid) => oid,
+                    Err(e) => {
+                        app.set_error_message(format!("Failed to get HEAD: {e}"));
+                        continue;
+                    }
+                };
+                let saved_index = app.selection_index;
+                match git_repo.edit_commit_metadata(&commit_oid, &metadata, &head_oid) {
+                    Ok(()) => {
+                        reload_after_rewrite(&git_repo, &mut app, "Commit metadata updated");
+                        app.selection_index = saved_index.min(app.commits.len().saturating_sub(1));
+                    }
+                    Err(e) => app.set_error_message(format!("Edit failed: {e}")),
+                }
+            }
             AppAction::PrepareSquash {
                 source_oid,
                 target_oid,
@@ -388,7 +855,8 @@ fn main() -> Result<()> {
                 };
 
                 let label = if is_fixup { "Fixup" } else { "Squash" };
-                let combined = format!("{target_message}\n\n{source_message}");
+                let options = MessageOptions::from_repo(&git_repo, None);
+                let combined = options.squash_message(&[&target_message, &source_message]);
 
                 // Try the tree combination first. If it conflicts, let the
                 // user resolve before opening the editor (T080).
@@ -409,7 +877,19 @@ fn main() -> Result<()> {
                 let final_message = if is_fixup {
                     Some(target_message)
                 } else {
-                    let editor_result = editor::edit_message_in_editor(&git_repo, &combined);
+                    let prepared = match git_repo.prepare_commit_message(
+                        &options.editor_text(&combined),
+                        "squash",
+                        None,
+                    ) {
+                        Ok(msg) => msg,
+                        Err(e) => {
+                            app.set_error_message(format!("{label} failed: {e}"));
+                            continue;
+                        }
+                    };
+                    let editor_result =
+                        editor::edit_message_in_editor(&git_repo, &prepared, &options);
                     terminal.clear()?;
                     match editor_result {
                         Err(e) => {
@@ -428,15 +908,14 @@ fn main() -> Result<()> {
                     let saved_index = app.selection_index;
                     match git_repo.squash_commits(&source_oid, &target_oid, &msg, &head_oid) {
                         Ok(RebaseOutcome::Complete) => {
-                            reload_commits(&git_repo, &mut app);
-                            app.selection_index =
-                                saved_index.min(app.commits.len().saturating_sub(1));
                             let success_msg = if is_fixup {
                                 "Commit fixed up"
                             } else {
                                 "Commits squashed"
                             };
-                            app.set_success_message(success_msg);
+                            reload_after_rewrite(&git_repo, &mut app, success_msg);
+                            app.selection_index =
+                                saved_index.min(app.commits.len().saturating_sub(1));
                         }
                         Ok(RebaseOutcome::Conflict(state)) => {
                             app.enter_rebase_conflict(*state);
@@ -457,9 +936,152 @@ fn main() -> Result<()> {
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
 
+    if todo_aborted {
+        // A failing sequence editor makes git abort the rebase.
+        std::process::exit(1);
+    }
     Ok(())
 }
 
+/// Build the app state for the normal commit list: the commits between the
+/// base reference and HEAD, plus staged/unstaged rows. Returns `None` when
+/// there are no commits to show.
+fn commit_list_app(git_repo: &impl GitRepo, cli: &Cli) -> Result<Option<AppState>> {
+    let commit_ish = match cli.commit_ish.clone() {
+        Some(commit_ish) => commit_ish,
+        None => git_repo.default_reference().context(
+            "No base reference given and none could be inferred \
+             (no upstream, origin/HEAD, init.defaultBranch or tailor.base)",
+        )?,
+    };
+    let merge_base_oid = git_repo.find_reference_point(&commit_ish)?;
+    let fork_point_oid = if cli.fork_point {
+        git_repo.find_fork_point(&commit_ish)?
+    } else {
+        None
+    };
+    let reference_oid = fork_point_oid.unwrap_or_else(|| merge_base_oid.clone());
+    let head_oid = git_repo.head_oid()?;
+
+    let commits = git_repo.list_commits(&head_oid, &reference_oid)?;
+
+    // Exclude the merge-base commit - it's shared with the target branch
+    // and must not be modified (squashed, moved, or split)
+    let commits: Vec<CommitInfo> = commits
+        .into_iter()
+        .filter(|c| c.oid != reference_oid)
+        .collect();
+
+    // Handle edge case: HEAD is at merge-base (no commits on current branch)
+    if commits.is_empty() {
+        eprintln!(
+            "No commits to display: HEAD is at the merge-base with '{}'",
+            commit_ish
+        );
+        eprintln!("The current branch has no commits beyond the common ancestor.");
+        return Ok(None);
+    }
+
+    let mut app = AppState::with_commits(commits);
+    app.original_commits = app.commits.clone();
+    app.reverse = cli.reverse;
+    if reference_oid != merge_base_oid {
+        // The commits between merge-base and fork point were ours once, but
+        // the upstream was rewritten; tell the user why they are hidden.
+        app.set_success_message(format!(
+            "Fork point {} differs from merge-base {}: '{commit_ish}' was rewritten",
+            &reference_oid[..8],
+            &merge_base_oid[..8],
+        ));
+        app.fork_point_merge_base = Some(merge_base_oid.clone());
+    }
+    app.reference_name = commit_ish;
+    app.protect_pushed = PushedScope::from_config(
+        git_repo
+            .get_config_string("tailor.protectPushed")
+            .as_deref(),
+    );
+    app.reference_oid = reference_oid;
+
+    // Append staged/unstaged working-tree changes as synthetic rows at the
+    // bottom of the commit list (newest position). Recompute fragmap with
+    // the extra diffs so their hunk overlap with commits is visible.
+    let mut extra_diffs: Vec<CommitDiff> = Vec::new();
+    if let Some(d) = git_repo.staged_diff() {
+        extra_diffs.push(d);
+    }
+    if let Some(d) = git_repo.unstaged_diff() {
+        extra_diffs.push(d);
+    }
+    let n_regular = app.commits.len();
+    for d in &extra_diffs {
+        app.commits.push(d.commit.clone());
+    }
+    app.full_fragmap = cli.full;
+    app.fragmap = compute_fragmap(git_repo, &app.commits[..n_regular], &extra_diffs, cli.full);
+    app.selection_index = select_initial_index(&app.commits);
+    refresh_commit_markers(git_repo, &mut app);
+
+    Ok(Some(app))
+}
+
+/// Build the app state for `--sequence-editor`: the commits of the todo
+/// list in `path`, in its order and with its commands. Returns `None` when
+/// the todo list has no commits.
+fn sequence_editor_app(
+    git_repo: &impl GitRepo,
+    path: &Path,
+    cli: &Cli,
+) -> Result<Option<AppState>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read todo list {}", path.display()))?;
+    let comment = MessageOptions::from_repo(git_repo, None).comment;
+    let cannot_arrange =
+        |errors: Vec<String>| anyhow!("Cannot arrange this todo list: {}", errors.join("; "));
+    let names = todo::todo_commit_names(&text, &comment).map_err(cannot_arrange)?;
+    if names.is_empty() {
+        return Ok(None);
+    }
+    let commits = names
+        .iter()
+        .map(|name| git_repo.commit_info(name))
+        .collect::<Result<Vec<CommitInfo>>>()?;
+    let lines = todo::parse_todo(&text, &commits, &comment).map_err(cannot_arrange)?;
+
+    let mut app = AppState::with_commits(commits);
+    for line in lines {
+        if let todo::TodoLine::Commit { command, oid } = line {
+            app.todo_commands.insert(oid, command);
+        }
+    }
+    app.reverse = cli.reverse;
+    app.full_fragmap = cli.full;
+    app.fragmap = compute_fragmap(git_repo, &app.commits, &[], cli.full);
+    app.selection_index = 0;
+    app.mode = AppMode::SequenceEdit;
+    Ok(Some(app))
+}
+
+/// Rewriting pushed commits needs explicit confirmation: show the dialog
+/// and return `None` when `action` would rewrite any. Once the user
+/// confirmed, the wrapped action runs without asking again.
+fn confirm_or_run(app: &mut AppState, action: AppAction) -> Option<AppAction> {
+    let action = match action {
+        AppAction::ConfirmedPushed(action) => return Some(*action),
+        action => action,
+    };
+    let pushed = action
+        .rewrite_start()
+        .map(|oid| app.pushed_commits_from(oid))
+        .unwrap_or_default();
+    if pushed.is_empty() {
+        Some(action)
+    } else {
+        app.enter_pushed_confirm(action, pushed);
+        None
+    }
+}
+
 /// Number of output commits above which a split requires explicit confirmation.
 const SPLIT_CONFIRM_THRESHOLD: usize = 5;
 
@@ -489,6 +1111,204 @@ fn execute_split(
     }
 }
 
+/// Recompute the fragmap of the edit session's virtual stack. When its plan
+/// would stop at a conflict, the commits' own diffs stand in and the
+/// conflict is reported.
+fn refresh_session_fragmap(git_repo: &Git2Repo, app: &mut AppState) {
+    let steps = app
+        .session
+        .as_ref()
+        .map(EditSession::plan)
+        .unwrap_or_default();
+    app.fragmap = match session_fragmap(git_repo, app, &steps) {
+        Ok(fragmap) => fragmap,
+        Err(e) => {
+            app.set_error_message(format!("Session: {e}"));
+            compute_fragmap(git_repo, &app.commits, &[], app.full_fragmap)
+        }
+    };
+}
+
+/// Run the plan of an edit session on an in-memory copy of the repository
+/// and compute the fragmap of the stack it leaves.
+fn session_fragmap(
+    git_repo: &Git2Repo,
+    app: &AppState,
+    steps: &[PlanStep],
+) -> Result<Option<fragmap::FragMap>> {
+    let sim = git_repo.dry_run()?;
+    let head_oid = git_repo.head_oid()?;
+    if let RebaseOutcome::Conflict(state) =
+        sim.execute_plan(&app.reference_oid, steps, &head_oid)?
+    {
+        let short: String = state.conflicting_commit_oid.chars().take(8).collect();
+        bail!("applying would stop at a conflict in {short}");
+    }
+    let tip = sim.simulated_tip().unwrap_or(head_oid);
+    let commits: Vec<CommitInfo> = sim
+        .list_commits(&tip, &app.reference_oid)?
+        .into_iter()
+        .filter(|c| c.oid != app.reference_oid)
+        .collect();
+    if commits.len() != app.commits.len() {
+        // A commit that became empty was left out; fall back to the
+        // commits' own diffs rather than misalign the rows.
+        return Ok(compute_fragmap(
+            git_repo,
+            &app.commits,
+            &[],
+            app.full_fragmap,
+        ));
+    }
+    let mut fragmap = compute_fragmap(&sim, &commits, &[], app.full_fragmap);
+    // The simulated commits stand for the session's, which keep their OIDs.
+    if let Some(fragmap) = &mut fragmap {
+        fragmap.commits = app.commits.iter().map(|c| c.oid.clone()).collect();
+    }
+    Ok(fragmap)
+}
+
+/// Simulate `op` on an in-memory copy of the repository and collect the
+/// stack it would leave. When it would stop at a conflict, the commits from
+/// the conflicting one on are listed as they are now.
+fn preview_stack(git_repo: &Git2Repo, app: &AppState, op: &PreviewOp) -> Result<StackPreview> {
+    let sim = git_repo.dry_run()?;
+    let head_oid = git_repo.head_oid()?;
+    let outcome = match op {
+        PreviewOp::Drop {
+            commit_oid,
+            head_oid,
+        } => sim.drop_commit(commit_oid, head_oid)?,
+        PreviewOp::DropMany {
+            commit_oids,
+            head_oid,
+        } => sim.drop_commits(commit_oids, head_oid)?,
+        PreviewOp::Squash {
+            source_oid,
+            target_oid,
+        } => {
+            // The message is only written after confirming; keep the target's.
+            let message = sim.commit_info(target_oid)?.message;
+            sim.squash_commits(source_oid, target_oid, &message, &head_oid)?
+        }
+        PreviewOp::Split {
+            strategy,
+            commit_oid,
+        } => {
+            match strategy {
+                SplitStrategy::PerFile => sim.split_commit_per_file(commit_oid, &head_oid)?,
+                SplitStrategy::PerHunk => sim.split_commit_per_hunk(commit_oid, &head_oid)?,
+                SplitStrategy::PerHunkGroup => {
+                    sim.split_commit_per_hunk_group(commit_oid, &head_oid, &app.reference_oid)?
+                }
+            }
+            RebaseOutcome::Complete
+        }
+    };
+
+    let mut preview = StackPreview {
+        label: op.label(),
+        commits: Vec::new(),
+        fragmap: None,
+        conflicting_oid: None,
+        conflicting_files: Vec::new(),
+        unapplied_oids: HashSet::new(),
+    };
+    let (tip, pending) = match outcome {
+        RebaseOutcome::Complete => (sim.simulated_tip().unwrap_or(head_oid), Vec::new()),
+        RebaseOutcome::Conflict(state) => {
+            let (tip, pending) = match &state.squash_context {
+                // The squash itself conflicts: nothing after its base is done.
+                Some(ctx) => {
+                    let mut pending = vec![ctx.target_oid.clone(), ctx.source_oid.clone()];
+                    pending.extend(ctx.descendant_oids.iter().cloned());
+                    (ctx.base_oid.clone(), pending)
+                }
+                None => {
+                    let mut pending = vec![state.conflicting_commit_oid.clone()];
+                    pending.extend(state.remaining_oids.iter().cloned());
+                    (state.new_tip_oid.clone(), pending)
+                }
+            };
+            preview.unapplied_oids = pending
+                .iter()
+                .filter(|oid| **oid != state.conflicting_commit_oid)
+                .cloned()
+                .collect();
+            preview.conflicting_oid = Some(state.conflicting_commit_oid);
+            preview.conflicting_files = state.conflicting_files;
+            (tip, pending)
+        }
+    };
+
+    let mut commits: Vec<CommitInfo> = sim
+        .list_commits(&tip, &app.reference_oid)?
+        .into_iter()
+        .filter(|c| c.oid != app.reference_oid)
+        .collect();
+    for oid in &pending {
+        commits.push(sim.commit_info(oid)?);
+    }
+    preview.fragmap = compute_fragmap(&sim, &commits, &[], app.full_fragmap);
+    preview.commits = commits;
+    Ok(preview)
+}
+
+/// Write the resolved file to the working tree and stage it, then return
+/// to the conflict dialog with the remaining conflicting files.
+fn write_resolution(git_repo: &impl GitRepo, app: &mut AppState, state: &ConflictResolveState) {
+    let result = merge3::merged_text(&state.chunks)
+        .context("conflicts are still unresolved")
+        .and_then(|text| {
+            let workdir = git_repo
+                .workdir()
+                .context("repository has no working tree")?;
+            std::fs::write(workdir.join(&state.path), text)?;
+            git_repo.stage_file(&state.path)
+        });
+    match result {
+        Ok(()) => {
+            app.enter_rebase_conflict(git_tailor::repo::ConflictState {
+                conflicting_files: git_repo.read_conflicting_files(),
+                conflict_kinds: git_repo.read_conflict_kinds(),
+                still_unresolved: false,
+                ..state.conflict.clone()
+            });
+            app.set_success_message(format!("Resolved and staged {}", state.path));
+        }
+        Err(e) => app.set_error_message(format!("Failed to resolve {}: {e}", state.path)),
+    }
+}
+
+/// Rewrite the commits of a bulk reword and reload commits on success.
+fn apply_bulk_reword(git_repo: &impl GitRepo, app: &mut AppState, messages: &[(String, String)]) {
+    let result = git_repo
+        .head_oid()
+        .and_then(|head_oid| git_repo.reword_commits(messages, &head_oid));
+    match result {
+        Ok(()) => {
+            let message = format!("Reworded {} commit(s)", messages.len());
+            reload_after_rewrite(git_repo, app, &message);
+        }
+        Err(e) => app.set_error_message(format!("Reword failed: {e}")),
+    }
+}
+
+/// Run an edited todo plan from the reference point and reload commits,
+/// or show the conflict dialog where it stopped.
+fn execute_plan(git_repo: &impl GitRepo, app: &mut AppState, steps: &[PlanStep]) {
+    let result = git_repo
+        .head_oid()
+        .and_then(|head_oid| git_repo.execute_plan(&app.reference_oid, steps, &head_oid));
+    match result {
+        Ok(RebaseOutcome::Complete) => {
+            reload_after_rewrite(git_repo, app, "Plan executed");
+        }
+        Ok(RebaseOutcome::Conflict(state)) => app.enter_rebase_conflict(*state),
+        Err(e) => app.set_error_message(format!("Plan failed: {e}")),
+    }
+}
+
 /// Choose the initial selection index for a commit list:
 /// unstaged row if present, else staged row if present, else the last commit.
 fn select_initial_index(commits: &[CommitInfo]) -> usize {
@@ -548,6 +1368,95 @@ fn reload_commits(git_repo: &impl GitRepo, app: &mut AppState) {
     app.fragmap = fragmap;
     app.fragmap_scroll_offset = 0;
     app.detail_scroll_offset = 0;
+    refresh_commit_markers(git_repo, app);
+}
+
+/// Recompute which commits already have an equivalent patch on the
+/// reference branch, which are already pushed and which break the lint
+/// rules. Failures (e.g. a reference that is a plain commit hash) simply
+/// leave no markers.
+fn refresh_commit_markers(git_repo: &impl GitRepo, app: &mut AppState) {
+    let Ok(head_oid) = git_repo.head_oid() else {
+        return;
+    };
+    app.upstreamed_oids = git_repo
+        .find_upstreamed_commits(&app.reference_name, &head_oid)
+        .map(|oids| oids.into_iter().collect())
+        .unwrap_or_default();
+    app.pushed_oids = match app.protect_pushed {
+        Some(scope) => git_repo
+            .find_pushed_commits(&head_oid, &app.reference_oid, scope)
+            .map(|oids| oids.into_iter().collect())
+            .unwrap_or_default(),
+        None => Default::default(),
+    };
+    let lint = LintConfig::from_repo(git_repo);
+    app.lint_warnings = app
+        .commits
+        .iter()
+        .filter(|c| c.oid != "staged" && c.oid != "unstaged")
+        .map(|c| (c.oid.clone(), lint.check(&c.message)))
+        .filter(|(_, warnings)| !warnings.is_empty())
+        .collect();
+}
+
+/// Reload commits after a successful rewrite and report it, pointing out
+/// rewritten commits that now break the lint rules so they are fixed
+/// before pushing. Commits the rewrite left alone are not counted.
+fn reload_after_rewrite(git_repo: &impl GitRepo, app: &mut AppState, message: &str) {
+    let previous: HashSet<String> = app.commits.iter().map(|c| c.oid.clone()).collect();
+    reload_commits(git_repo, app);
+    let warned = app
+        .lint_warnings
+        .keys()
+        .filter(|oid| !previous.contains(*oid))
+        .count();
+    match warned {
+        0 => app.set_success_message(message),
+        n => app.set_success_message(format!(
+            "{message}; {n} rewritten commit(s) have lint warnings (marked !)"
+        )),
+    }
+}
+
+/// Align the current commits with the ones on the branch's push
+/// destination. Both stacks are listed from the same base so that commits
+/// shared with the reference branch do not show up as added or dropped.
+/// Returns `None` when the branch has no push destination.
+fn compare_with_pushed(
+    git_repo: &impl GitRepo,
+    app: &AppState,
+) -> Result<Option<(String, Vec<StackRow>)>> {
+    let Some(remote) = git_repo.push_destination() else {
+        return Ok(None);
+    };
+    let stack = |commits: Vec<CommitInfo>| {
+        commits
+            .into_iter()
+            .map(|info| {
+                let patch_id = git_repo.patch_id(&info.oid)?;
+                Ok(StackCommit { info, patch_id })
+            })
+            .collect::<Result<Vec<_>>>()
+    };
+
+    // A pushed branch based on an older upstream shares less history with
+    // the reference; stop at the common ancestor instead.
+    let remote_base = git_repo.merge_base(&remote, &app.reference_oid)?;
+    let remote_commits = git_repo
+        .list_commits(&remote, &remote_base)?
+        .into_iter()
+        .filter(|c| c.oid != remote_base)
+        .collect();
+    let local_commits = app
+        .commits
+        .iter()
+        .filter(|c| c.oid != "staged" && c.oid != "unstaged")
+        .cloned()
+        .collect();
+
+    let rows = stack_compare::compare_stacks(&stack(remote_commits)?, &stack(local_commits)?);
+    Ok(Some((remote, rows)))
 }
 
 /// Render the main view with split screen (commit list on left, detail on right).
@@ -618,8 +1527,19 @@ fn render_mode(
         AppMode::SplitSelect { .. } => views::split_select::render(app, frame),
         AppMode::SplitConfirm(_) => views::split_select::render_split_confirm(app, frame),
         AppMode::DropConfirm(_) => views::drop::render_drop_confirm(app, frame),
+        AppMode::DropUpstreamedConfirm(_) => {
+            views::drop::render_drop_upstreamed_confirm(app, frame)
+        }
+        AppMode::PushedConfirm(_) => views::pushed::render_pushed_confirm(app, frame),
+        AppMode::RangeDiff { .. } => views::range_diff::render(git_repo, app, frame),
+        AppMode::StackCompare { .. } => views::stack_compare::render(git_repo, app, frame),
         AppMode::RebaseConflict(_) => views::conflict::render_conflict(app, frame),
+        AppMode::StrategySelect { .. } => views::strategy_select::render(app, frame),
+        AppMode::Preview(_) => views::commit_list::render(app, frame),
         AppMode::SquashSelect { .. } => views::commit_list::render(app, frame),
-        AppMode::Help(_) => views::help::render(frame),
+        AppMode::MetadataEdit(_) => views::metadata::render(app, frame),
+        AppMode::SequenceEdit | AppMode::EditSession => views::commit_list::render(app, frame),
+        AppMode::ConflictResolve(_) => views::conflict_resolve::render(app, frame),
+        AppMode::Help(_) => views::help::render(app, frame),
     }
 }
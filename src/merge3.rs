@@ -0,0 +1,328 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Line-based three-way merge of a conflicted file.
+//
+// The base, ours and theirs versions (index stages 1, 2 and 3) are each
+// matched against the base with a longest common subsequence. Base lines
+// matched in both sides are stable; between them, a region changed on only
+// one side takes that side, and a region changed differently on both sides
+// is a conflict for the user to resolve.
+
+use crate::repo::GitRepo;
+use anyhow::{bail, Context, Result};
+
+/// Beyond this many line comparisons a changed stretch is not diffed
+/// further but treated as one region.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// Length of git's conflict markers.
+pub(crate) const MARKER_SIZE: usize = 7;
+
+/// How the user resolved a conflict region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+    /// Our lines followed by theirs.
+    Both,
+    /// Lines written in the editor.
+    Edited(Vec<String>),
+}
+
+/// Lines changed differently on both sides, as in a diff3 conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictRegion {
+    pub base: Vec<String>,
+    pub ours: Vec<String>,
+    pub theirs: Vec<String>,
+    pub resolution: Option<Resolution>,
+}
+
+impl ConflictRegion {
+    /// The lines this region resolves to, or `None` while unresolved.
+    pub fn resolved_lines(&self) -> Option<Vec<String>> {
+        Some(match self.resolution.as_ref()? {
+            Resolution::Ours => self.ours.clone(),
+            Resolution::Theirs => self.theirs.clone(),
+            Resolution::Both => [self.ours.as_slice(), self.theirs.as_slice()].concat(),
+            Resolution::Edited(lines) => lines.clone(),
+        })
+    }
+
+    /// The region as diff3-style conflict markers, for editing.
+    pub fn marked_text(&self) -> String {
+        let mut text = String::from("<<<<<<< ours\n");
+        push_lines(&mut text, &self.ours);
+        text.push_str("||||||| base\n");
+        push_lines(&mut text, &self.base);
+        text.push_str("=======\n");
+        push_lines(&mut text, &self.theirs);
+        text.push_str(">>>>>>> theirs\n");
+        text
+    }
+}
+
+/// A stretch of the merged file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeChunk {
+    /// Lines both sides agree on, or changed on one side only.
+    Resolved(Vec<String>),
+    Conflict(ConflictRegion),
+}
+
+/// Split text into lines, each keeping its line ending.
+pub fn split_lines(text: &str) -> Vec<String> {
+    text.split_inclusive('\n').map(str::to_string).collect()
+}
+
+fn push_lines(text: &mut String, lines: &[String]) {
+    for line in lines {
+        text.push_str(line);
+        if !line.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+}
+
+/// Merge the three versions of a file line by line.
+pub fn merge(base: &str, ours: &str, theirs: &str) -> Vec<MergeChunk> {
+    let (base, ours, theirs) = (split_lines(base), split_lines(ours), split_lines(theirs));
+    let to_ours = match_lines(&base, &ours);
+    let to_theirs = match_lines(&base, &theirs);
+
+    let mut chunks: Vec<MergeChunk> = Vec::new();
+    let push_resolved = |chunks: &mut Vec<MergeChunk>, lines: &[String]| {
+        if lines.is_empty() {
+            return;
+        }
+        if let Some(MergeChunk::Resolved(previous)) = chunks.last_mut() {
+            previous.extend_from_slice(lines);
+        } else {
+            chunks.push(MergeChunk::Resolved(lines.to_vec()));
+        }
+    };
+
+    let (mut b, mut o, mut t) = (0, 0, 0);
+    loop {
+        // The next base line kept by both sides ends the current region.
+        let stable = (b..base.len()).find_map(|x| Some((x, to_ours[x]?, to_theirs[x]?)));
+        let (bx, ox, tx) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+        let (base_part, ours_part, theirs_part) = (&base[b..bx], &ours[o..ox], &theirs[t..tx]);
+        if ours_part == base_part || ours_part == theirs_part {
+            push_resolved(&mut chunks, theirs_part);
+        } else if theirs_part == base_part {
+            push_resolved(&mut chunks, ours_part);
+        } else {
+            chunks.push(MergeChunk::Conflict(ConflictRegion {
+                base: base_part.to_vec(),
+                ours: ours_part.to_vec(),
+                theirs: theirs_part.to_vec(),
+                resolution: None,
+            }));
+        }
+
+        if stable.is_none() {
+            break;
+        }
+        push_resolved(&mut chunks, &base[bx..bx + 1]);
+        (b, o, t) = (bx + 1, ox + 1, tx + 1);
+    }
+    chunks
+}
+
+/// Merge the conflicting index stages of `path`: base (1), ours (2) and
+/// theirs (3). Fails for files deleted on one side or that are not text.
+pub fn merge_index_stages(repo: &impl GitRepo, path: &str) -> Result<Vec<MergeChunk>> {
+    let stage = |stage: i32, name: &str| -> Result<Option<String>> {
+        let Some(content) = repo
+            .read_index_stage(path, stage)
+            .with_context(|| format!("failed to read {name} stage"))?
+        else {
+            return Ok(None);
+        };
+        match String::from_utf8(content) {
+            Ok(text) => Ok(Some(text)),
+            Err(_) => bail!("'{path}' is not a text file"),
+        }
+    };
+    // Files added on both sides have no base.
+    let base = stage(1, "base")?.unwrap_or_default();
+    let (Some(ours), Some(theirs)) = (stage(2, "ours")?, stage(3, "theirs")?) else {
+        bail!("'{path}' was deleted on one side");
+    };
+    Ok(merge(&base, &ours, &theirs))
+}
+
+/// Whether `line` is a conflict marker made of `marker`: exactly
+/// [`MARKER_SIZE`] of them, then a space or the end of the line. The `<`
+/// and `>` markers are followed by a label, the others may stand alone.
+pub(crate) fn is_marker(line: &str, marker: char) -> bool {
+    let Some(rest) = line.strip_prefix(&marker.to_string().repeat(MARKER_SIZE)) else {
+        return false;
+    };
+    match marker {
+        '<' | '>' => rest.starts_with(' '),
+        _ => rest.is_empty() || rest.starts_with(char::is_whitespace),
+    }
+}
+
+/// Whether edited text still contains conflict markers.
+pub fn has_conflict_markers(lines: &[String]) -> bool {
+    lines
+        .iter()
+        .any(|line| ['<', '|', '=', '>'].iter().any(|&m| is_marker(line, m)))
+}
+
+/// The merged file, or `None` while any conflict is unresolved.
+pub fn merged_text(chunks: &[MergeChunk]) -> Option<String> {
+    let mut text = String::new();
+    for chunk in chunks {
+        match chunk {
+            MergeChunk::Resolved(lines) => text.extend(lines.iter().map(String::as_str)),
+            MergeChunk::Conflict(region) => {
+                push_lines(&mut text, &region.resolved_lines()?);
+            }
+        }
+    }
+    Some(text)
+}
+
+/// For each line of `old`, the index of the line of `new` it is matched
+/// with by a longest common subsequence. Shared leading and trailing lines
+/// are matched directly; a middle stretch larger than `MAX_LCS_CELLS` is
+/// left unmatched.
+pub(crate) fn match_lines(old: &[String], new: &[String]) -> Vec<Option<usize>> {
+    let mut matched = vec![None; old.len()];
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    for (i, slot) in matched.iter_mut().enumerate().take(prefix) {
+        *slot = Some(i);
+    }
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for k in 1..=suffix {
+        matched[old.len() - k] = Some(new.len() - k);
+    }
+
+    let (old_mid, new_mid) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n == 0 || m == 0 || n * m > MAX_LCS_CELLS {
+        return matched;
+    }
+    // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_mid[i] == new_mid[j] {
+            matched[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflicts(chunks: &[MergeChunk]) -> Vec<&ConflictRegion> {
+        chunks
+            .iter()
+            .filter_map(|c| match c {
+                MergeChunk::Conflict(region) => Some(region),
+                MergeChunk::Resolved(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn one_sided_changes_merge_cleanly() {
+        let chunks = merge("a\nb\nc\nd\n", "a\nB\nc\nd\n", "a\nb\nc\nD\n");
+        assert!(conflicts(&chunks).is_empty());
+        assert_eq!(merged_text(&chunks).as_deref(), Some("a\nB\nc\nD\n"));
+    }
+
+    #[test]
+    fn overlapping_changes_conflict() {
+        let chunks = merge("a\nb\nc\n", "a\nours\nc\n", "a\ntheirs\nc\n");
+        let regions = conflicts(&chunks);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].base, ["b\n"]);
+        assert_eq!(regions[0].ours, ["ours\n"]);
+        assert_eq!(regions[0].theirs, ["theirs\n"]);
+        assert_eq!(merged_text(&chunks), None);
+        assert_eq!(
+            regions[0].marked_text(),
+            "<<<<<<< ours\nours\n||||||| base\nb\n=======\ntheirs\n>>>>>>> theirs\n"
+        );
+    }
+
+    #[test]
+    fn resolutions_produce_merged_text() {
+        let mut chunks = merge("x\n", "x\nours\n", "x\ntheirs\n");
+        let mut resolve = |resolution: Resolution| {
+            for chunk in &mut chunks {
+                if let MergeChunk::Conflict(region) = chunk {
+                    region.resolution = Some(resolution.clone());
+                }
+            }
+            merged_text(&chunks).unwrap()
+        };
+        assert_eq!(resolve(Resolution::Ours), "x\nours\n");
+        assert_eq!(resolve(Resolution::Theirs), "x\ntheirs\n");
+        assert_eq!(resolve(Resolution::Both), "x\nours\ntheirs\n");
+        assert_eq!(
+            resolve(Resolution::Edited(vec!["mine\n".to_string()])),
+            "x\nmine\n"
+        );
+    }
+
+    #[test]
+    fn identical_changes_and_missing_final_newline() {
+        let chunks = merge("a\nb", "a\nc", "a\nc");
+        assert!(conflicts(&chunks).is_empty());
+        assert_eq!(merged_text(&chunks).as_deref(), Some("a\nc"));
+    }
+
+    #[test]
+    fn heading_underlines_and_quotes_are_not_markers() {
+        let text = "Title\n==========\n\n>>>>>>>> quoted\n=======x\n";
+        assert!(!has_conflict_markers(&split_lines(text)));
+        assert!(has_conflict_markers(&split_lines("a\n=======\nb\n")));
+        assert!(has_conflict_markers(&split_lines("<<<<<<< ours\n")));
+        assert!(has_conflict_markers(&split_lines("a\n=======")));
+    }
+}
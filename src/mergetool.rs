@@ -15,60 +15,191 @@
 // Launch the user-configured merge tool to resolve index conflicts.
 //
 // Git's merge tool contract:
-//   - `merge.tool` names the tool
-//   - `mergetool.<name>.cmd` is an optional shell command for that tool
+//   - `merge.tool` names the tool, or `merge.guitool` when
+//     `mergetool.guiDefault` asks for the GUI tool
+//   - `mergetool.<name>.cmd` is an optional shell command for that tool;
+//     otherwise a built-in command is used, run from `mergetool.<name>.path`
+//     when set
 //   - The shell command has access to $LOCAL (ours), $REMOTE (theirs),
 //     $BASE (ancestor), and $MERGED (the working-tree file to save the result)
 //   - git runs the cmd through a shell and waits for it to exit
+//   - Unless `mergetool.<name>.trustExitCode` is set, the exit status is
+//     ignored and the merge counts as done when MERGED has no conflict
+//     markers left
+//   - `mergetool.keepBackup` keeps the conflicted file as `<file>.orig` and
+//     `mergetool.prompt` asks before each file
 //
 // We follow the same contract: suspend the TUI, write the three index stages
 // to temp files, run the tool via `sh -c`, wait for exit, then restore.
+// Unlike git, which prompts for delete/modify and symlink conflicts, files
+// that are not text are skipped; the conflict dialog offers their choices.
 
-use crate::repo::GitRepo;
+use crate::{
+    merge3,
+    repo::{config_bool, GitRepo},
+};
 use anyhow::{Context, Result};
 use crossterm::{execute, terminal};
-use std::io::Write as _;
+use std::io::{BufRead as _, Write as _};
 use std::path::Path;
 
-/// Resolve the shell command to use for the configured merge tool.
+/// A merge tool and how to run it, resolved from git config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeTool {
+    pub name: String,
+    /// Shell command using $BASE, $LOCAL, $REMOTE and $MERGED.
+    pub cmd: String,
+    /// Whether the exit status tells if the merge succeeded. Otherwise the
+    /// result is checked for conflict markers.
+    pub trust_exit_code: bool,
+    /// Keep the conflicted file as `<file>.orig` after a successful merge.
+    pub keep_backup: bool,
+    /// Ask before starting the tool for each file.
+    pub prompt: bool,
+}
+
+impl MergeTool {
+    /// A tool running `cmd`, with git's defaults otherwise.
+    pub fn from_cmd(name: &str, cmd: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            cmd: cmd.to_string(),
+            trust_exit_code: false,
+            keep_backup: true,
+            prompt: false,
+        }
+    }
+}
+
+/// Resolve the configured merge tool.
 ///
 /// Lookup order:
-/// 1. `merge.tool` config → tool name
+/// 1. `merge.guitool` when `mergetool.guiDefault` is true, or `auto` and a
+///    display is available; `merge.tool` otherwise
 /// 2. `mergetool.<name>.cmd` → custom shell command for that tool
-/// 3. Built-in patterns for well-known tools
+/// 3. Built-in patterns for the tools git knows, run from
+///    `mergetool.<name>.path` when set
 ///
 /// Returns `None` when no merge tool is configured or the named tool is not
 /// recognised and has no custom cmd.
-pub fn resolve_merge_tool_cmd(repo: &impl GitRepo) -> Option<String> {
-    let name = repo.get_config_string("merge.tool")?;
-    let name = name.trim().to_string();
+///
+/// `mergetool.prompt` defaults to false rather than git's true: pressing the
+/// mergetool key already asks for the tool.
+pub fn resolve_merge_tool(repo: &impl GitRepo) -> Option<MergeTool> {
+    let name = tool_name(repo, "merge")?;
+    let flag = |key: &str| repo.get_config_string(key).map(|v| config_bool(&v));
 
-    if let Some(cmd) = repo.get_config_string(&format!("mergetool.{name}.cmd")) {
-        return Some(cmd.trim().to_string());
-    }
+    let cmd = match repo.get_config_string(&format!("mergetool.{name}.cmd")) {
+        Some(cmd) => cmd.trim().to_string(),
+        None => {
+            let (program, args) = builtin(&name)?;
+            match repo.get_config_string(&format!("mergetool.{name}.path")) {
+                Some(path) => format!("{} {args}", shell_quote(path.trim())),
+                None => format!("{program} {args}"),
+            }
+        }
+    };
+    Some(MergeTool {
+        trust_exit_code: flag(&format!("mergetool.{name}.trustExitCode")).unwrap_or(false),
+        keep_backup: flag("mergetool.keepBackup").unwrap_or(true),
+        prompt: flag("mergetool.prompt").unwrap_or(false),
+        name,
+        cmd,
+    })
+}
+
+/// Resolve the shell command to use for the configured merge tool.
+pub fn resolve_merge_tool_cmd(repo: &impl GitRepo) -> Option<String> {
+    resolve_merge_tool(repo).map(|tool| tool.cmd)
+}
 
-    builtin_cmd(&name)
+/// The tool configured for `kind` ("merge" or "diff"), preferring the GUI
+/// tool as git does: `<kind>.guitool` when `<kind>tool.guiDefault` is true, or
+/// `auto` and a display is available, and `<kind>.tool` otherwise.
+pub(crate) fn tool_name(repo: &impl GitRepo, kind: &str) -> Option<String> {
+    let gui = match repo.get_config_string(&format!("{kind}tool.guiDefault")) {
+        Some(value) if value.trim().eq_ignore_ascii_case("auto") => {
+            std::env::var_os("DISPLAY").is_some_and(|d| !d.is_empty())
+        }
+        Some(value) => config_bool(&value),
+        None => false,
+    };
+    let gui_tool = gui
+        .then(|| repo.get_config_string(&format!("{kind}.guitool")))
+        .flatten();
+    let name = gui_tool.or_else(|| repo.get_config_string(&format!("{kind}.tool")))?;
+    Some(name.trim().to_string())
 }
 
-/// Shell command for well-known built-in merge tools.
-fn builtin_cmd(name: &str) -> Option<String> {
-    match name {
-        // vimdiff / nvimdiff family — two-way diff with MERGED as output
-        "vimdiff" | "vimdiff2" => Some(format!("{name} -d $LOCAL $MERGED $REMOTE")),
-        "vimdiff3" => Some(format!("{name} -d $LOCAL $BASE $REMOTE $MERGED")),
-        "nvimdiff" | "nvimdiff2" => Some(format!("{name} -d $LOCAL $MERGED $REMOTE")),
-        "nvimdiff3" => Some("nvim -d $LOCAL $BASE $REMOTE $MERGED".to_string()),
-        "meld" => Some("meld $LOCAL $MERGED $REMOTE".to_string()),
-        "kdiff3" => Some(
-            "kdiff3 --L1 $MERGED --L2 $LOCAL --L3 $REMOTE -o $MERGED $BASE $LOCAL $REMOTE"
-                .to_string(),
+/// Program and arguments of the merge tools built into git, as listed by
+/// `git mergetool --tool-help`.
+fn builtin(name: &str) -> Option<(&'static str, &'static str)> {
+    Some(match name {
+        "araxis" => (
+            "compare",
+            "-wait -merge -3 -a1 $BASE $LOCAL $REMOTE $MERGED",
         ),
-        "opendiff" => Some("opendiff $LOCAL $REMOTE -ancestor $BASE -merge $MERGED".to_string()),
-        _ => None,
-    }
+        "bc" | "bc3" | "bc4" => ("bcompare", "$LOCAL $REMOTE $BASE -mergeoutput=$MERGED"),
+        "codecompare" => ("CodeMerge", "-MF=$LOCAL -TF=$REMOTE -BF=$BASE -RF=$MERGED"),
+        "deltawalker" => (
+            "DeltaWalker",
+            "$LOCAL $REMOTE $BASE -pwd=\"$(pwd)\" -merged=$MERGED",
+        ),
+        "diffmerge" => ("diffmerge", "--merge --result=$MERGED $LOCAL $BASE $REMOTE"),
+        "diffuse" => ("diffuse", "-o $MERGED $LOCAL $MERGED $REMOTE $BASE"),
+        "ecmerge" => (
+            "ecmerge",
+            "$BASE $LOCAL $REMOTE --default --mode=merge3 --to=$MERGED",
+        ),
+        "emerge" => (
+            "emacs",
+            "-f emerge-files-with-ancestor-command $LOCAL $REMOTE $BASE $MERGED",
+        ),
+        "examdiff" => ("ExamDiff", "-merge $LOCAL $BASE $REMOTE -o:$MERGED -nh"),
+        "guiffy" => ("guiffy", "-s $LOCAL $REMOTE $BASE $MERGED"),
+        // vimdiff / nvimdiff / gvimdiff family: MERGED is the buffer saved as
+        // the result. gvim must stay in the foreground to be waited for.
+        "vimdiff" => ("vimdiff", "-d $LOCAL $MERGED $REMOTE"),
+        "vimdiff1" => ("vim", "-d $MERGED $REMOTE"),
+        "vimdiff2" => ("vim", "-d $LOCAL $MERGED $REMOTE"),
+        "vimdiff3" => ("vim", "-d $LOCAL $BASE $REMOTE $MERGED"),
+        "gvimdiff1" => ("gvim", "-f -d $MERGED $REMOTE"),
+        "gvimdiff" | "gvimdiff2" => ("gvim", "-f -d $LOCAL $MERGED $REMOTE"),
+        "gvimdiff3" => ("gvim", "-f -d $LOCAL $BASE $REMOTE $MERGED"),
+        "nvimdiff1" => ("nvim", "-d $MERGED $REMOTE"),
+        "nvimdiff" | "nvimdiff2" => ("nvim", "-d $LOCAL $MERGED $REMOTE"),
+        "nvimdiff3" => ("nvim", "-d $LOCAL $BASE $REMOTE $MERGED"),
+        "kdiff3" => (
+            "kdiff3",
+            "--L1 $MERGED --L2 $LOCAL --L3 $REMOTE -o $MERGED $BASE $LOCAL $REMOTE",
+        ),
+        "meld" => ("meld", "$LOCAL $MERGED $REMOTE"),
+        "opendiff" => ("opendiff", "$LOCAL $REMOTE -ancestor $BASE -merge $MERGED"),
+        "p4merge" => ("p4merge", "$BASE $REMOTE $LOCAL $MERGED"),
+        "smerge" => ("smerge", "mergetool $BASE $LOCAL $REMOTE -o $MERGED"),
+        "tkdiff" => ("tkdiff", "-a $BASE -o $MERGED $LOCAL $REMOTE"),
+        "tortoisemerge" => (
+            "TortoiseGitMerge",
+            "-base:$BASE -mine:$LOCAL -theirs:$REMOTE -merged:$MERGED",
+        ),
+        "winmerge" => (
+            "WinMergeU",
+            "-u -e -dl Local -dr Remote $LOCAL $REMOTE -o $MERGED",
+        ),
+        "xxdiff" => (
+            "xxdiff",
+            "-X --show-merged-pane --merged-file $MERGED $LOCAL $BASE $REMOTE",
+        ),
+        _ => return None,
+    })
+}
+
+/// Quote `value` as a single shell word.
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
 }
 
-/// Launch the configured merge tool for every file in `conflicting_files`.
+/// Launch the configured merge tool for every text file in `conflicting_files`.
 ///
 /// Suspends the TUI before the first tool invocation and restores it after the
 /// last one, so each tool instance has full control of the terminal. The TUI is
@@ -77,7 +208,7 @@ fn builtin_cmd(name: &str) -> Option<String> {
 /// Returns `true` when the tool was invoked for at least one file, or `false`
 /// when no merge tool is configured (so the caller can show a hint).
 pub fn run_mergetool(repo: &impl GitRepo, conflicting_files: &[String]) -> Result<bool> {
-    let Some(cmd) = resolve_merge_tool_cmd(repo) else {
+    let Some(tool) = resolve_merge_tool(repo) else {
         return Ok(false);
     };
 
@@ -93,7 +224,7 @@ pub fn run_mergetool(repo: &impl GitRepo, conflicting_files: &[String]) -> Resul
     terminal::disable_raw_mode().context("failed to disable raw mode")?;
     let _ = execute!(std::io::stdout(), terminal::LeaveAlternateScreen);
 
-    let result = run_for_all_files(&cmd, &workdir, repo, conflicting_files);
+    let result = run_for_all_files(&tool, &workdir, repo, conflicting_files);
 
     // Restore the TUI unconditionally so the app is never left in a broken state.
     let _ = terminal::enable_raw_mode();
@@ -109,20 +240,26 @@ pub fn run_mergetool(repo: &impl GitRepo, conflicting_files: &[String]) -> Resul
 /// Use [`run_mergetool`] from application code — it handles TUI suspend/restore.
 #[doc(hidden)]
 pub fn run_for_all_files(
-    cmd: &str,
+    tool: &MergeTool,
     workdir: &Path,
     repo: &impl GitRepo,
     files: &[String],
 ) -> Result<()> {
+    let kinds = repo.read_conflict_kinds();
     for file_path in files {
-        run_tool_for_file(cmd, workdir, repo, file_path)
+        // Deleted, renamed, binary and mode conflicts are resolved by
+        // picking a side, not in a text merge tool.
+        if kinds.get(file_path).is_some_and(|kind| !kind.is_text()) {
+            continue;
+        }
+        run_tool_for_file(tool, workdir, repo, file_path)
             .with_context(|| format!("merge tool failed on '{file_path}'"))?;
     }
     Ok(())
 }
 
 fn run_tool_for_file(
-    cmd: &str,
+    tool: &MergeTool,
     workdir: &Path,
     repo: &impl GitRepo,
     file_path: &str,
@@ -180,11 +317,22 @@ fn run_tool_for_file(
         .context("failed to flush REMOTE temp file")?;
 
     let merged_path = workdir.join(file_path);
+    let conflicted = std::fs::read(&merged_path).unwrap_or_default();
+
+    if tool.prompt {
+        print!(
+            "\nNormal merge conflict for '{file_path}':\n\
+             Hit return to start merge resolution tool ({}): ",
+            tool.name
+        );
+        std::io::stdout().flush()?;
+        std::io::stdin().lock().read_line(&mut String::new())?;
+    }
 
     // Run via shell so $LOCAL / $BASE / $REMOTE / $MERGED expand from the env vars.
     let status = std::process::Command::new("sh")
         .arg("-c")
-        .arg(cmd)
+        .arg(&tool.cmd)
         .env("BASE", base_tmp.path())
         .env("LOCAL", local_tmp.path())
         .env("REMOTE", remote_tmp.path())
@@ -198,8 +346,22 @@ fn run_tool_for_file(
     drop(local_tmp);
     drop(remote_tmp);
 
-    if !status.success() {
-        anyhow::bail!("merge tool exited with {status}");
+    if tool.trust_exit_code {
+        if !status.success() {
+            anyhow::bail!("merge tool exited with {status}");
+        }
+    } else {
+        let merged = std::fs::read(&merged_path).unwrap_or_default();
+        let lines = merge3::split_lines(&String::from_utf8_lossy(&merged));
+        if merge3::has_conflict_markers(&lines) {
+            anyhow::bail!("conflict markers remain in '{file_path}'");
+        }
+    }
+
+    if tool.keep_backup {
+        let mut backup = merged_path.clone().into_os_string();
+        backup.push(".orig");
+        std::fs::write(&backup, &conflicted).context("failed to write backup file")?;
     }
 
     // Stage the resolved file so the index conflict entries (stage 1/2/3) are
@@ -0,0 +1,478 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Commit message helpers: trailer parsing, editor preparation and cleanup
+//
+// Cleanup follows `git commit --cleanup`:
+//   - strip: drop comment lines, then whitespace cleanup (the default when
+//     the message is edited)
+//   - whitespace: strip trailing whitespace, collapse blank lines and drop
+//     leading/trailing ones
+//   - scissors: whitespace cleanup after cutting at the scissors line
+//   - verbatim: keep the message as written
+
+use crate::repo::GitRepo;
+
+/// Comment characters tried, in order, for `core.commentChar=auto`.
+const AUTO_COMMENT_CHARS: &str = "#;@!$%^&|:";
+
+/// How an edited message is cleaned up (`commit.cleanup`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupMode {
+    Strip,
+    Whitespace,
+    Scissors,
+    Verbatim,
+}
+
+impl CleanupMode {
+    /// Parse a `commit.cleanup` value. `default` is `strip`, since gt only
+    /// cleans up messages that went through the editor.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "default" | "strip" => Some(CleanupMode::Strip),
+            "whitespace" => Some(CleanupMode::Whitespace),
+            "scissors" => Some(CleanupMode::Scissors),
+            "verbatim" => Some(CleanupMode::Verbatim),
+            _ => None,
+        }
+    }
+}
+
+/// Settings for preparing a message for the editor and cleaning it up
+/// afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageOptions {
+    /// Comment prefix (`core.commentString` / `core.commentChar`).
+    pub comment: String,
+    pub cleanup: CleanupMode,
+    /// Contents of `commit.template`, offered when the message is empty.
+    pub template: Option<String>,
+}
+
+impl Default for MessageOptions {
+    fn default() -> Self {
+        MessageOptions {
+            comment: "#".to_string(),
+            cleanup: CleanupMode::Strip,
+            template: None,
+        }
+    }
+}
+
+impl MessageOptions {
+    /// Read the options from the repository configuration.
+    ///
+    /// With `core.commentChar=auto` the comment character is picked so that
+    /// no line of `existing` starts with it; without `existing` (as for
+    /// squash messages, like git's sequencer) it stays `#`.
+    pub fn from_repo(repo: &impl GitRepo, existing: Option<&str>) -> Self {
+        let configured = repo
+            .get_config_string("core.commentString")
+            .or_else(|| repo.get_config_string("core.commentChar"))
+            .filter(|c| !c.is_empty());
+        let comment = match (configured.as_deref(), existing) {
+            (Some("auto"), Some(text)) => auto_comment(text),
+            (Some("auto") | None, _) => "#".to_string(),
+            (Some(c), _) => c.to_string(),
+        };
+        let cleanup = repo
+            .get_config_string("commit.cleanup")
+            .and_then(|v| CleanupMode::parse(&v))
+            .unwrap_or(CleanupMode::Strip);
+        let template = repo
+            .get_config_string("commit.template")
+            .and_then(|path| std::fs::read_to_string(template_path(repo, &path)).ok());
+        MessageOptions {
+            comment,
+            cleanup,
+            template,
+        }
+    }
+
+    /// The scissors line; it and everything below are dropped in scissors
+    /// mode.
+    pub fn scissors_line(&self) -> String {
+        format!(
+            "{} ------------------------ >8 ------------------------",
+            self.comment
+        )
+    }
+
+    /// Comment out each line of `text`.
+    pub fn comment_lines(&self, text: &str) -> String {
+        text.lines()
+            .map(|line| {
+                if line.is_empty() {
+                    self.comment.clone()
+                } else {
+                    format!("{} {line}", self.comment)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    /// The buffer to open in the editor: `message` (or the template when it
+    /// is empty) followed by git's help comments for the cleanup mode.
+    pub fn editor_text(&self, message: &str) -> String {
+        let mut text = match (&self.template, message.trim().is_empty()) {
+            (Some(template), true) => template.clone(),
+            _ => message.to_string(),
+        };
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push('\n');
+        match self.cleanup {
+            CleanupMode::Strip => text.push_str(&self.comment_lines(&format!(
+                "Please enter the commit message for your changes. Lines starting\n\
+                 with '{}' will be ignored, and an empty message aborts the commit.",
+                self.comment
+            ))),
+            CleanupMode::Scissors => {
+                text.push_str(&self.scissors_line());
+                text.push('\n');
+                text.push_str(&self.comment_lines(
+                    "Do not modify or remove the line above.\n\
+                     Everything below it will be ignored.",
+                ));
+            }
+            CleanupMode::Whitespace | CleanupMode::Verbatim => {
+                text.push_str(&self.comment_lines(&format!(
+                    "Please enter the commit message for your changes. Lines starting\n\
+                     with '{}' will be kept; you may remove them yourself if you want to.\n\
+                     An empty message aborts the commit.",
+                    self.comment
+                )))
+            }
+        }
+        text
+    }
+
+    /// Clean up an edited message. An unedited template counts as empty.
+    /// The headers of [`Self::squash_message`] are dropped in every cleanup
+    /// mode, like git's sequencer does.
+    pub fn cleanup(&self, text: &str) -> String {
+        let cleaned = self.cleanup_text(&self.strip_squash_headers(text));
+        match &self.template {
+            Some(template) if cleaned == self.cleanup_text(template) => String::new(),
+            _ => cleaned,
+        }
+    }
+
+    fn cleanup_text(&self, text: &str) -> String {
+        match self.cleanup {
+            CleanupMode::Verbatim => text.to_string(),
+            CleanupMode::Whitespace => stripspace(text, None),
+            CleanupMode::Strip => stripspace(text, Some(&self.comment)),
+            CleanupMode::Scissors => {
+                let scissors = self.scissors_line();
+                let kept: Vec<&str> = text.lines().take_while(|l| *l != scissors).collect();
+                stripspace(&kept.join("\n"), None)
+            }
+        }
+    }
+
+    /// `text` without the comment headers written by [`Self::squash_message`],
+    /// each with the blank line following it.
+    fn strip_squash_headers(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut after_header = false;
+        for line in text.split_inclusive('\n') {
+            if self.is_squash_header(line.trim_end()) {
+                after_header = true;
+                continue;
+            }
+            if !std::mem::take(&mut after_header) || !line.trim_end().is_empty() {
+                out.push_str(line);
+            }
+        }
+        out
+    }
+
+    fn is_squash_header(&self, line: &str) -> bool {
+        let number = |n: &str| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit());
+        let Some(rest) = line
+            .strip_prefix(self.comment.as_str())
+            .and_then(|rest| rest.strip_prefix(' '))
+        else {
+            return false;
+        };
+        rest == "This is the 1st commit message:"
+            || rest
+                .strip_prefix("This is a combination of ")
+                .and_then(|n| n.strip_suffix(" commits."))
+                .is_some_and(number)
+            || rest
+                .strip_prefix("This is the commit message #")
+                .and_then(|n| n.strip_suffix(':'))
+                .is_some_and(number)
+    }
+
+    /// Default message for squashing `messages` (oldest first), with git's
+    /// "This is a combination of N commits." comment header.
+    pub fn squash_message(&self, messages: &[&str]) -> String {
+        let mut text = self.comment_lines(&format!(
+            "This is a combination of {} commits.",
+            messages.len()
+        ));
+        for (i, message) in messages.iter().enumerate() {
+            let title = if i == 0 {
+                "This is the 1st commit message:".to_string()
+            } else {
+                format!("This is the commit message #{}:", i + 1)
+            };
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(&self.comment_lines(&title));
+            text.push('\n');
+            text.push_str(message.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// First auto comment character that starts no line of `text`.
+fn auto_comment(text: &str) -> String {
+    AUTO_COMMENT_CHARS
+        .chars()
+        .find(|c| !text.lines().any(|line| line.starts_with(*c)))
+        .unwrap_or('#')
+        .to_string()
+}
+
+/// Resolve a `commit.template` path: `~/` is the home directory and
+/// relative paths start at the top of the working tree.
+fn template_path(repo: &impl GitRepo, path: &str) -> std::path::PathBuf {
+    if let (Some(rest), Some(home)) = (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        return std::path::PathBuf::from(home).join(rest);
+    }
+    match repo.workdir() {
+        Some(workdir) => workdir.join(path),
+        None => std::path::PathBuf::from(path),
+    }
+}
+
+/// `git stripspace`: drop lines starting with `comment` (if given), strip
+/// trailing whitespace, collapse runs of blank lines and remove leading and
+/// trailing ones. A non-empty result ends with a newline.
+pub fn stripspace(text: &str, comment: Option<&str>) -> String {
+    let mut out = String::new();
+    let mut pending_blank = false;
+    for line in text.lines() {
+        if comment.is_some_and(|c| line.starts_with(c)) {
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+        if pending_blank {
+            out.push('\n');
+            pending_blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Whether `line` looks like a `Token: value` trailer, e.g.
+/// `Signed-off-by: A U Thor <author@example.com>`.
+pub fn is_trailer_line(line: &str) -> bool {
+    let Some((token, value)) = line.split_once(':') else {
+        return false;
+    };
+    !token.is_empty()
+        && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !value.trim().is_empty()
+}
+
+/// Split a message into its body and the trailer lines of its final
+/// paragraph, like `git interpret-trailers --parse`.
+///
+/// The final paragraph counts as trailer block only when every line is a
+/// trailer (indented lines continue the previous trailer) and it is not the
+/// subject paragraph. The returned body has trailing whitespace removed.
+pub fn split_trailers(message: &str) -> (String, Vec<String>) {
+    let trimmed = message.trim_end();
+    let Some(start) = trimmed.rfind("\n\n") else {
+        return (trimmed.to_string(), Vec::new());
+    };
+    let (body, block) = (&trimmed[..start], &trimmed[start + 2..]);
+
+    let mut trailers: Vec<String> = Vec::new();
+    for line in block.lines() {
+        if line.starts_with([' ', '\t']) {
+            match trailers.last_mut() {
+                Some(last) => {
+                    last.push('\n');
+                    last.push_str(line);
+                }
+                None => return (trimmed.to_string(), Vec::new()),
+            }
+        } else if is_trailer_line(line) {
+            trailers.push(line.to_string());
+        } else {
+            return (trimmed.to_string(), Vec::new());
+        }
+    }
+    (body.trim_end().to_string(), trailers)
+}
+
+/// Rebuild a message from a body and trailer lines, separated by a blank
+/// line. Empty trailers are skipped; the result ends with a newline.
+pub fn join_trailers(body: &str, trailers: &[String]) -> String {
+    let trailers: Vec<&str> = trailers
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let mut message = body.trim_end().to_string();
+    if !trailers.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&trailers.join("\n"));
+    }
+    message.push('\n');
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(cleanup: CleanupMode) -> MessageOptions {
+        MessageOptions {
+            cleanup,
+            ..MessageOptions::default()
+        }
+    }
+
+    #[test]
+    fn strip_removes_comments_and_extra_blank_lines() {
+        let text = "\n\nFix parser  \n\n\n\nBody\n# a comment\n\n";
+        assert_eq!(
+            options(CleanupMode::Strip).cleanup(text),
+            "Fix parser\n\nBody\n"
+        );
+        assert_eq!(
+            options(CleanupMode::Whitespace).cleanup(text),
+            "Fix parser\n\nBody\n# a comment\n"
+        );
+        assert_eq!(options(CleanupMode::Verbatim).cleanup(text), text);
+    }
+
+    #[test]
+    fn scissors_cuts_everything_below_the_line() {
+        let opts = options(CleanupMode::Scissors);
+        let edited = opts.editor_text("Fix parser\n\n# kept\n");
+        assert_eq!(opts.cleanup(&edited), "Fix parser\n\n# kept\n");
+    }
+
+    #[test]
+    fn editor_text_round_trips_with_custom_comment_char() {
+        let opts = MessageOptions {
+            comment: ";".to_string(),
+            ..MessageOptions::default()
+        };
+        let text = opts.editor_text("Fix parser\n\n# not a comment\n");
+        assert!(text.contains("\n; Please enter the commit message"));
+        assert_eq!(opts.cleanup(&text), "Fix parser\n\n# not a comment\n");
+    }
+
+    #[test]
+    fn unedited_template_counts_as_empty() {
+        let opts = MessageOptions {
+            template: Some("Subject\n\n# Explain why\n".to_string()),
+            ..MessageOptions::default()
+        };
+        let text = opts.editor_text("");
+        assert!(text.starts_with("Subject\n\n# Explain why\n"));
+        assert_eq!(opts.cleanup(&text), "");
+        assert_eq!(
+            opts.cleanup(&text.replace("Subject", "Fix parser")),
+            "Fix parser\n"
+        );
+    }
+
+    #[test]
+    fn squash_message_has_combination_header() {
+        let text = MessageOptions::default().squash_message(&["Add a\n", "Fix a\n"]);
+        assert_eq!(
+            text,
+            "# This is a combination of 2 commits.\n\
+             # This is the 1st commit message:\n\nAdd a\n\n\
+             # This is the commit message #2:\n\nFix a\n"
+        );
+        assert_eq!(MessageOptions::default().cleanup(&text), "Add a\n\nFix a\n");
+    }
+
+    #[test]
+    fn squash_headers_are_dropped_without_strip_cleanup() {
+        let opts = options(CleanupMode::Whitespace);
+        let text = opts.squash_message(&["Add a\n", "Fix a\n\n# issue 12\n"]);
+        assert_eq!(opts.cleanup(&text), "Add a\n\nFix a\n\n# issue 12\n");
+        let opts = options(CleanupMode::Verbatim);
+        let text = opts.squash_message(&["Add a\n", "Fix a\n"]);
+        assert_eq!(opts.cleanup(&text), "Add a\n\nFix a\n");
+    }
+
+    #[test]
+    fn auto_comment_avoids_used_characters() {
+        assert_eq!(auto_comment("Fix\n#1 is fixed\n"), ";");
+        assert_eq!(auto_comment("Fix\n"), "#");
+    }
+
+    #[test]
+    fn split_finds_trailer_block() {
+        let (body, trailers) = split_trailers(
+            "Fix parser\n\nLonger text.\n\nSigned-off-by: A <a@x>\nCo-authored-by: B <b@x>\n",
+        );
+        assert_eq!(body, "Fix parser\n\nLonger text.");
+        assert_eq!(
+            trailers,
+            vec!["Signed-off-by: A <a@x>", "Co-authored-by: B <b@x>"]
+        );
+    }
+
+    #[test]
+    fn split_ignores_prose_paragraph() {
+        let message = "Fix parser\n\nNote: this is prose\nthat continues.\n";
+        let (body, trailers) = split_trailers(message);
+        assert_eq!(body, message.trim_end());
+        assert!(trailers.is_empty());
+    }
+
+    #[test]
+    fn subject_is_never_a_trailer() {
+        let (body, trailers) = split_trailers("fix: handle empty input\n");
+        assert_eq!(body, "fix: handle empty input");
+        assert!(trailers.is_empty());
+    }
+
+    #[test]
+    fn join_round_trips_and_skips_empty() {
+        let joined = join_trailers(
+            "Fix parser",
+            &["Signed-off-by: A <a@x>".to_string(), "  ".to_string()],
+        );
+        assert_eq!(joined, "Fix parser\n\nSigned-off-by: A <a@x>\n");
+        assert_eq!(join_trailers("Fix parser\n", &[]), "Fix parser\n");
+    }
+}
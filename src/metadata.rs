@@ -0,0 +1,184 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Author identities and dates for the commit metadata editor
+
+use crate::{repo::GitRepo, CommitInfo};
+
+/// Split `Name <email>` into its parts. Both must be non-empty.
+pub fn parse_identity(text: &str) -> Option<(String, String)> {
+    let (name, rest) = text.trim().split_once('<')?;
+    let email = rest.strip_suffix('>')?.trim();
+    let name = name.trim();
+    if name.is_empty() || email.is_empty() || email.contains(['<', '>']) {
+        return None;
+    }
+    Some((name.to_string(), email.to_string()))
+}
+
+/// Format a date the way the detail view shows it:
+/// `YYYY-MM-DD HH:MM:SS ±HHMM`.
+pub fn format_date(date: time::OffsetDateTime) -> String {
+    let offset = date.offset();
+    let (hours, minutes, _) = offset.as_hms();
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} {}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day(),
+        date.hour(),
+        date.minute(),
+        date.second(),
+        if offset.is_negative() { '-' } else { '+' },
+        hours.unsigned_abs(),
+        minutes.unsigned_abs(),
+    )
+}
+
+/// Parse a date in the format of [`format_date`] into seconds since the
+/// epoch and the UTC offset in minutes.
+pub fn parse_date(text: &str) -> Option<(i64, i32)> {
+    let mut parts = text.split_whitespace();
+    let (date, clock, zone) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let numbers =
+        |s: &str, sep: char| -> Option<Vec<i32>> { s.split(sep).map(|n| n.parse().ok()).collect() };
+    let [year, month, day] = numbers(date, '-')?[..] else {
+        return None;
+    };
+    let [hour, minute, second] = numbers(clock, ':')?[..] else {
+        return None;
+    };
+    let (sign, digits) = match zone.split_at_checked(1)? {
+        ("+", d) => (1, d),
+        ("-", d) => (-1, d),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let offset_minutes =
+        sign * (digits[..2].parse::<i32>().ok()? * 60 + digits[2..].parse::<i32>().ok()?);
+
+    let date = time::Date::from_calendar_date(
+        year,
+        time::Month::try_from(u8::try_from(month).ok()?).ok()?,
+        u8::try_from(day).ok()?,
+    )
+    .ok()?;
+    let clock = time::Time::from_hms(
+        u8::try_from(hour).ok()?,
+        u8::try_from(minute).ok()?,
+        u8::try_from(second).ok()?,
+    )
+    .ok()?;
+    let offset = time::UtcOffset::from_whole_seconds(offset_minutes * 60).ok()?;
+    let timestamp = time::PrimitiveDateTime::new(date, clock)
+        .assume_offset(offset)
+        .unix_timestamp();
+    Some((timestamp, offset_minutes))
+}
+
+/// Canonical identities (`Name <email>`) named by a `.mailmap` file.
+///
+/// Each entry line starts with the proper name and/or email; only entries
+/// that give both are usable as suggestions.
+pub fn parse_mailmap(text: &str) -> Vec<String> {
+    let mut identities = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some(close) = line.find('>') else {
+            continue;
+        };
+        if let Some((name, email)) = parse_identity(&line[..=close]) {
+            let identity = format!("{name} <{email}>");
+            if !identities.contains(&identity) {
+                identities.push(identity);
+            }
+        }
+    }
+    identities
+}
+
+/// Author suggestions for the metadata editor: identities from the
+/// repository's `.mailmap` (and `mailmap.file`) first, then the authors of
+/// `commits`, without duplicates.
+pub fn author_suggestions(repo: &impl GitRepo, commits: &[CommitInfo]) -> Vec<String> {
+    let mut files = Vec::new();
+    if let Some(workdir) = repo.workdir() {
+        files.push(workdir.join(".mailmap"));
+    }
+    if let Some(file) = repo.get_config_string("mailmap.file") {
+        files.push(std::path::PathBuf::from(file));
+    }
+
+    let mut suggestions: Vec<String> = files
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|text| parse_mailmap(&text))
+        .collect();
+    for commit in commits {
+        if let (Some(name), Some(email)) = (&commit.author, &commit.author_email) {
+            suggestions.push(format!("{name} <{email}>"));
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    suggestions.retain(|s| seen.insert(s.clone()));
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_round_trip() {
+        assert_eq!(
+            parse_identity(" Jane Doe <jane@example.com> "),
+            Some(("Jane Doe".to_string(), "jane@example.com".to_string()))
+        );
+        assert_eq!(parse_identity("Jane Doe"), None);
+        assert_eq!(parse_identity("<jane@example.com>"), None);
+    }
+
+    #[test]
+    fn date_round_trip() {
+        let (seconds, offset) = parse_date("2026-03-01 12:30:05 -0130").unwrap();
+        assert_eq!(offset, -90);
+        let date = time::OffsetDateTime::from_unix_timestamp(seconds)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_whole_seconds(offset * 60).unwrap());
+        assert_eq!(format_date(date), "2026-03-01 12:30:05 -0130");
+    }
+
+    #[test]
+    fn date_rejects_garbage() {
+        assert_eq!(parse_date("2026-13-01 12:00:00 +0000"), None);
+        assert_eq!(parse_date("2026-01-01 12:00 +0000"), None);
+        assert_eq!(parse_date("2026-01-01 12:00:00 0100"), None);
+    }
+
+    #[test]
+    fn mailmap_yields_canonical_identities() {
+        let text = "# comment\n\
+                    Jane Doe <jane@example.com> <jdoe@old.example.com>\n\
+                    <bob@example.com> <bob@laptop>\n\
+                    Jane Doe <jane@example.com> Jane <jane@home>\n";
+        assert_eq!(parse_mailmap(text), vec!["Jane Doe <jane@example.com>"]);
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Range-diff: compare two versions of a commit stack, like `git range-diff`
+
+use std::collections::HashMap;
+
+use crate::{merge3, CommitDiff, CommitInfo, DiffLine, DiffLineKind};
+
+/// Minimum similarity (0.0–1.0) for an old and a new commit to be paired.
+const PAIR_THRESHOLD: f64 = 0.5;
+
+/// Number of unchanged lines kept around each change in an interdiff.
+const INTERDIFF_CONTEXT: usize = 3;
+
+/// How a commit in the old stack relates to one in the new stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairStatus {
+    /// Paired and identical (same message and same patch).
+    Same,
+    /// Paired, but the message or the patch differs.
+    Changed,
+    /// Only in the old stack (dropped or squashed away).
+    Removed,
+    /// Only in the new stack (e.g. created by a split).
+    Added,
+}
+
+/// One row of a range-diff: an old commit, a new commit, or a pair.
+///
+/// Indices are positions in the old/new stacks (oldest first).
+#[derive(Debug, Clone)]
+pub struct RangeDiffEntry {
+    pub old: Option<(usize, CommitInfo)>,
+    pub new: Option<(usize, CommitInfo)>,
+    pub status: PairStatus,
+    /// Diff between the two versions for `Changed` pairs, see [`interdiff`].
+    pub interdiff: Vec<DiffLine>,
+}
+
+/// Compare two versions of a commit stack (both oldest first).
+///
+/// Commits are paired by patch similarity: the share of added and removed
+/// lines the two patches have in common. Pairs are picked greedily, best
+/// match first, so a reordered commit still finds its counterpart. The
+/// result follows the order of `new`, with unpaired old commits inserted
+/// before the first new commit that pairs with a later old commit.
+pub fn range_diff(old: &[CommitDiff], new: &[CommitDiff]) -> Vec<RangeDiffEntry> {
+    let old_changes: Vec<HashMap<String, usize>> = old.iter().map(changed_lines).collect();
+    let new_changes: Vec<HashMap<String, usize>> = new.iter().map(changed_lines).collect();
+
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (i, old_commit) in old.iter().enumerate() {
+        for (j, new_commit) in new.iter().enumerate() {
+            let score = if old_changes[i].is_empty() && new_changes[j].is_empty() {
+                // Empty patches can only be told apart by their message.
+                if old_commit.commit.summary == new_commit.commit.summary {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                similarity(&old_changes[i], &new_changes[j])
+            };
+            if score >= PAIR_THRESHOLD {
+                candidates.push((score, i, j));
+            }
+        }
+    }
+    // Best score first; prefer commits at similar positions on ties.
+    candidates.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1.abs_diff(a.2).cmp(&b.1.abs_diff(b.2)))
+    });
+
+    let mut old_pair: Vec<Option<usize>> = vec![None; old.len()];
+    let mut new_pair: Vec<Option<usize>> = vec![None; new.len()];
+    for (_, i, j) in candidates {
+        if old_pair[i].is_none() && new_pair[j].is_none() {
+            old_pair[i] = Some(j);
+            new_pair[j] = Some(i);
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut next_old = 0;
+    let mut push_removed_before = |limit: usize, entries: &mut Vec<RangeDiffEntry>| {
+        while next_old < limit {
+            if old_pair[next_old].is_none() {
+                entries.push(RangeDiffEntry {
+                    old: Some((next_old, old[next_old].commit.clone())),
+                    new: None,
+                    status: PairStatus::Removed,
+                    interdiff: Vec::new(),
+                });
+            }
+            next_old += 1;
+        }
+    };
+
+    for (j, new_commit) in new.iter().enumerate() {
+        match new_pair[j] {
+            Some(i) => {
+                push_removed_before(i, &mut entries);
+                let interdiff = interdiff(&old[i], new_commit);
+                let status = if interdiff.is_empty() {
+                    PairStatus::Same
+                } else {
+                    PairStatus::Changed
+                };
+                entries.push(RangeDiffEntry {
+                    old: Some((i, old[i].commit.clone())),
+                    new: Some((j, new_commit.commit.clone())),
+                    status,
+                    interdiff,
+                });
+            }
+            None => entries.push(RangeDiffEntry {
+                old: None,
+                new: Some((j, new_commit.commit.clone())),
+                status: PairStatus::Added,
+                interdiff: Vec::new(),
+            }),
+        }
+    }
+    push_removed_before(old.len(), &mut entries);
+
+    entries
+}
+
+/// Diff between two versions of a commit: their messages and patches
+/// compared line by line, with distant changes separated by a `~` line.
+///
+/// Each line's content is a line of the patch text (including its own
+/// `+`/`-` prefix), and its kind tells whether the line was added or
+/// removed between the versions. Empty when both versions are identical.
+pub fn interdiff(old: &CommitDiff, new: &CommitDiff) -> Vec<DiffLine> {
+    let lines = line_diff(&patch_text(old), &patch_text(new));
+    if lines.iter().all(|l| l.kind == DiffLineKind::Context) {
+        return Vec::new();
+    }
+    trim_context(lines)
+}
+
+/// Multiset of the added and removed lines of a commit, keyed by file path
+/// and prefix so that moving a line between files counts as a change.
+fn changed_lines(diff: &CommitDiff) -> HashMap<String, usize> {
+    let mut lines = HashMap::new();
+    for file in &diff.files {
+        let path = file
+            .new_path
+            .as_deref()
+            .or(file.old_path.as_deref())
+            .unwrap_or("");
+        for hunk in &file.hunks {
+            for line in &hunk.lines {
+                let prefix = match line.kind {
+                    DiffLineKind::Addition => '+',
+                    DiffLineKind::Deletion => '-',
+                    DiffLineKind::Context => continue,
+                };
+                let key = format!("{path}\0{prefix}{}", line.content.trim_end_matches('\n'));
+                *lines.entry(key).or_insert(0) += 1;
+            }
+        }
+    }
+    lines
+}
+
+/// Dice coefficient of two line multisets.
+fn similarity(a: &HashMap<String, usize>, b: &HashMap<String, usize>) -> f64 {
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let common: usize = a
+        .iter()
+        .map(|(line, &count)| count.min(b.get(line).copied().unwrap_or(0)))
+        .sum();
+    (2 * common) as f64 / total as f64
+}
+
+/// Render a commit as the text `git range-diff` compares: the indented
+/// message followed by each file's hunks without line numbers, which would
+/// otherwise make every commit after an edit look changed.
+fn patch_text(diff: &CommitDiff) -> Vec<String> {
+    let mut lines: Vec<String> = diff
+        .commit
+        .message
+        .trim_end()
+        .lines()
+        .map(|l| format!("    {l}"))
+        .collect();
+    for file in &diff.files {
+        let path = file
+            .new_path
+            .as_deref()
+            .or(file.old_path.as_deref())
+            .unwrap_or("<unknown>");
+        lines.push(String::new());
+        lines.push(format!("## {path} ##"));
+        for hunk in &file.hunks {
+            lines.push("@@".to_string());
+            for line in &hunk.lines {
+                let prefix = match line.kind {
+                    DiffLineKind::Addition => '+',
+                    DiffLineKind::Deletion => '-',
+                    DiffLineKind::Context => ' ',
+                };
+                lines.push(format!("{prefix}{}", line.content.trim_end_matches('\n')));
+            }
+        }
+    }
+    lines
+}
+
+/// Line diff of `old` against `new` via a longest common subsequence.
+/// Lines left unmatched come out as deletions followed by additions.
+fn line_diff(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let matched = merge3::match_lines(old, new);
+    let mut out = Vec::with_capacity(old.len().max(new.len()));
+    let mut next_new = 0;
+    for (line, matched) in old.iter().zip(matched) {
+        match matched {
+            Some(j) => {
+                out.extend(
+                    new[next_new..j]
+                        .iter()
+                        .map(|l| diff_line(DiffLineKind::Addition, l)),
+                );
+                out.push(diff_line(DiffLineKind::Context, line));
+                next_new = j + 1;
+            }
+            None => out.push(diff_line(DiffLineKind::Deletion, line)),
+        }
+    }
+    out.extend(
+        new[next_new..]
+            .iter()
+            .map(|l| diff_line(DiffLineKind::Addition, l)),
+    );
+    out
+}
+
+fn diff_line(kind: DiffLineKind, content: &str) -> DiffLine {
+    DiffLine {
+        kind,
+        content: content.to_string(),
+    }
+}
+
+/// Keep only changed lines and up to `INTERDIFF_CONTEXT` unchanged lines
+/// around them, separating distant groups with a `~` line.
+fn trim_context(lines: Vec<DiffLine>) -> Vec<DiffLine> {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.kind != DiffLineKind::Context)
+        .map(|(i, _)| i)
+        .collect();
+    let keep = |i: usize| {
+        changed
+            .iter()
+            .any(|&c| i + INTERDIFF_CONTEXT >= c && i <= c + INTERDIFF_CONTEXT)
+    };
+
+    let mut out = Vec::new();
+    let mut skipped = false;
+    for (i, line) in lines.into_iter().enumerate() {
+        if keep(i) {
+            if skipped && !out.is_empty() {
+                out.push(diff_line(DiffLineKind::Context, "~"));
+            }
+            skipped = false;
+            out.push(line);
+        } else {
+            skipped = true;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeltaStatus, FileDiff, Hunk};
+
+    fn commit(oid: &str, message: &str, added: &[&str]) -> CommitDiff {
+        CommitDiff {
+            commit: CommitInfo {
+                oid: oid.to_string(),
+                summary: message.lines().next().unwrap_or("").to_string(),
+                author: None,
+                date: None,
+                parent_oids: vec![],
+                message: message.to_string(),
+                author_email: None,
+                author_date: None,
+                committer: None,
+                committer_email: None,
+                commit_date: None,
+            },
+            files: vec![FileDiff {
+                old_path: Some("file.txt".to_string()),
+                new_path: Some("file.txt".to_string()),
+                status: DeltaStatus::Modified,
+                hunks: vec![Hunk {
+                    old_start: 1,
+                    old_lines: 0,
+                    new_start: 1,
+                    new_lines: added.len() as u32,
+                    lines: added
+                        .iter()
+                        .map(|l| diff_line(DiffLineKind::Addition, l))
+                        .collect(),
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn identical_stacks_pair_as_same() {
+        let old = vec![commit("a1", "A", &["a"]), commit("b1", "B", &["b"])];
+        let new = vec![commit("a2", "A", &["a"]), commit("b2", "B", &["b"])];
+
+        let entries = range_diff(&old, &new);
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.status == PairStatus::Same));
+    }
+
+    #[test]
+    fn reordered_commits_still_pair() {
+        let old = vec![commit("a1", "A", &["a"]), commit("b1", "B", &["b"])];
+        let new = vec![commit("b2", "B", &["b"]), commit("a2", "A", &["a"])];
+
+        let entries = range_diff(&old, &new);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].old.as_ref().unwrap().1.oid, "b1");
+        assert_eq!(entries[1].old.as_ref().unwrap().1.oid, "a1");
+        assert!(entries.iter().all(|e| e.status == PairStatus::Same));
+    }
+
+    #[test]
+    fn squash_shows_changed_and_removed() {
+        let old = vec![
+            commit("a1", "A", &["a1", "a2", "a3"]),
+            commit("b1", "B", &["b"]),
+        ];
+        let new = vec![commit("a2", "A", &["a1", "a2", "a3", "b"])];
+
+        let entries = range_diff(&old, &new);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].status, PairStatus::Changed);
+        assert!(entries[0]
+            .interdiff
+            .iter()
+            .any(|l| l.kind == DiffLineKind::Addition && l.content == "+b"));
+        assert_eq!(entries[1].status, PairStatus::Removed);
+        assert_eq!(entries[1].old.as_ref().unwrap().1.oid, "b1");
+    }
+
+    #[test]
+    fn reworded_commit_is_changed() {
+        let old = vec![commit("a1", "Old title", &["a"])];
+        let new = vec![commit("a2", "New title", &["a"])];
+
+        let entries = range_diff(&old, &new);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, PairStatus::Changed);
+        let changed: Vec<&str> = entries[0]
+            .interdiff
+            .iter()
+            .filter(|l| l.kind != DiffLineKind::Context)
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(changed, vec!["    Old title", "    New title"]);
+    }
+
+    #[test]
+    fn unrelated_commit_is_added() {
+        let old = vec![commit("a1", "A", &["a"])];
+        let new = vec![commit("a2", "A", &["a"]), commit("c2", "C", &["c"])];
+
+        let entries = range_diff(&old, &new);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].status, PairStatus::Added);
+    }
+
+    #[test]
+    fn large_rewrites_are_not_diffed_line_by_line() {
+        let old: Vec<String> = (0..3000).map(|i| format!("old {i}")).collect();
+        let mut new: Vec<String> = (0..3000).map(|i| format!("new {i}")).collect();
+        new.insert(0, "old 0".to_string());
+
+        let lines = line_diff(&old, &new);
+
+        assert_eq!(lines[0].kind, DiffLineKind::Context);
+        let count = |kind| lines.iter().filter(|l| l.kind == kind).count();
+        assert_eq!(count(DiffLineKind::Deletion), 2999);
+        assert_eq!(count(DiffLineKind::Addition), 3000);
+    }
+}
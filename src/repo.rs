@@ -17,8 +17,9 @@ pub mod git2_impl;
 pub use git2_impl::Git2Repo;
 
 use anyhow::Result;
+use std::collections::BTreeMap;
 
-use crate::{CommitDiff, CommitInfo};
+use crate::{fragmap::SpanCluster, CommitDiff, CommitInfo};
 
 /// Result of a rebase operation that may encounter merge conflicts.
 #[derive(Debug)]
@@ -37,7 +38,7 @@ pub enum RebaseOutcome {
 /// conflicts, then calls `rebase_continue` (which reads the resolved
 /// index and creates the commit) or `rebase_abort` (which restores
 /// the branch to `original_branch_oid`).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ConflictState {
     /// Human-readable label for the operation that triggered this conflict
     /// (e.g. "Drop", "Squash"). Used in dialog titles and messages.
@@ -50,6 +51,8 @@ pub struct ConflictState {
     pub new_tip_oid: String,
     /// The OID of the commit whose cherry-pick conflicted.
     pub conflicting_commit_oid: String,
+    /// Summary line of the conflicting commit.
+    pub conflicting_commit_summary: String,
     /// OIDs of commits that still need to be cherry-picked after the
     /// conflicting commit is resolved, in order (oldest first).
     pub remaining_oids: Vec<String>,
@@ -64,6 +67,233 @@ pub struct ConflictState {
     /// conflict the TUI should open the editor and then call
     /// `squash_finalize` instead of `rebase_continue`.
     pub squash_context: Option<SquashContext>,
+    /// When present, the stop happened while executing an edited plan (see
+    /// [`GitRepo::execute_plan`]); `rebase_continue` resumes the plan.
+    pub plan_context: Option<PlanContext>,
+    /// Fragmap clusters the conflicting commit touches in the conflicting
+    /// files, i.e. the code regions the conflict can be in. Left empty by
+    /// the repository and filled in by the TUI from its fragmap.
+    pub conflict_clusters: Vec<SpanCluster>,
+    /// Files resolved from a recorded resolution when `rerere` is enabled.
+    pub reused_resolutions: Vec<String>,
+    /// Kind of each conflicting file; files without an entry are plain
+    /// content conflicts.
+    pub conflict_kinds: BTreeMap<String, ConflictKind>,
+}
+
+impl ConflictState {
+    /// The kind of conflict `path` has.
+    pub fn kind(&self, path: &str) -> ConflictKind {
+        self.conflict_kinds
+            .get(path)
+            .cloned()
+            .unwrap_or(ConflictKind::Content)
+    }
+
+    /// The first conflicting file that cannot be merged as text and needs
+    /// the user to pick a side.
+    pub fn choice_file(&self) -> Option<&str> {
+        self.conflicting_files
+            .iter()
+            .find(|path| !self.kind(path).is_text())
+            .map(String::as_str)
+    }
+}
+
+/// How the two sides of a conflicting file disagree, which decides how the
+/// conflict can be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both sides changed the text of the file.
+    Content,
+    /// Both sides added the file with different content.
+    AddAdd,
+    /// One side deleted the file while the other modified it.
+    DeleteModify { deleted_by_us: bool },
+    /// Only our side has the file.
+    AddedByUs,
+    /// Only their side has the file.
+    AddedByThem,
+    /// Both sides deleted the file.
+    BothDeleted,
+    /// The file was renamed, so the path exists on at most one side;
+    /// `ours` and `theirs` tell which.
+    Rename { ours: bool, theirs: bool },
+    /// The file is binary on at least one side and cannot be merged by line.
+    Binary,
+    /// The sides disagree on the file mode, e.g. executable or symlink.
+    ModeChange { ours: u32, theirs: u32 },
+}
+
+impl ConflictKind {
+    /// Whether the conflict can be merged line by line, in a merge tool or
+    /// region by region.
+    pub fn is_text(&self) -> bool {
+        matches!(self, ConflictKind::Content | ConflictKind::AddAdd)
+    }
+
+    /// Short description shown next to the path.
+    pub fn label(&self) -> String {
+        match self {
+            ConflictKind::Content => "both modified".to_string(),
+            ConflictKind::AddAdd => "both added".to_string(),
+            ConflictKind::DeleteModify {
+                deleted_by_us: true,
+            } => "deleted by us".to_string(),
+            ConflictKind::DeleteModify {
+                deleted_by_us: false,
+            } => "deleted by them".to_string(),
+            ConflictKind::AddedByUs => "added by us".to_string(),
+            ConflictKind::AddedByThem => "added by them".to_string(),
+            ConflictKind::BothDeleted => "both deleted".to_string(),
+            ConflictKind::Rename {
+                ours: false,
+                theirs: false,
+            } => "renamed on both sides".to_string(),
+            ConflictKind::Rename { ours: true, .. } => "renamed by us".to_string(),
+            ConflictKind::Rename { .. } => "renamed by them".to_string(),
+            ConflictKind::Binary => "binary".to_string(),
+            ConflictKind::ModeChange { ours, theirs } => format!("mode {ours:o} vs {theirs:o}"),
+        }
+    }
+
+    /// What taking `side` as the whole resolution does.
+    pub fn choice(&self, side: ConflictSide) -> &'static str {
+        let present = match (self, side) {
+            (ConflictKind::DeleteModify { deleted_by_us }, ConflictSide::Ours) => !deleted_by_us,
+            (ConflictKind::DeleteModify { deleted_by_us }, ConflictSide::Theirs) => *deleted_by_us,
+            (ConflictKind::Rename { ours, .. }, ConflictSide::Ours) => *ours,
+            (ConflictKind::Rename { theirs, .. }, ConflictSide::Theirs) => *theirs,
+            (ConflictKind::AddedByUs, ConflictSide::Theirs)
+            | (ConflictKind::AddedByThem, ConflictSide::Ours)
+            | (ConflictKind::BothDeleted, _) => false,
+            _ => true,
+        };
+        match (self, side, present) {
+            (ConflictKind::DeleteModify { .. } | ConflictKind::BothDeleted, _, false) => {
+                "keep deleted"
+            }
+            (ConflictKind::DeleteModify { .. }, _, true) => "keep modified",
+            (
+                ConflictKind::Rename { .. } | ConflictKind::AddedByUs | ConflictKind::AddedByThem,
+                _,
+                false,
+            ) => "remove",
+            (
+                ConflictKind::Rename { .. } | ConflictKind::AddedByUs | ConflictKind::AddedByThem,
+                _,
+                true,
+            ) => "keep",
+            (ConflictKind::Binary, ConflictSide::Ours, _) => "take ours binary",
+            (ConflictKind::Binary, ConflictSide::Theirs, _) => "take theirs binary",
+            (_, ConflictSide::Ours, _) => "take ours",
+            (_, ConflictSide::Theirs, _) => "take theirs",
+        }
+    }
+}
+
+/// How conflicting hunks are settled when commits are replayed, like the
+/// `-X` strategy options of `git rebase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Leave conflicts for the user.
+    #[default]
+    Normal,
+    /// Take our side of conflicting hunks.
+    Ours,
+    /// Take their side of conflicting hunks.
+    Theirs,
+    /// Ignore all whitespace when comparing lines.
+    IgnoreAllSpace,
+    /// Ignore changes in the amount of whitespace.
+    IgnoreSpaceChange,
+}
+
+impl MergeStrategy {
+    /// Parse a `git rebase -X` option name. `ignore-whitespace` is accepted
+    /// as an alias of `ignore-all-space`.
+    pub fn from_option(option: &str) -> Option<Self> {
+        match option.trim() {
+            "ours" => Some(MergeStrategy::Ours),
+            "theirs" => Some(MergeStrategy::Theirs),
+            "ignore-all-space" | "ignore-whitespace" => Some(MergeStrategy::IgnoreAllSpace),
+            "ignore-space-change" => Some(MergeStrategy::IgnoreSpaceChange),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MergeStrategy::Normal => "Normal",
+            MergeStrategy::Ours => "Ours",
+            MergeStrategy::Theirs => "Theirs",
+            MergeStrategy::IgnoreAllSpace => "Ignore whitespace",
+            MergeStrategy::IgnoreSpaceChange => "Ignore space change",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            MergeStrategy::Normal => "Stop at every conflicting hunk",
+            MergeStrategy::Ours => "Keep the rewritten base where hunks conflict",
+            MergeStrategy::Theirs => "Keep the replayed commit where hunks conflict",
+            MergeStrategy::IgnoreAllSpace => "Lines differing only in whitespace match",
+            MergeStrategy::IgnoreSpaceChange => "Lines differing in amount of space match",
+        }
+    }
+}
+
+/// Side of a conflict taken as the whole resolution of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// One step of a rebase plan, with every message already decided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanStep {
+    /// Apply the commit on top of the tip, with `message` replacing its
+    /// message when given.
+    Pick {
+        oid: String,
+        message: Option<String>,
+    },
+    /// Meld the commit into the tip commit. `message` replaces the tip's
+    /// message when given.
+    Fold {
+        oid: String,
+        message: Option<String>,
+    },
+    /// Stop so the user can amend the tip commit (`edit`).
+    Stop,
+    /// Run a shell command at the top of the working tree with the tip
+    /// checked out; a failing command stops the plan.
+    Exec(String),
+}
+
+/// Where an edited plan stopped and what is left of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanContext {
+    /// The step that conflicted, stopped for editing or failed.
+    pub step: PlanStep,
+    /// Steps still to run after `step`, in order.
+    pub remaining: Vec<PlanStep>,
+    /// First line of output of a failed `Exec` step.
+    pub failure: Option<String>,
+}
+
+/// New author and message for a commit, applied by
+/// [`GitRepo::edit_commit_metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitMetadata {
+    pub author_name: String,
+    pub author_email: String,
+    /// Author date in seconds since the epoch.
+    pub author_time: i64,
+    /// UTC offset of the author date in minutes.
+    pub author_offset_minutes: i32,
+    pub message: String,
 }
 
 /// Extra state carried through a squash-time conflict so that the squash
@@ -82,6 +312,42 @@ pub struct SquashContext {
     pub descendant_oids: Vec<String>,
 }
 
+/// Both sides of one changed file, for handing to an external diff tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBlobs {
+    /// Path on the new side, or on the old side for a deleted file.
+    pub path: String,
+    /// Content on the old side; empty for an added file.
+    pub old: Vec<u8>,
+    /// Content on the new side; empty for a deleted file.
+    pub new: Vec<u8>,
+}
+
+/// Which remote-tracking refs count as "published" when protecting pushed
+/// commits from accidental rewrites (`tailor.protectPushed`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushedScope {
+    /// A commit is pushed when any remote-tracking ref can reach it.
+    AnyRemote,
+    /// A commit is pushed only when the branch's push destination can reach it.
+    PushDestination,
+}
+
+impl PushedScope {
+    /// Interpret the value of `tailor.protectPushed`.
+    ///
+    /// `false`, `off`, `no` and `0` disable the protection (`None`); `push`
+    /// selects `PushDestination`. Anything else, including an unset key,
+    /// means `AnyRemote`.
+    pub fn from_config(value: Option<&str>) -> Option<Self> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            Some("false" | "off" | "no" | "0") => None,
+            Some("push") => Some(PushedScope::PushDestination),
+            _ => Some(PushedScope::AnyRemote),
+        }
+    }
+}
+
 /// Abstraction over git repository operations.
 ///
 /// Isolates the `git2` crate to the `repo::git2_impl` module. Callers work
@@ -104,6 +370,53 @@ pub trait GitRepo {
     /// Returns the OID of the common ancestor as a string.
     fn find_reference_point(&self, commit_ish: &str) -> Result<String>;
 
+    /// Short name of the remote-tracking branch the current branch pushes to
+    /// (the equivalent of `@{push}`, e.g. `origin/feature`).
+    ///
+    /// The remote is taken from `branch.<name>.pushRemote`, then
+    /// `remote.pushDefault`, then `branch.<name>.remote`. With
+    /// `push.default=upstream` the upstream branch is returned instead.
+    /// Returns `None` when HEAD is detached or the ref does not exist.
+    fn push_destination(&self) -> Option<String>;
+
+    /// Find commits between `reference_oid` and `head_oid` that are already
+    /// published, i.e. reachable from a remote-tracking ref selected by
+    /// `scope`. Rewriting them requires a force-push.
+    fn find_pushed_commits(
+        &self,
+        head_oid: &str,
+        reference_oid: &str,
+        scope: PushedScope,
+    ) -> Result<Vec<String>>;
+
+    /// Find where HEAD forked from `commit_ish`, using the ref's reflog.
+    ///
+    /// Equivalent to `git merge-base --fork-point`: every commit the ref has
+    /// pointed at according to its reflog is considered, and the best common
+    /// ancestor of HEAD and those commits is returned — but only if it is
+    /// itself one of the reflog entries. This finds the real fork point even
+    /// after the upstream branch has been rewritten and force-pushed.
+    ///
+    /// Returns `Ok(None)` when `commit_ish` is not a reference with a reflog
+    /// or no fork point can be determined; callers should then fall back to
+    /// `find_reference_point`.
+    fn find_fork_point(&self, commit_ish: &str) -> Result<Option<String>>;
+
+    /// Pick a base reference to use when none is given on the command line.
+    ///
+    /// Candidates are tried in order:
+    /// 1. The upstream of the current branch (`@{upstream}`)
+    /// 2. The remote's default branch (`origin/HEAD`)
+    /// 3. The local branch named by `init.defaultBranch`
+    /// 4. The value of the `tailor.base` git config key
+    ///
+    /// Returns the short name of the first candidate that resolves to a
+    /// commit, or `None` when none of them do.
+    fn default_reference(&self) -> Option<String>;
+
+    /// Return the best common ancestor of two commit-ishes.
+    fn merge_base(&self, a: &str, b: &str) -> Result<String>;
+
     /// List commits from one commit back to another (inclusive).
     ///
     /// Walks the commit graph from `from_oid` back to `to_oid`, collecting
@@ -113,6 +426,10 @@ pub trait GitRepo {
     /// The range includes both endpoints.
     fn list_commits(&self, from_oid: &str, to_oid: &str) -> Result<Vec<CommitInfo>>;
 
+    /// Look up a single commit by any commit-ish, such as an abbreviated
+    /// hash from a rebase todo list.
+    fn commit_info(&self, commit_ish: &str) -> Result<CommitInfo>;
+
     /// Extract the full diff for a single commit compared to its first parent.
     ///
     /// For the root commit (no parents), diffs against an empty tree so all
@@ -128,6 +445,13 @@ pub trait GitRepo {
     /// span tracking.
     fn commit_diff_for_fragmap(&self, oid: &str) -> Result<CommitDiff>;
 
+    /// Read both sides of every file that differs between `from_oid` and
+    /// `to_oid`, with renames detected.
+    ///
+    /// With `from_oid` of `None`, compares `to_oid` against its first parent
+    /// (or the empty tree for a root commit).
+    fn diff_file_blobs(&self, from_oid: Option<&str>, to_oid: &str) -> Result<Vec<FileBlobs>>;
+
     /// Return a synthetic `CommitDiff` for changes staged in the index (index vs HEAD).
     ///
     /// Returns `None` when the index is clean (no staged changes).
@@ -210,6 +534,41 @@ pub trait GitRepo {
     /// no conflicts can arise from staged or unstaged working-tree changes.
     fn reword_commit(&self, commit_oid: &str, new_message: &str, head_oid: &str) -> Result<()>;
 
+    /// Reword several commits at once.
+    ///
+    /// `messages` maps commit OIDs to their new messages. The commits from
+    /// the oldest of them up to `head_oid` are recreated in one pass, keeping
+    /// every tree, and the branch ref is moved once at the end. Each message
+    /// goes through the `commit-msg` hook first; when any of them is
+    /// rejected nothing is rewritten and the error names every failing
+    /// commit.
+    fn reword_commits(&self, messages: &[(String, String)], head_oid: &str) -> Result<()>;
+
+    /// Replace the author, author date and message of an existing commit.
+    ///
+    /// Works like [`reword_commit`](GitRepo::reword_commit): the tree and
+    /// parents stay the same and the descendants up to `head_oid` are
+    /// rebuilt on top of the new commit.
+    fn edit_commit_metadata(
+        &self,
+        commit_oid: &str,
+        metadata: &CommitMetadata,
+        head_oid: &str,
+    ) -> Result<()>;
+
+    /// Run the `prepare-commit-msg` hook on a message about to be opened in
+    /// the editor and return the message as the hook left it.
+    ///
+    /// `source` and `commit_oid` are passed on like git does: `"commit"`
+    /// with the OID when rewording, `"squash"` when squashing. Returns the
+    /// message unchanged when no hook is installed.
+    fn prepare_commit_message(
+        &self,
+        message: &str,
+        source: &str,
+        commit_oid: Option<&str>,
+    ) -> Result<String>;
+
     /// Read a string value from the repository's git configuration.
     ///
     /// Returns `None` when the key does not exist or is not valid UTF-8.
@@ -224,14 +583,50 @@ pub trait GitRepo {
     /// and index contain the partially merged state for the user to resolve.
     fn drop_commit(&self, commit_oid: &str, head_oid: &str) -> Result<RebaseOutcome>;
 
+    /// Stable patch-id of a commit's change against its parent, as computed
+    /// by `git patch-id`. Two commits with the same patch-id make the same
+    /// change regardless of their message, author or base. Returns `None`
+    /// for merge and root commits.
+    fn patch_id(&self, oid: &str) -> Result<Option<String>>;
+
+    /// Drop several commits from the branch in a single rebase.
+    ///
+    /// Like `drop_commit`, but cherry-picks the descendants of the oldest
+    /// dropped commit onto its parent in one pass, skipping every commit in
+    /// `commit_oids`. Fails if any of them is not reachable from `head_oid`
+    /// or is a merge or root commit.
+    fn drop_commits(&self, commit_oids: &[String], head_oid: &str) -> Result<RebaseOutcome>;
+
+    /// Find branch commits whose changes are already present upstream.
+    ///
+    /// Like `git cherry`, compares the patch-ids of the commits from
+    /// `head_oid` back to its merge-base with `upstream` against the
+    /// patch-ids of the commits on `upstream` since that merge-base. Returns
+    /// the OIDs of branch commits that have an equivalent upstream commit.
+    /// Merge commits are never reported.
+    fn find_upstreamed_commits(&self, upstream: &str, head_oid: &str) -> Result<Vec<String>>;
+
     /// Resume a conflicted rebase after the user has resolved conflicts.
     ///
     /// Reads the current index (which the user resolved), creates a commit
     /// for the conflicting cherry-pick, then continues cherry-picking the
     /// remaining descendants. Returns a new `RebaseOutcome` — the next
     /// cherry-pick may also conflict.
+    ///
+    /// For a stopped plan the remaining plan steps run instead; after an
+    /// edit stop the branch tip (amended by the user, plus any staged
+    /// changes) is where the plan continues.
     fn rebase_continue(&self, state: &ConflictState) -> Result<RebaseOutcome>;
 
+    /// Skip the commit that conflicted, like `git rebase --skip`.
+    ///
+    /// Resets the index and working tree to `state.new_tip_oid`, dropping
+    /// the conflicting commit, then cherry-picks the remaining commits (or
+    /// runs the remaining plan steps). A stopped plan step is skipped by
+    /// discarding the changes made on top of it. The squash itself cannot
+    /// be skipped.
+    fn rebase_skip(&self, state: &ConflictState) -> Result<RebaseOutcome>;
+
     /// Abort a conflicted rebase and restore the branch to its original state.
     ///
     /// Resets the branch ref to `state.original_branch_oid`, cleans up the
@@ -253,6 +648,21 @@ pub trait GitRepo {
     /// (entries with stage > 0), sorted alphabetically and deduplicated.
     fn read_conflicting_files(&self) -> Vec<String>;
 
+    /// Classify every conflicting file in the index; see [`ConflictKind`].
+    fn read_conflict_kinds(&self) -> BTreeMap<String, ConflictKind>;
+
+    /// Merge the conflicting text files of the index again with `strategy`,
+    /// writing and staging those that now merge cleanly. Returns their paths.
+    ///
+    /// The index stages hold the inputs of the step that conflicted, so this
+    /// retries that step without replaying it from scratch.
+    fn retry_conflicts(&self, strategy: MergeStrategy) -> Result<Vec<String>>;
+
+    /// Resolve the conflict of `path` by taking `side` whole, including its
+    /// file mode. When the file does not exist on that side, it is removed
+    /// from the index and the working tree.
+    fn take_conflict_side(&self, path: &str, side: ConflictSide) -> Result<()>;
+
     /// Squash two commits into one.
     ///
     /// Creates a single commit that combines `target_oid` (older) and
@@ -304,6 +714,21 @@ pub trait GitRepo {
         original_branch_oid: &str,
     ) -> Result<RebaseOutcome>;
 
+    /// Execute an edited rebase plan on top of `base_oid`.
+    ///
+    /// Steps run in order from `base_oid`. A pick whose parent already is
+    /// the tip and whose message is unchanged keeps the commit as it is. The
+    /// branch ref is moved once the whole plan has run. A conflict, an edit
+    /// stop or a failing command returns `RebaseOutcome::Conflict` with a
+    /// `plan_context`; `rebase_continue` resumes the plan and `rebase_abort`
+    /// restores `head_oid`.
+    fn execute_plan(
+        &self,
+        base_oid: &str,
+        steps: &[PlanStep],
+        head_oid: &str,
+    ) -> Result<RebaseOutcome>;
+
     /// Stage a working-tree file, clearing any conflict entries for that path.
     ///
     /// Equivalent to `git add <path>`. Reads the file from the working directory,
@@ -320,3 +745,11 @@ impl ConflictState {
         self.squash_context.is_some()
     }
 }
+
+/// Parse a git config boolean; anything unrecognized counts as false.
+pub(crate) fn config_bool(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "on" | "1"
+    )
+}
@@ -13,17 +13,43 @@
 // limitations under the License.
 
 use anyhow::{Context, Result};
-use std::collections::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use crate::{fragmap, CommitDiff, CommitInfo, DiffLine, DiffLineKind, FileDiff, Hunk};
+use crate::{
+    fragmap, hooks::Hooks, rerere, signing::SigningConfig, CommitDiff, CommitInfo, DiffLine,
+    DiffLineKind, FileDiff, Hunk,
+};
 
-use super::GitRepo;
+use super::{
+    CommitMetadata, ConflictKind, ConflictSide, FileBlobs, GitRepo, MergeStrategy, PlanContext,
+    PlanStep, PushedScope,
+};
 
 /// Concrete git repository backed by `libgit2` via the `git2` crate.
 ///
 /// Construct with [`Git2Repo::open`]; then use through the [`GitRepo`] trait.
 pub struct Git2Repo {
     inner: git2::Repository,
+    /// Whether `pre-commit` and `commit-msg` hooks run (`--no-verify`
+    /// turns them off).
+    verify: bool,
+    /// Old → new OIDs of commits rewritten by the current operation, fed to
+    /// the `post-rewrite` hook once the branch ref is updated.
+    rewritten: RefCell<Vec<(git2::Oid, git2::Oid)>>,
+    /// Files the last conflict resolved from recorded resolutions, taken by
+    /// the conflict state built for it.
+    rerere_resolved: RefCell<Vec<String>>,
+    /// Use the author date as committer date of rewritten commits instead
+    /// of the current time.
+    committer_date_is_author_date: bool,
+    /// How conflicting hunks are settled when commits are replayed.
+    merge_strategy: MergeStrategy,
+    /// Simulate rewrites: new objects stay in memory and refs, index and
+    /// working tree are left alone (see [`Git2Repo::dry_run`]).
+    dry_run: bool,
+    /// Where the branch would point after a simulated rewrite.
+    simulated_tip: RefCell<Option<git2::Oid>>,
 }
 
 impl Git2Repo {
@@ -33,13 +59,83 @@ impl Git2Repo {
         loop {
             let result = git2::Repository::open(&path);
             if let Ok(repo) = result {
-                return Ok(Git2Repo { inner: repo });
+                return Ok(Git2Repo {
+                    inner: repo,
+                    verify: true,
+                    rewritten: RefCell::new(Vec::new()),
+                    rerere_resolved: RefCell::new(Vec::new()),
+                    committer_date_is_author_date: false,
+                    merge_strategy: MergeStrategy::Normal,
+                    dry_run: false,
+                    simulated_tip: RefCell::new(None),
+                });
             }
             if !path.pop() {
                 anyhow::bail!("Could not find git repository root");
             }
         }
     }
+
+    /// Enable or disable the `pre-commit` and `commit-msg` hooks, like
+    /// `git commit --no-verify`.
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+
+    /// Keep the author date as committer date of rewritten commits, like
+    /// `git rebase --committer-date-is-author-date`.
+    pub fn set_committer_date_is_author_date(&mut self, enabled: bool) {
+        self.committer_date_is_author_date = enabled;
+    }
+
+    /// Settle conflicting hunks of replayed commits with `strategy`, like
+    /// `git rebase -X <option>`.
+    pub fn set_merge_strategy(&mut self, strategy: MergeStrategy) {
+        self.merge_strategy = strategy;
+    }
+
+    /// A second handle on this repository that simulates rewrites.
+    ///
+    /// Objects it writes go to an in-memory object database that is dropped
+    /// with it. Moving the branch only records the would-be tip (see
+    /// [`Git2Repo::simulated_tip`]); the index, working tree and hooks are
+    /// not touched and commits are not signed. A conflict is kept in the
+    /// handle's in-memory index, where `read_conflicting_files` finds it.
+    pub fn dry_run(&self) -> Result<Git2Repo> {
+        let inner = git2::Repository::open(self.inner.path())
+            .context("failed to open repository for simulation")?;
+        // Above the loose and packed backends, so all writes land here.
+        inner
+            .odb()?
+            .add_new_mempack_backend(1000)
+            .context("failed to create in-memory object database")?;
+        Ok(Git2Repo {
+            inner,
+            verify: false,
+            rewritten: RefCell::new(Vec::new()),
+            rerere_resolved: RefCell::new(Vec::new()),
+            committer_date_is_author_date: self.committer_date_is_author_date,
+            merge_strategy: self.merge_strategy,
+            dry_run: true,
+            simulated_tip: RefCell::new(None),
+        })
+    }
+
+    /// Where a simulated rewrite left the branch, or `None` when nothing
+    /// has been simulated (or this handle is not a [`Git2Repo::dry_run`]).
+    pub fn simulated_tip(&self) -> Option<String> {
+        self.simulated_tip.borrow().map(|oid| oid.to_string())
+    }
+
+    /// Merge options for cherry-picking commits with the configured strategy.
+    fn cherrypick_options(&self) -> git2::MergeOptions {
+        let flags = StrategyFlags::from(self.merge_strategy);
+        let mut opts = git2::MergeOptions::new();
+        opts.file_favor(flags.favor)
+            .ignore_whitespace(flags.ignore_whitespace)
+            .ignore_whitespace_change(flags.ignore_whitespace_change);
+        opts
+    }
 }
 
 impl GitRepo for Git2Repo {
@@ -71,6 +167,157 @@ impl GitRepo for Git2Repo {
         Ok(reference_oid.to_string())
     }
 
+    fn push_destination(&self) -> Option<String> {
+        let head = self.inner.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        let branch = head.shorthand()?.to_string();
+
+        if self.get_config_string("push.default").as_deref() == Some("upstream") {
+            return self.current_upstream_name();
+        }
+
+        let remote = self
+            .get_config_string(&format!("branch.{branch}.pushRemote"))
+            .or_else(|| self.get_config_string("remote.pushDefault"))
+            .or_else(|| self.get_config_string(&format!("branch.{branch}.remote")))?;
+        let name = format!("{remote}/{branch}");
+        self.inner
+            .find_reference(&format!("refs/remotes/{name}"))
+            .ok()?;
+        Some(name)
+    }
+
+    fn find_pushed_commits(
+        &self,
+        head_oid: &str,
+        reference_oid: &str,
+        scope: PushedScope,
+    ) -> Result<Vec<String>> {
+        let repo = &self.inner;
+        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID")?;
+        let reference_git_oid =
+            git2::Oid::from_str(reference_oid).context("Invalid reference OID")?;
+
+        let mut revwalk = repo.revwalk()?;
+        match scope {
+            PushedScope::AnyRemote => revwalk.push_glob("refs/remotes/*")?,
+            PushedScope::PushDestination => match self.push_destination() {
+                Some(name) => revwalk.push_ref(&format!("refs/remotes/{name}"))?,
+                None => return Ok(Vec::new()),
+            },
+        }
+        revwalk.hide(reference_git_oid)?;
+        let published: HashSet<git2::Oid> = revwalk.collect::<std::result::Result<_, _>>()?;
+
+        Ok(self
+            .walk_range(head_git_oid, reference_git_oid)?
+            .into_iter()
+            .filter(|oid| published.contains(oid))
+            .map(|oid| oid.to_string())
+            .collect())
+    }
+
+    fn find_fork_point(&self, commit_ish: &str) -> Result<Option<String>> {
+        let repo = &self.inner;
+
+        let reference = match repo.resolve_reference_from_short_name(commit_ish) {
+            Ok(r) => r,
+            Err(_) => return Ok(None),
+        };
+        let Some(refname) = reference.name() else {
+            return Ok(None);
+        };
+        let reflog = repo
+            .reflog(refname)
+            .context(format!("Failed to read reflog of '{}'", refname))?;
+
+        let mut candidates: HashSet<git2::Oid> = reflog
+            .iter()
+            .map(|entry| entry.id_new())
+            .filter(|oid| !oid.is_zero())
+            .collect();
+        if let Some(tip) = reference.target() {
+            candidates.insert(tip);
+        }
+
+        let head_oid = repo
+            .head()
+            .context("Failed to get HEAD")?
+            .target()
+            .context("HEAD is not a direct reference")?;
+
+        // Best common ancestor of HEAD and the union of all reflog entries:
+        // the merge-base that descends from every other merge-base.
+        let bases: HashSet<git2::Oid> = candidates
+            .iter()
+            .filter_map(|&candidate| repo.merge_base(head_oid, candidate).ok())
+            .collect();
+        let mut best = None;
+        for &base in &bases {
+            let mut dominates = true;
+            for &other in &bases {
+                if other != base && !repo.graph_descendant_of(base, other)? {
+                    dominates = false;
+                    break;
+                }
+            }
+            if dominates {
+                best = Some(base);
+                break;
+            }
+        }
+
+        Ok(best
+            .filter(|oid| candidates.contains(oid))
+            .map(|oid| oid.to_string()))
+    }
+
+    fn default_reference(&self) -> Option<String> {
+        let repo = &self.inner;
+
+        if let Some(upstream) = self.current_upstream_name() {
+            return Some(upstream);
+        }
+
+        if let Ok(origin_head) = repo.find_reference("refs/remotes/origin/HEAD") {
+            if let Ok(resolved) = origin_head.resolve() {
+                if let Some(name) = resolved.shorthand() {
+                    return Some(name.to_string());
+                }
+            }
+        }
+
+        if let Some(name) = self.get_config_string("init.defaultBranch") {
+            if repo.find_branch(&name, git2::BranchType::Local).is_ok() {
+                return Some(name);
+            }
+        }
+
+        let base = self.get_config_string("tailor.base")?;
+        repo.revparse_single(&base).ok()?;
+        Some(base)
+    }
+
+    fn merge_base(&self, a: &str, b: &str) -> Result<String> {
+        let a_oid = self
+            .inner
+            .revparse_single(a)
+            .context(format!("Failed to resolve '{}'", a))?
+            .id();
+        let b_oid = self
+            .inner
+            .revparse_single(b)
+            .context(format!("Failed to resolve '{}'", b))?
+            .id();
+        let base = self
+            .inner
+            .merge_base(a_oid, b_oid)
+            .context("Failed to find merge base")?;
+        Ok(base.to_string())
+    }
+
     fn list_commits(&self, from_oid: &str, to_oid: &str) -> Result<Vec<CommitInfo>> {
         let from_object = self
             .inner
@@ -103,6 +350,16 @@ impl GitRepo for Git2Repo {
         Ok(commits)
     }
 
+    fn commit_info(&self, commit_ish: &str) -> Result<CommitInfo> {
+        let commit = self
+            .inner
+            .revparse_single(commit_ish)
+            .context(format!("Failed to resolve '{}'", commit_ish))?
+            .peel_to_commit()
+            .context("Resolved object is not a commit")?;
+        Ok(commit_info_from(&commit))
+    }
+
     fn commit_diff(&self, oid: &str) -> Result<CommitDiff> {
         let object = self
             .inner
@@ -155,6 +412,58 @@ impl GitRepo for Git2Repo {
         extract_commit_diff(&diff, &commit)
     }
 
+    fn diff_file_blobs(&self, from_oid: Option<&str>, to_oid: &str) -> Result<Vec<FileBlobs>> {
+        let commit = self
+            .inner
+            .revparse_single(to_oid)
+            .context(format!("Failed to resolve '{}'", to_oid))?
+            .peel_to_commit()
+            .context("Resolved object is not a commit")?;
+        let new_tree = commit.tree().context("Failed to get commit tree")?;
+        let old_tree = match from_oid {
+            Some(oid) => Some(
+                self.inner
+                    .revparse_single(oid)
+                    .context(format!("Failed to resolve '{}'", oid))?
+                    .peel_to_tree()?,
+            ),
+            None if commit.parent_count() > 0 => Some(commit.parent(0)?.tree()?),
+            None => None,
+        };
+
+        let mut diff = self
+            .inner
+            .diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), None)?;
+        diff.find_similar(None)?;
+
+        // Submodule entries have no blob; show them as empty.
+        let read = |file: git2::DiffFile| -> Vec<u8> {
+            if !file.exists() {
+                return Vec::new();
+            }
+            self.inner
+                .find_blob(file.id())
+                .map(|blob| blob.content().to_vec())
+                .unwrap_or_default()
+        };
+        diff.deltas()
+            .map(|delta| {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .context("diff entry has no path")?
+                    .to_string_lossy()
+                    .into_owned();
+                Ok(FileBlobs {
+                    path,
+                    old: read(delta.old_file()),
+                    new: read(delta.new_file()),
+                })
+            })
+            .collect()
+    }
+
     fn staged_diff(&self) -> Option<CommitDiff> {
         let head = self.inner.head().ok()?.peel_to_tree().ok();
 
@@ -233,6 +542,7 @@ impl GitRepo for Git2Repo {
             .collect();
 
         self.check_dirty_overlap(&commit_paths)?;
+        self.begin_rewrite();
 
         // Create one commit per file, each building on the previous
         let mut current_base_oid = parent_commit.id();
@@ -262,30 +572,26 @@ impl GitRepo for Git2Repo {
             let new_tree_oid = new_index.write_tree_to(repo)?;
             let new_tree = repo.find_tree(new_tree_oid)?;
 
+            self.run_pre_commit(&new_tree)?;
             let author = commit.author();
-            let committer = commit.committer();
-            let message = format!(
+            let message = self.run_commit_msg(&format!(
                 "{} ({}/{})",
                 commit.summary().unwrap_or("split"),
                 delta_idx + 1,
                 file_count
-            );
+            ))?;
 
-            let new_oid = repo.commit(
-                None,
-                &author,
-                &committer,
-                &message,
-                &new_tree,
-                &[&base_commit],
-            )?;
+            let new_oid = self.create_commit(&author, &message, &new_tree, &[&base_commit])?;
             current_base_oid = new_oid;
         }
 
+        // Like `git rebase` after an edit stop, the original commit maps to
+        // the last commit of the split.
+        self.record_rewrite(commit_git_oid, current_base_oid);
         let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid head OID")?;
         current_base_oid =
             self.rebase_descendants(commit_git_oid, head_git_oid, current_base_oid)?;
-        self.advance_branch_ref(current_base_oid, "git-tailor: split per-file")?;
+        self.finish_rewrite(current_base_oid, "git-tailor: split per-file")?;
 
         Ok(())
     }
@@ -339,6 +645,7 @@ impl GitRepo for Git2Repo {
             .collect();
 
         self.check_dirty_overlap(&commit_paths)?;
+        self.begin_rewrite();
 
         // Build one commit per hunk using incremental blob manipulation.
         //
@@ -369,31 +676,27 @@ impl GitRepo for Git2Repo {
             let next_tree = repo.find_tree(next_tree_oid)?;
             let base_commit = repo.find_commit(current_base_oid)?;
 
+            self.run_pre_commit(&next_tree)?;
             let author = commit.author();
-            let committer = commit.committer();
-            let message = format!(
+            let message = self.run_commit_msg(&format!(
                 "{} ({}/{})",
                 commit.summary().unwrap_or("split"),
                 target_k + 1,
                 hunk_count
-            );
+            ))?;
 
-            let new_oid = repo.commit(
-                None,
-                &author,
-                &committer,
-                &message,
-                &next_tree,
-                &[&base_commit],
-            )?;
+            let new_oid = self.create_commit(&author, &message, &next_tree, &[&base_commit])?;
             current_base_oid = new_oid;
             current_tree_oid = next_tree_oid;
         }
 
+        // Like `git rebase` after an edit stop, the original commit maps to
+        // the last commit of the split.
+        self.record_rewrite(commit_git_oid, current_base_oid);
         let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid head OID")?;
         current_base_oid =
             self.rebase_descendants(commit_git_oid, head_git_oid, current_base_oid)?;
-        self.advance_branch_ref(current_base_oid, "git-tailor: split per-hunk")?;
+        self.finish_rewrite(current_base_oid, "git-tailor: split per-hunk")?;
 
         Ok(())
     }
@@ -449,6 +752,7 @@ impl GitRepo for Git2Repo {
             })
             .collect();
         self.check_dirty_overlap(&commit_paths)?;
+        self.begin_rewrite();
 
         // Build delta_hunk_groups: for each (delta_idx, hunk_idx), its group index.
         let mut delta_hunk_groups: Vec<Vec<usize>> = Vec::new();
@@ -511,30 +815,26 @@ impl GitRepo for Git2Repo {
             let next_tree = repo.find_tree(next_tree_oid)?;
             let base_commit = repo.find_commit(current_base_oid)?;
 
+            self.run_pre_commit(&next_tree)?;
             let author = commit.author();
-            let committer = commit.committer();
-            let message = format!(
+            let message = self.run_commit_msg(&format!(
                 "{} ({}/{})",
                 commit.summary().unwrap_or("split"),
                 out_pos + 1,
                 split_count
-            );
-            let new_oid = repo.commit(
-                None,
-                &author,
-                &committer,
-                &message,
-                &next_tree,
-                &[&base_commit],
-            )?;
+            ))?;
+            let new_oid = self.create_commit(&author, &message, &next_tree, &[&base_commit])?;
             current_base_oid = new_oid;
             current_tree_oid = next_tree_oid;
         }
 
+        // Like `git rebase` after an edit stop, the original commit maps to
+        // the last commit of the split.
+        self.record_rewrite(commit_git_oid, current_base_oid);
         let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid head OID")?;
         current_base_oid =
             self.rebase_descendants(commit_git_oid, head_git_oid, current_base_oid)?;
-        self.advance_branch_ref(current_base_oid, "git-tailor: split per-hunk-group")?;
+        self.finish_rewrite(current_base_oid, "git-tailor: split per-hunk-group")?;
 
         let _ = current_tree_oid; // suppress unused warning
         Ok(())
@@ -605,32 +905,101 @@ impl GitRepo for Git2Repo {
     }
 
     fn reword_commit(&self, commit_oid: &str, new_message: &str, head_oid: &str) -> Result<()> {
-        let repo = &self.inner;
+        self.rewrite_commit(commit_oid, None, new_message, head_oid)
+    }
 
-        let commit_git_oid =
-            git2::Oid::from_str(commit_oid).context("Invalid commit OID for reword")?;
+    fn reword_commits(&self, messages: &[(String, String)], head_oid: &str) -> Result<()> {
+        let repo = &self.inner;
         let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID for reword")?;
-        let commit = repo.find_commit(commit_git_oid)?;
 
-        let parents: Vec<git2::Commit> = (0..commit.parent_count())
-            .map(|i| commit.parent(i))
-            .collect::<std::result::Result<_, _>>()?;
-        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        let mut new_messages: HashMap<git2::Oid, String> = HashMap::new();
+        let mut errors = Vec::new();
+        for (oid, message) in messages {
+            let git_oid = git2::Oid::from_str(oid).context("Invalid commit OID for reword")?;
+            match self.run_commit_msg(message) {
+                Ok(message) => {
+                    new_messages.insert(git_oid, message);
+                }
+                Err(e) => errors.push(format!("{}: {e}", &oid[..oid.len().min(10)])),
+            }
+        }
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("; "));
+        }
+        if new_messages.is_empty() {
+            return Ok(());
+        }
 
-        let new_oid = repo.commit(
-            None,
-            &commit.author(),
-            &commit.committer(),
-            new_message,
-            &commit.tree()?,
-            &parent_refs,
-        )?;
+        // Walk back from HEAD until every reworded commit has been seen.
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head_git_oid)?;
+        revwalk.simplify_first_parent()?;
+        let mut chain: Vec<git2::Oid> = Vec::new();
+        let mut remaining = new_messages.len();
+        for oid_result in revwalk {
+            let oid = oid_result?;
+            chain.push(oid);
+            if new_messages.contains_key(&oid) {
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+        }
+        if remaining > 0 {
+            anyhow::bail!("Not all reworded commits are ancestors of HEAD");
+        }
+        chain.reverse();
+
+        // Only messages change, so every tree is reused as is; parents are
+        // mapped to their rewritten counterparts.
+        self.begin_rewrite();
+        let mut mapped: HashMap<git2::Oid, git2::Oid> = HashMap::new();
+        let mut tip = head_git_oid;
+        for oid in chain {
+            let commit = repo.find_commit(oid)?;
+            let parents: Vec<git2::Commit> = commit
+                .parent_ids()
+                .map(|p| repo.find_commit(*mapped.get(&p).unwrap_or(&p)))
+                .collect::<std::result::Result<_, _>>()?;
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            let message = match new_messages.get(&oid) {
+                Some(message) => message.as_str(),
+                None => commit.message().unwrap_or(""),
+            };
+            tip = self.create_commit(&commit.author(), message, &commit.tree()?, &parent_refs)?;
+            mapped.insert(oid, tip);
+            self.record_rewrite(oid, tip);
+        }
 
-        let tip = self.rebase_descendants(commit_git_oid, head_git_oid, new_oid)?;
-        self.advance_branch_ref(tip, "reword: update branch ref")?;
+        self.finish_rewrite(tip, "reword: update branch ref")?;
         Ok(())
     }
 
+    fn edit_commit_metadata(
+        &self,
+        commit_oid: &str,
+        metadata: &CommitMetadata,
+        head_oid: &str,
+    ) -> Result<()> {
+        let when = git2::Time::new(metadata.author_time, metadata.author_offset_minutes);
+        let author = git2::Signature::new(&metadata.author_name, &metadata.author_email, &when)
+            .context("Invalid author")?;
+        self.rewrite_commit(commit_oid, Some(&author), &metadata.message, head_oid)
+    }
+
+    fn prepare_commit_message(
+        &self,
+        message: &str,
+        source: &str,
+        commit_oid: Option<&str>,
+    ) -> Result<String> {
+        let mut args = vec![source];
+        args.extend(commit_oid);
+        self.hooks()
+            .run_message_hook("prepare-commit-msg", message, &args)
+    }
+
     fn get_config_string(&self, key: &str) -> Option<String> {
         self.inner.config().ok()?.get_string(key).ok()
     }
@@ -655,40 +1024,103 @@ impl GitRepo for Git2Repo {
 
         // Cherry-pick each descendant onto the new chain, starting from the
         // dropped commit's parent.
+        self.begin_rewrite();
         let result = self.cherry_pick_chain(parent_oid, &descendants)?;
-        match result {
-            CherryPickResult::Complete(tip) => {
-                self.advance_branch_ref(tip, "git-tailor: drop commit")?;
-                self.checkout_head()?;
-                Ok(super::RebaseOutcome::Complete)
+        self.finish_drop(result, &descendants, original_branch_oid, "drop commit")
+    }
+
+    fn drop_commits(&self, commit_oids: &[String], head_oid: &str) -> Result<super::RebaseOutcome> {
+        let repo = &self.inner;
+
+        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID for drop")?;
+        let drop_set: HashSet<git2::Oid> = commit_oids
+            .iter()
+            .map(|oid| git2::Oid::from_str(oid).context("Invalid commit OID for drop"))
+            .collect::<Result<_>>()?;
+        if drop_set.is_empty() {
+            anyhow::bail!("No commits to drop");
+        }
+
+        // Walk back from HEAD until every dropped commit has been seen; the
+        // last one reached is the oldest and its parent is the new base.
+        let mut chain: Vec<git2::Oid> = Vec::new();
+        let mut unseen = drop_set.clone();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head_git_oid)?;
+        for oid_result in revwalk {
+            let oid = oid_result?;
+            chain.push(oid);
+            unseen.remove(&oid);
+            if unseen.is_empty() {
+                break;
             }
-            CherryPickResult::Conflict {
-                tip,
-                conflicting_idx,
-            } => {
-                let conflicting_oid = descendants[conflicting_idx];
-                let remaining: Vec<String> = descendants[conflicting_idx + 1..]
-                    .iter()
-                    .map(|oid| oid.to_string())
-                    .collect();
+        }
+        if let Some(missing) = unseen.iter().next() {
+            anyhow::bail!("Commit {missing} is not an ancestor of HEAD");
+        }
 
-                Ok(super::RebaseOutcome::Conflict(Box::new(
-                    super::ConflictState {
-                        operation_label: "Drop".to_string(),
-                        original_branch_oid,
-                        new_tip_oid: tip.to_string(),
-                        conflicting_commit_oid: conflicting_oid.to_string(),
-                        remaining_oids: remaining,
-                        conflicting_files: collect_conflict_files(repo),
-                        still_unresolved: false,
-                        squash_context: None,
-                    },
-                )))
+        for oid in &drop_set {
+            if repo.find_commit(*oid)?.parent_count() != 1 {
+                anyhow::bail!("Cannot drop a merge or root commit");
+            }
+        }
+
+        let oldest = chain.pop().context("No commits to drop")?;
+        let parent_oid = repo.find_commit(oldest)?.parent_id(0)?;
+
+        chain.reverse();
+        let descendants: Vec<git2::Oid> = chain
+            .into_iter()
+            .filter(|oid| !drop_set.contains(oid))
+            .collect();
+
+        self.begin_rewrite();
+        let result = self.cherry_pick_chain(parent_oid, &descendants)?;
+        self.finish_drop(result, &descendants, head_oid.to_string(), "drop commits")
+    }
+
+    fn patch_id(&self, oid: &str) -> Result<Option<String>> {
+        let git_oid = git2::Oid::from_str(oid).context("Invalid commit OID")?;
+        Ok(self.commit_patch_id(git_oid)?.map(|id| id.to_string()))
+    }
+
+    fn find_upstreamed_commits(&self, upstream: &str, head_oid: &str) -> Result<Vec<String>> {
+        let repo = &self.inner;
+
+        let upstream_oid = repo
+            .revparse_single(upstream)
+            .context(format!("Failed to resolve '{}'", upstream))?
+            .peel_to_commit()?
+            .id();
+        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID")?;
+        let base_oid = repo
+            .merge_base(head_git_oid, upstream_oid)
+            .context("Failed to find merge base")?;
+
+        let upstream_ids: HashSet<git2::Oid> = self
+            .walk_range(upstream_oid, base_oid)?
+            .into_iter()
+            .filter_map(|oid| self.commit_patch_id(oid).transpose())
+            .collect::<Result<_>>()?;
+        if upstream_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut upstreamed = Vec::new();
+        for oid in self.walk_range(head_git_oid, base_oid)? {
+            if let Some(id) = self.commit_patch_id(oid)? {
+                if upstream_ids.contains(&id) {
+                    upstreamed.push(oid.to_string());
+                }
             }
         }
+        Ok(upstreamed)
     }
 
     fn rebase_continue(&self, state: &super::ConflictState) -> Result<super::RebaseOutcome> {
+        if let Some(ctx) = &state.plan_context {
+            return self.plan_continue(state, ctx);
+        }
         let repo = &self.inner;
 
         let tip_oid =
@@ -709,69 +1141,51 @@ impl GitRepo for Git2Repo {
             // out and leaving the repo in a broken state.
             return Ok(super::RebaseOutcome::Conflict(Box::new(
                 super::ConflictState {
-                    operation_label: state.operation_label.clone(),
-                    original_branch_oid: state.original_branch_oid.clone(),
-                    new_tip_oid: state.new_tip_oid.clone(),
-                    conflicting_commit_oid: state.conflicting_commit_oid.clone(),
-                    remaining_oids: state.remaining_oids.clone(),
                     conflicting_files: collect_conflict_files(repo),
+                    conflict_kinds: collect_conflict_kinds(repo),
                     still_unresolved: true,
-                    squash_context: state.squash_context.clone(),
+                    ..state.clone()
                 },
             )));
         }
 
+        self.rerere_record();
         let new_tree_oid = index.write_tree()?;
         let new_tree = repo.find_tree(new_tree_oid)?;
 
-        let new_tip = repo.commit(
-            None,
+        let new_tip = self.create_commit(
             &conflicting_commit.author(),
-            &conflicting_commit.committer(),
             conflicting_commit.message().unwrap_or(""),
             &new_tree,
             &[&onto_commit],
         )?;
+        self.record_rewrite(conflicting_oid, new_tip);
 
-        // Continue cherry-picking remaining descendants.
-        let remaining: Vec<git2::Oid> = state
-            .remaining_oids
-            .iter()
-            .map(|s| git2::Oid::from_str(s))
-            .collect::<std::result::Result<_, _>>()
-            .context("Invalid OID in remaining list")?;
+        self.pick_remaining(state, new_tip, "continue")
+    }
 
-        let result = self.cherry_pick_chain(new_tip, &remaining)?;
-        match result {
-            CherryPickResult::Complete(final_tip) => {
-                let label = state.operation_label.to_lowercase();
-                self.advance_branch_ref(final_tip, &format!("git-tailor: {label} (continue)"))?;
-                self.checkout_head()?;
-                Ok(super::RebaseOutcome::Complete)
-            }
-            CherryPickResult::Conflict {
-                tip,
-                conflicting_idx,
-            } => {
-                let conflicting_oid = remaining[conflicting_idx];
-                let new_remaining: Vec<String> = remaining[conflicting_idx + 1..]
-                    .iter()
-                    .map(|oid| oid.to_string())
-                    .collect();
+    fn rebase_skip(&self, state: &super::ConflictState) -> Result<super::RebaseOutcome> {
+        if state.squash_context.is_some() {
+            anyhow::bail!("Cannot skip the squash itself; abort it instead");
+        }
+        let repo = &self.inner;
+        // Discard the half-applied commit, and with it any resolution the
+        // user started, like `git rebase --skip`.
+        self.rerere_clear();
+        let tip = match state.plan_context.as_ref().map(|ctx| &ctx.step) {
+            // A stopped commit is already applied; skipping drops whatever
+            // was changed on top of it.
+            Some(PlanStep::Stop | PlanStep::Exec(_)) => repo.head()?.peel_to_commit()?.id(),
+            _ => git2::Oid::from_str(&state.new_tip_oid)
+                .context("Invalid tip OID in conflict state")?,
+        };
+        let tip_commit = repo.find_commit(tip)?;
+        repo.reset(tip_commit.as_object(), git2::ResetType::Hard, None)
+            .context("failed to reset working tree")?;
 
-                Ok(super::RebaseOutcome::Conflict(Box::new(
-                    super::ConflictState {
-                        operation_label: state.operation_label.clone(),
-                        original_branch_oid: state.original_branch_oid.clone(),
-                        new_tip_oid: tip.to_string(),
-                        conflicting_commit_oid: conflicting_oid.to_string(),
-                        remaining_oids: new_remaining,
-                        conflicting_files: collect_conflict_files(repo),
-                        still_unresolved: false,
-                        squash_context: None,
-                    },
-                )))
-            }
+        match &state.plan_context {
+            Some(ctx) => self.run_plan(tip, &ctx.remaining, &state.original_branch_oid),
+            None => self.pick_remaining(state, tip, "skip"),
         }
     }
 
@@ -779,6 +1193,8 @@ impl GitRepo for Git2Repo {
         let original_oid = git2::Oid::from_str(&state.original_branch_oid)
             .context("Invalid original branch OID in conflict state")?;
         let label = state.operation_label.to_lowercase();
+        self.rewritten.borrow_mut().clear();
+        self.rerere_clear();
         self.advance_branch_ref(original_oid, &format!("git-tailor: {label} (abort)"))?;
         self.checkout_head()?;
         Ok(())
@@ -807,20 +1223,132 @@ impl GitRepo for Git2Repo {
         collect_conflict_files(&self.inner)
     }
 
-    fn squash_commits(
-        &self,
-        source_oid: &str,
-        target_oid: &str,
-        message: &str,
-        head_oid: &str,
-    ) -> Result<super::RebaseOutcome> {
-        let repo = &self.inner;
+    fn read_conflict_kinds(&self) -> BTreeMap<String, ConflictKind> {
+        collect_conflict_kinds(&self.inner)
+    }
 
-        let source_git_oid =
-            git2::Oid::from_str(source_oid).context("Invalid source OID for squash")?;
-        let target_git_oid =
-            git2::Oid::from_str(target_oid).context("Invalid target OID for squash")?;
-        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID for squash")?;
+    fn retry_conflicts(&self, strategy: MergeStrategy) -> Result<Vec<String>> {
+        let repo = &self.inner;
+        let workdir = repo
+            .workdir()
+            .context("repository has no working directory")?
+            .to_path_buf();
+        let mut index = repo.index().context("failed to read index")?;
+        index
+            .read(true)
+            .context("failed to refresh index from disk")?;
+        let kinds = collect_conflict_kinds(repo);
+
+        let flags = StrategyFlags::from(strategy);
+        let mut opts = git2::MergeFileOptions::new();
+        opts.favor(flags.favor)
+            .ignore_whitespace(flags.ignore_whitespace)
+            .ignore_whitespace_change(flags.ignore_whitespace_change);
+
+        let mut resolved = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let (Some(ours), Some(theirs)) = (conflict.our, conflict.their) else {
+                continue;
+            };
+            let path = String::from_utf8_lossy(&ours.path).into_owned();
+            if !kinds.get(&path).is_none_or(ConflictKind::is_text) {
+                continue;
+            }
+            // Add/add conflicts have no base; merge against an empty file.
+            let ancestor = match conflict.ancestor {
+                Some(ancestor) => ancestor,
+                None => git2::IndexEntry {
+                    ctime: git2::IndexTime::new(0, 0),
+                    mtime: git2::IndexTime::new(0, 0),
+                    dev: 0,
+                    ino: 0,
+                    mode: ours.mode,
+                    uid: 0,
+                    gid: 0,
+                    file_size: 0,
+                    id: repo.blob(b"")?,
+                    flags: 0,
+                    flags_extended: 0,
+                    path: ours.path.clone(),
+                },
+            };
+            let result = repo
+                .merge_file_from_index(&ancestor, &ours, &theirs, Some(&mut opts))
+                .with_context(|| format!("failed to merge '{path}'"))?;
+            if !result.is_automergeable() {
+                continue;
+            }
+            std::fs::write(workdir.join(&path), result.content())
+                .with_context(|| format!("failed to write '{path}'"))?;
+            resolved.push(path);
+        }
+
+        for path in &resolved {
+            index.conflict_remove(std::path::Path::new(path))?;
+            index.add_path(std::path::Path::new(path))?;
+        }
+        if !resolved.is_empty() {
+            index.write().context("failed to write index")?;
+        }
+        Ok(resolved)
+    }
+
+    fn take_conflict_side(&self, path: &str, side: ConflictSide) -> Result<()> {
+        let repo = &self.inner;
+        let mut index = repo.index().context("failed to read index")?;
+        index
+            .read(true)
+            .context("failed to refresh index from disk")?;
+        let conflict = index
+            .conflict_get(std::path::Path::new(path))
+            .with_context(|| format!("'{path}' has no conflict"))?;
+        let entry = match side {
+            ConflictSide::Ours => conflict.our,
+            ConflictSide::Theirs => conflict.their,
+        };
+        index.conflict_remove(std::path::Path::new(path))?;
+
+        match entry {
+            Some(mut entry) => {
+                // Stage 0; checking out the entry writes content and mode.
+                entry.flags &= !(0x3 << 12);
+                index.add(&entry)?;
+                index.write().context("failed to write index")?;
+                let mut checkout = git2::build::CheckoutBuilder::new();
+                checkout.force().path(path);
+                repo.checkout_index(Some(&mut index), Some(&mut checkout))
+                    .with_context(|| format!("failed to write '{path}'"))?;
+            }
+            None => {
+                index.write().context("failed to write index")?;
+                let workdir = repo
+                    .workdir()
+                    .context("repository has no working directory")?;
+                let file = workdir.join(path);
+                if file.symlink_metadata().is_ok() {
+                    std::fs::remove_file(&file)
+                        .with_context(|| format!("failed to remove '{path}'"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn squash_commits(
+        &self,
+        source_oid: &str,
+        target_oid: &str,
+        message: &str,
+        head_oid: &str,
+    ) -> Result<super::RebaseOutcome> {
+        let repo = &self.inner;
+
+        let source_git_oid =
+            git2::Oid::from_str(source_oid).context("Invalid source OID for squash")?;
+        let target_git_oid =
+            git2::Oid::from_str(target_oid).context("Invalid target OID for squash")?;
+        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID for squash")?;
 
         let source_commit = repo.find_commit(source_git_oid)?;
         let target_commit = repo.find_commit(target_git_oid)?;
@@ -832,7 +1360,12 @@ impl GitRepo for Git2Repo {
         let base_commit = repo.find_commit(base_oid)?;
 
         // Create the combined tree by applying source's diff onto target's tree.
-        let mut cherry_index = repo.cherrypick_commit(&source_commit, &target_commit, 0, None)?;
+        let mut cherry_index = repo.cherrypick_commit(
+            &source_commit,
+            &target_commit,
+            0,
+            Some(&self.cherrypick_options()),
+        )?;
         if cherry_index.has_conflicts() {
             let original_branch_oid = head_oid.to_string();
 
@@ -850,13 +1383,6 @@ impl GitRepo for Git2Repo {
 
             return Ok(super::RebaseOutcome::Conflict(Box::new(
                 super::ConflictState {
-                    operation_label: "Squash".to_string(),
-                    original_branch_oid,
-                    new_tip_oid: target_git_oid.to_string(),
-                    conflicting_commit_oid: source_git_oid.to_string(),
-                    remaining_oids: vec![],
-                    conflicting_files: collect_conflict_files(repo),
-                    still_unresolved: false,
                     squash_context: Some(super::SquashContext {
                         base_oid: base_oid.to_string(),
                         source_oid: source_oid.to_string(),
@@ -864,6 +1390,13 @@ impl GitRepo for Git2Repo {
                         combined_message: message.to_string(),
                         descendant_oids: descendants,
                     }),
+                    ..self.conflict_state(
+                        "Squash",
+                        &original_branch_oid,
+                        target_git_oid,
+                        &source_git_oid.to_string(),
+                        Vec::new(),
+                    )
                 },
             )));
         }
@@ -871,14 +1404,17 @@ impl GitRepo for Git2Repo {
         let combined_tree_oid = cherry_index.write_tree_to(repo)?;
         let combined_tree = repo.find_tree(combined_tree_oid)?;
 
-        let squash_oid = repo.commit(
-            None,
+        self.begin_rewrite();
+        self.run_pre_commit(&combined_tree)?;
+        let message = self.run_commit_msg(message)?;
+        let squash_oid = self.create_commit(
             &target_commit.author(),
-            &target_commit.committer(),
-            message,
+            &message,
             &combined_tree,
             &[&base_commit],
         )?;
+        self.record_rewrite(target_git_oid, squash_oid);
+        self.record_rewrite(source_git_oid, squash_oid);
 
         let original_branch_oid = head_oid.to_string();
 
@@ -892,7 +1428,7 @@ impl GitRepo for Git2Repo {
         let result = self.cherry_pick_chain(squash_oid, &descendants)?;
         match result {
             CherryPickResult::Complete(tip) => {
-                self.advance_branch_ref(tip, "git-tailor: squash commits")?;
+                self.finish_rewrite(tip, "git-tailor: squash commits")?;
                 self.checkout_head()?;
                 Ok(super::RebaseOutcome::Complete)
             }
@@ -907,16 +1443,13 @@ impl GitRepo for Git2Repo {
                     .collect();
 
                 Ok(super::RebaseOutcome::Conflict(Box::new(
-                    super::ConflictState {
-                        operation_label: "Squash".to_string(),
-                        original_branch_oid,
-                        new_tip_oid: tip.to_string(),
-                        conflicting_commit_oid: conflicting_oid.to_string(),
-                        remaining_oids: remaining,
-                        conflicting_files: collect_conflict_files(repo),
-                        still_unresolved: false,
-                        squash_context: None,
-                    },
+                    self.conflict_state(
+                        "Squash",
+                        &original_branch_oid,
+                        tip,
+                        &conflicting_oid.to_string(),
+                        remaining,
+                    ),
                 )))
             }
         }
@@ -960,10 +1493,16 @@ impl GitRepo for Git2Repo {
         }
         let base_oid = target_commit.parent_id(0)?;
 
-        let cherry_index = repo.cherrypick_commit(&source_commit, &target_commit, 0, None)?;
+        let cherry_index = repo.cherrypick_commit(
+            &source_commit,
+            &target_commit,
+            0,
+            Some(&self.cherrypick_options()),
+        )?;
         if !cherry_index.has_conflicts() {
             return Ok(None);
         }
+        self.begin_rewrite();
 
         let original_branch_oid = head_oid.to_string();
 
@@ -977,13 +1516,6 @@ impl GitRepo for Git2Repo {
         self.write_conflicts_to_workdir(&cherry_index, &target_commit)?;
 
         Ok(Some(super::ConflictState {
-            operation_label: "Squash".to_string(),
-            original_branch_oid,
-            new_tip_oid: target_git_oid.to_string(),
-            conflicting_commit_oid: source_git_oid.to_string(),
-            remaining_oids: vec![],
-            conflicting_files: collect_conflict_files(repo),
-            still_unresolved: false,
             squash_context: Some(super::SquashContext {
                 base_oid: base_oid.to_string(),
                 source_oid: source_oid.to_string(),
@@ -991,9 +1523,28 @@ impl GitRepo for Git2Repo {
                 combined_message: combined_message.to_string(),
                 descendant_oids: descendants,
             }),
+            ..self.conflict_state(
+                "Squash",
+                &original_branch_oid,
+                target_git_oid,
+                &source_git_oid.to_string(),
+                Vec::new(),
+            )
         }))
     }
 
+    fn execute_plan(
+        &self,
+        base_oid: &str,
+        steps: &[PlanStep],
+        head_oid: &str,
+    ) -> Result<super::RebaseOutcome> {
+        let base = git2::Oid::from_str(base_oid).context("Invalid base OID for plan")?;
+        git2::Oid::from_str(head_oid).context("Invalid HEAD OID for plan")?;
+        self.begin_rewrite();
+        self.run_plan(base, steps, head_oid)
+    }
+
     fn squash_finalize(
         &self,
         ctx: &super::SquashContext,
@@ -1007,6 +1558,7 @@ impl GitRepo for Git2Repo {
         if index.has_conflicts() {
             anyhow::bail!("Cannot finalize squash: index still has unresolved conflicts");
         }
+        self.rerere_record();
 
         let base_git_oid =
             git2::Oid::from_str(&ctx.base_oid).context("Invalid base OID in squash context")?;
@@ -1018,14 +1570,18 @@ impl GitRepo for Git2Repo {
         let combined_tree_oid = index.write_tree()?;
         let combined_tree = repo.find_tree(combined_tree_oid)?;
 
-        let squash_oid = repo.commit(
-            None,
+        self.run_pre_commit(&combined_tree)?;
+        let message = self.run_commit_msg(message)?;
+        let squash_oid = self.create_commit(
             &target_commit.author(),
-            &target_commit.committer(),
-            message,
+            &message,
             &combined_tree,
             &[&base_commit],
         )?;
+        self.record_rewrite(target_git_oid, squash_oid);
+        let source_git_oid =
+            git2::Oid::from_str(&ctx.source_oid).context("Invalid source OID in squash context")?;
+        self.record_rewrite(source_git_oid, squash_oid);
 
         let descendants: Vec<git2::Oid> = ctx
             .descendant_oids
@@ -1037,7 +1593,7 @@ impl GitRepo for Git2Repo {
         let result = self.cherry_pick_chain(squash_oid, &descendants)?;
         match result {
             CherryPickResult::Complete(tip) => {
-                self.advance_branch_ref(tip, "git-tailor: squash commits (finalize)")?;
+                self.finish_rewrite(tip, "git-tailor: squash commits (finalize)")?;
                 self.checkout_head()?;
                 Ok(super::RebaseOutcome::Complete)
             }
@@ -1052,16 +1608,13 @@ impl GitRepo for Git2Repo {
                     .collect();
 
                 Ok(super::RebaseOutcome::Conflict(Box::new(
-                    super::ConflictState {
-                        operation_label: "Squash".to_string(),
-                        original_branch_oid: original_branch_oid.to_string(),
-                        new_tip_oid: tip.to_string(),
-                        conflicting_commit_oid: conflicting_oid.to_string(),
-                        remaining_oids: remaining,
-                        conflicting_files: collect_conflict_files(repo),
-                        still_unresolved: false,
-                        squash_context: None,
-                    },
+                    self.conflict_state(
+                        "Squash",
+                        original_branch_oid,
+                        tip,
+                        &conflicting_oid.to_string(),
+                        remaining,
+                    ),
                 )))
             }
         }
@@ -1072,6 +1625,40 @@ impl GitRepo for Git2Repo {
 // Private helpers for drop/conflict operations
 // ---------------------------------------------------------------------------
 
+/// The libgit2 merge settings a [`MergeStrategy`] maps to, shared by tree
+/// merges during replay and per-file merges when retrying conflicts.
+struct StrategyFlags {
+    favor: git2::FileFavor,
+    ignore_whitespace: bool,
+    ignore_whitespace_change: bool,
+}
+
+impl From<MergeStrategy> for StrategyFlags {
+    fn from(strategy: MergeStrategy) -> Self {
+        let mut flags = StrategyFlags {
+            favor: git2::FileFavor::Normal,
+            ignore_whitespace: false,
+            ignore_whitespace_change: false,
+        };
+        match strategy {
+            MergeStrategy::Normal => {}
+            MergeStrategy::Ours => flags.favor = git2::FileFavor::Ours,
+            MergeStrategy::Theirs => flags.favor = git2::FileFavor::Theirs,
+            MergeStrategy::IgnoreAllSpace => flags.ignore_whitespace = true,
+            MergeStrategy::IgnoreSpaceChange => flags.ignore_whitespace_change = true,
+        }
+        flags
+    }
+}
+
+/// Summary line of the commit `oid`, or an empty string if it cannot be read.
+fn commit_summary(repo: &git2::Repository, oid: &str) -> String {
+    git2::Oid::from_str(oid)
+        .and_then(|oid| repo.find_commit(oid))
+        .map(|commit| commit.summary().unwrap_or("").to_string())
+        .unwrap_or_default()
+}
+
 /// Collect paths of all files that have conflict entries (stage > 0) in the
 /// repository's current index.  Returns them sorted for a stable display order.
 fn collect_conflict_files(repo: &git2::Repository) -> Vec<String> {
@@ -1093,6 +1680,122 @@ fn collect_conflict_files(repo: &git2::Repository) -> Vec<String> {
     paths.into_iter().collect()
 }
 
+/// Classify the conflicts in the repository's index by which stages exist,
+/// their modes and whether their content is binary.
+fn collect_conflict_kinds(repo: &git2::Repository) -> BTreeMap<String, ConflictKind> {
+    let mut kinds = BTreeMap::new();
+    let Ok(mut index) = repo.index() else {
+        return kinds;
+    };
+    let _ = index.read(true);
+    let Ok(conflicts) = index.conflicts() else {
+        return kinds;
+    };
+    let is_binary = |entry: &git2::IndexEntry| {
+        repo.find_blob(entry.id)
+            .map(|blob| blob.is_binary())
+            .unwrap_or(false)
+    };
+    let conflicts: Vec<git2::IndexConflict> = conflicts.flatten().collect();
+    // A path on one side only was renamed when some path left only in the
+    // base holds similar content; otherwise it was added or deleted.
+    let base_only: Vec<git2::Oid> = conflicts
+        .iter()
+        .filter_map(|c| match (&c.ancestor, &c.our, &c.their) {
+            (Some(base), None, None) => Some(base.id),
+            _ => None,
+        })
+        .collect();
+    let one_sided: Vec<git2::Oid> = conflicts
+        .iter()
+        .filter_map(|c| match (&c.ancestor, &c.our, &c.their) {
+            (None, Some(side), None) | (None, None, Some(side)) => Some(side.id),
+            _ => None,
+        })
+        .collect();
+    let renamed = |id: git2::Oid, candidates: &[git2::Oid]| {
+        candidates
+            .iter()
+            .any(|&other| similar_content(repo, id, other))
+    };
+    for conflict in &conflicts {
+        let (base, ours, theirs) = (&conflict.ancestor, &conflict.our, &conflict.their);
+        let Some(path) = [base, ours, theirs]
+            .into_iter()
+            .flatten()
+            .find_map(|entry| String::from_utf8(entry.path.clone()).ok())
+        else {
+            continue;
+        };
+        let kind = match (base, ours, theirs) {
+            (_, Some(o), Some(t)) if is_binary(o) || is_binary(t) => ConflictKind::Binary,
+            (_, Some(o), Some(t)) if o.mode != t.mode => ConflictKind::ModeChange {
+                ours: o.mode,
+                theirs: t.mode,
+            },
+            (Some(_), Some(_), Some(_)) => ConflictKind::Content,
+            (None, Some(_), Some(_)) => ConflictKind::AddAdd,
+            (Some(_), None, Some(_)) => ConflictKind::DeleteModify {
+                deleted_by_us: true,
+            },
+            (Some(_), Some(_), None) => ConflictKind::DeleteModify {
+                deleted_by_us: false,
+            },
+            (Some(b), None, None) if renamed(b.id, &one_sided) => ConflictKind::Rename {
+                ours: false,
+                theirs: false,
+            },
+            (None, Some(o), None) if renamed(o.id, &base_only) => ConflictKind::Rename {
+                ours: true,
+                theirs: false,
+            },
+            (None, None, Some(t)) if renamed(t.id, &base_only) => ConflictKind::Rename {
+                ours: false,
+                theirs: true,
+            },
+            (_, Some(_), None) => ConflictKind::AddedByUs,
+            (_, None, Some(_)) => ConflictKind::AddedByThem,
+            (_, None, None) => ConflictKind::BothDeleted,
+        };
+        kinds.insert(path, kind);
+    }
+    kinds
+}
+
+/// A commit `buffer` with a `gpgsig` header holding `signature` after the
+/// other headers, laid out like libgit2's `commit_signed` does it.
+fn with_gpgsig(buffer: &[u8], signature: &str) -> Vec<u8> {
+    let header_end = buffer
+        .windows(2)
+        .position(|w| w == b"\n\n")
+        .map_or(buffer.len(), |pos| pos + 1);
+    let mut signed = buffer[..header_end].to_vec();
+    signed.extend_from_slice(b"gpgsig ");
+    signed.extend_from_slice(signature.replace('\n', "\n ").as_bytes());
+    signed.push(b'\n');
+    signed.extend_from_slice(&buffer[header_end..]);
+    signed
+}
+
+/// Whether two blobs are similar enough to count as a rename, by git's
+/// default threshold of half the lines kept.
+fn similar_content(repo: &git2::Repository, a: git2::Oid, b: git2::Oid) -> bool {
+    if a == b {
+        return true;
+    }
+    let (Ok(old), Ok(new)) = (repo.find_blob(a), repo.find_blob(b)) else {
+        return false;
+    };
+    let Ok((_, _, deletions)) =
+        git2::Patch::from_blobs(&old, None, &new, None, None).and_then(|patch| patch.line_stats())
+    else {
+        return false;
+    };
+    let lines = |blob: &git2::Blob| split_lines_keep_eol(blob.content()).len();
+    let kept = lines(&old).saturating_sub(deletions);
+    kept > 0 && kept * 2 >= lines(&old).max(lines(&new))
+}
+
 // ---------------------------------------------------------------------------
 // Private helpers for split operations (not part of the GitRepo trait)
 // ---------------------------------------------------------------------------
@@ -1380,6 +2083,386 @@ fn apply_selected_hunks_to_tree(
 }
 
 impl Git2Repo {
+    /// Run plan steps starting from `tip`. Completes the rewrite, or stops
+    /// at a conflict, an edit stop or a failing command.
+    fn run_plan(
+        &self,
+        mut tip: git2::Oid,
+        steps: &[PlanStep],
+        original_branch_oid: &str,
+    ) -> Result<super::RebaseOutcome> {
+        // The last commit the plan applied, named in the stop dialog.
+        let mut current_oid = String::new();
+        for (idx, step) in steps.iter().enumerate() {
+            let failure = match step {
+                PlanStep::Pick { oid, message } | PlanStep::Fold { oid, message } => {
+                    current_oid = oid.clone();
+                    let fold = matches!(step, PlanStep::Fold { .. });
+                    match self.plan_apply(tip, oid, message.as_deref(), fold)? {
+                        Some(new_tip) => {
+                            tip = new_tip;
+                            continue;
+                        }
+                        None => None,
+                    }
+                }
+                PlanStep::Stop => {
+                    self.advance_branch_ref(tip, "git-tailor: plan (edit)")?;
+                    self.checkout_head()?;
+                    None
+                }
+                PlanStep::Exec(command) => {
+                    self.advance_branch_ref(tip, "git-tailor: plan (exec)")?;
+                    self.checkout_head()?;
+                    match self.run_exec(command) {
+                        None => continue,
+                        failure => failure,
+                    }
+                }
+            };
+
+            let remaining = steps[idx + 1..]
+                .iter()
+                .filter_map(|step| match step {
+                    PlanStep::Pick { oid, .. } | PlanStep::Fold { oid, .. } => Some(oid.clone()),
+                    PlanStep::Stop | PlanStep::Exec(_) => None,
+                })
+                .collect();
+            return Ok(super::RebaseOutcome::Conflict(Box::new(
+                super::ConflictState {
+                    plan_context: Some(PlanContext {
+                        step: step.clone(),
+                        remaining: steps[idx + 1..].to_vec(),
+                        failure,
+                    }),
+                    ..self.conflict_state("Plan", original_branch_oid, tip, &current_oid, remaining)
+                },
+            )));
+        }
+
+        self.finish_rewrite(tip, "git-tailor: plan")?;
+        self.checkout_head()?;
+        Ok(super::RebaseOutcome::Complete)
+    }
+
+    /// Apply one commit of a plan onto `tip`, as a pick or folded into the
+    /// tip. Returns the new tip, or `None` after writing a conflict to the
+    /// working tree.
+    fn plan_apply(
+        &self,
+        tip: git2::Oid,
+        oid: &str,
+        message: Option<&str>,
+        fold: bool,
+    ) -> Result<Option<git2::Oid>> {
+        let repo = &self.inner;
+        let commit = repo.find_commit(git2::Oid::from_str(oid).context("Invalid OID in plan")?)?;
+        if !fold && message.is_none() && commit.parent_ids().eq([tip]) {
+            return Ok(Some(commit.id()));
+        }
+
+        let onto = repo.find_commit(tip)?;
+        let mut index =
+            repo.cherrypick_commit(&commit, &onto, 0, Some(&self.cherrypick_options()))?;
+        if index.has_conflicts() {
+            self.write_conflicts_to_workdir(&index, &onto)?;
+            return Ok(None);
+        }
+        let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        self.plan_commit(tip, &commit, message, fold, &tree)
+            .map(Some)
+    }
+
+    /// Create the commit for a plan step with the given tree: on top of
+    /// `tip` for a pick, replacing `tip` for a fold.
+    fn plan_commit(
+        &self,
+        tip: git2::Oid,
+        commit: &git2::Commit,
+        message: Option<&str>,
+        fold: bool,
+        tree: &git2::Tree,
+    ) -> Result<git2::Oid> {
+        let onto = self.inner.find_commit(tip)?;
+        let message = match message {
+            Some(message) => self.run_commit_msg(message)?,
+            None if fold => onto.message().unwrap_or("").to_string(),
+            None => commit.message().unwrap_or("").to_string(),
+        };
+        let new_oid = if fold {
+            let parents: Vec<git2::Commit> = onto.parents().collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            self.create_commit(&onto.author(), &message, tree, &parent_refs)?
+        } else {
+            self.create_commit(&commit.author(), &message, tree, &[&onto])?
+        };
+        self.record_rewrite(commit.id(), new_oid);
+        Ok(new_oid)
+    }
+
+    /// Run an `exec` command from the top of the working tree. Returns the
+    /// first line of its output when it fails.
+    fn run_exec(&self, command: &str) -> Option<String> {
+        let workdir = self.inner.workdir().unwrap_or(self.inner.path());
+        let output = match std::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(workdir)
+            .stdin(std::process::Stdio::null())
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => return Some(format!("failed to run command: {e}")),
+        };
+        if output.status.success() {
+            return None;
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let first = stderr
+            .lines()
+            .chain(stdout.lines())
+            .find(|l| !l.trim().is_empty())
+            .map(|l| l.trim().to_string());
+        Some(first.unwrap_or_else(|| format!("command exited with {}", output.status)))
+    }
+
+    /// Resume a stopped plan: commit the resolved conflict, or take the
+    /// (possibly amended) branch tip after an edit stop or failed command,
+    /// then run the remaining steps.
+    fn plan_continue(
+        &self,
+        state: &super::ConflictState,
+        ctx: &PlanContext,
+    ) -> Result<super::RebaseOutcome> {
+        let repo = &self.inner;
+        let still_unresolved = |files: Vec<String>| {
+            Ok(super::RebaseOutcome::Conflict(Box::new(
+                super::ConflictState {
+                    conflicting_files: files,
+                    conflict_kinds: collect_conflict_kinds(repo),
+                    still_unresolved: true,
+                    ..state.clone()
+                },
+            )))
+        };
+
+        let mut index = repo.index()?;
+        index.read(true)?;
+        if index.has_conflicts() {
+            return still_unresolved(collect_conflict_files(repo));
+        }
+
+        let tip = match &ctx.step {
+            PlanStep::Pick { oid, message } | PlanStep::Fold { oid, message } => {
+                self.rerere_record();
+                let tip = git2::Oid::from_str(&state.new_tip_oid)
+                    .context("Invalid tip OID in conflict state")?;
+                let commit = repo.find_commit(git2::Oid::from_str(oid)?)?;
+                let tree = repo.find_tree(index.write_tree()?)?;
+                let fold = matches!(ctx.step, PlanStep::Fold { .. });
+                self.plan_commit(tip, &commit, message.as_deref(), fold, &tree)?
+            }
+            PlanStep::Stop | PlanStep::Exec(_) => {
+                // Unstaged changes would be lost when the plan checks out
+                // its result, so they have to be staged or discarded first.
+                if let Some(diff) = self.unstaged_diff() {
+                    let files = diff
+                        .files
+                        .iter()
+                        .filter_map(|f| f.new_path.clone().or_else(|| f.old_path.clone()))
+                        .collect();
+                    return still_unresolved(files);
+                }
+                let head = repo.head()?.peel_to_commit()?;
+                let tree = repo.find_tree(index.write_tree()?)?;
+                if tree.id() == head.tree_id() {
+                    head.id()
+                } else {
+                    // Staged changes amend the stopped commit, like
+                    // `git rebase --continue`.
+                    let parents: Vec<git2::Commit> = head.parents().collect();
+                    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+                    self.create_commit(
+                        &head.author(),
+                        head.message().unwrap_or(""),
+                        &tree,
+                        &parent_refs,
+                    )?
+                }
+            }
+        };
+        self.run_plan(tip, &ctx.remaining, &state.original_branch_oid)
+    }
+
+    /// Write a commit object without updating any ref, signing it when
+    /// `commit.gpgsign` is enabled. The author is kept; the committer is
+    /// the current user, like `git rebase`.
+    fn create_commit(
+        &self,
+        author: &git2::Signature,
+        message: &str,
+        tree: &git2::Tree,
+        parents: &[&git2::Commit],
+    ) -> Result<git2::Oid> {
+        let repo = &self.inner;
+        let committer = self.committer_for(author)?;
+        // A simulation must not ask for a passphrase.
+        let signing = if self.dry_run {
+            None
+        } else {
+            SigningConfig::from_repo(self)?
+        };
+        let Some(signing) = signing else {
+            return Ok(repo.commit(None, author, &committer, message, tree, parents)?);
+        };
+
+        let buffer = repo.commit_create_buffer(author, &committer, message, tree, parents)?;
+        // The committer is the current user, which is also whose key signs.
+        let ident = format!(
+            "{} <{}>",
+            String::from_utf8_lossy(committer.name_bytes()),
+            String::from_utf8_lossy(committer.email_bytes())
+        );
+        let signature = signing.sign(&buffer, &ident)?;
+        match buffer.as_str() {
+            Some(content) => Ok(repo.commit_signed(content, &signature, None)?),
+            // `commit_signed` takes text only; legacy encodings are written
+            // with the signature header added by hand instead.
+            None => Ok(repo
+                .odb()?
+                .write(git2::ObjectType::Commit, &with_gpgsig(&buffer, &signature))?),
+        }
+    }
+
+    /// Recreate `commit_oid` with a new message and optionally a new author,
+    /// keeping tree and parents, then rebuild its descendants up to
+    /// `head_oid`.
+    fn rewrite_commit(
+        &self,
+        commit_oid: &str,
+        author: Option<&git2::Signature>,
+        new_message: &str,
+        head_oid: &str,
+    ) -> Result<()> {
+        let repo = &self.inner;
+
+        let commit_git_oid =
+            git2::Oid::from_str(commit_oid).context("Invalid commit OID for reword")?;
+        let head_git_oid = git2::Oid::from_str(head_oid).context("Invalid HEAD OID for reword")?;
+        let commit = repo.find_commit(commit_git_oid)?;
+
+        let parents: Vec<git2::Commit> = (0..commit.parent_count())
+            .map(|i| commit.parent(i))
+            .collect::<std::result::Result<_, _>>()?;
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+
+        self.begin_rewrite();
+        let new_message = self.run_commit_msg(new_message)?;
+        let new_oid = self.create_commit(
+            author.unwrap_or(&commit.author()),
+            &new_message,
+            &commit.tree()?,
+            &parent_refs,
+        )?;
+        self.record_rewrite(commit_git_oid, new_oid);
+
+        let tip = self.rebase_descendants(commit_git_oid, head_git_oid, new_oid)?;
+        self.finish_rewrite(tip, "reword: update branch ref")?;
+        Ok(())
+    }
+
+    /// Committer signature for a rewritten commit: `user.name`/`user.email`
+    /// at the current time, or at the author time with
+    /// `--committer-date-is-author-date`.
+    fn committer_for(&self, author: &git2::Signature) -> Result<git2::Signature<'static>> {
+        let user = self
+            .inner
+            .signature()
+            .context("Committer identity unknown: set user.name and user.email")?;
+        if !self.committer_date_is_author_date {
+            return Ok(user.to_owned());
+        }
+        Ok(git2::Signature::new(
+            user.name().unwrap_or(""),
+            user.email().unwrap_or(""),
+            &author.when(),
+        )?)
+    }
+
+    /// Short name of the current branch's upstream (e.g. `origin/main`), or
+    /// `None` when HEAD is detached or the branch has no upstream configured.
+    fn current_upstream_name(&self) -> Option<String> {
+        let head = self.inner.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        let branch = git2::Branch::wrap(head);
+        let upstream = branch.upstream().ok()?;
+        upstream.name().ok()?.map(str::to_string)
+    }
+
+    /// Turn the result of cherry-picking `descendants` after a drop into a
+    /// `RebaseOutcome`, updating the branch ref on success.
+    fn finish_drop(
+        &self,
+        result: CherryPickResult,
+        descendants: &[git2::Oid],
+        original_branch_oid: String,
+        log_action: &str,
+    ) -> Result<super::RebaseOutcome> {
+        match result {
+            CherryPickResult::Complete(tip) => {
+                self.finish_rewrite(tip, &format!("git-tailor: {log_action}"))?;
+                self.checkout_head()?;
+                Ok(super::RebaseOutcome::Complete)
+            }
+            CherryPickResult::Conflict {
+                tip,
+                conflicting_idx,
+            } => {
+                let conflicting_oid = descendants[conflicting_idx];
+                let remaining: Vec<String> = descendants[conflicting_idx + 1..]
+                    .iter()
+                    .map(|oid| oid.to_string())
+                    .collect();
+
+                Ok(super::RebaseOutcome::Conflict(Box::new(
+                    self.conflict_state(
+                        "Drop",
+                        &original_branch_oid,
+                        tip,
+                        &conflicting_oid.to_string(),
+                        remaining,
+                    ),
+                )))
+            }
+        }
+    }
+
+    /// Commits reachable from `from` but not from `hide`, newest first.
+    fn walk_range(&self, from: git2::Oid, hide: git2::Oid) -> Result<Vec<git2::Oid>> {
+        let mut revwalk = self.inner.revwalk()?;
+        revwalk.push(from)?;
+        revwalk.hide(hide)?;
+        revwalk
+            .collect::<std::result::Result<_, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Stable patch-id of a commit's diff against its parent, as computed
+    /// by `git patch-id`. Returns `None` for merge and root commits.
+    fn commit_patch_id(&self, oid: git2::Oid) -> Result<Option<git2::Oid>> {
+        let repo = &self.inner;
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() != 1 {
+            return Ok(None);
+        }
+        let parent_tree = commit.parent(0)?.tree()?;
+        let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit.tree()?), None)?;
+        Ok(Some(diff.patchid(None)?))
+    }
+
     /// Refuse if any staged or unstaged change touches a file in `commit_paths`.
     fn check_dirty_overlap(&self, commit_paths: &HashSet<String>) -> Result<()> {
         let mut overlapping: Vec<String> = Vec::new();
@@ -1438,7 +2521,12 @@ impl Git2Repo {
             let desc_commit = repo.find_commit(desc_oid)?;
             let onto_commit = repo.find_commit(tip)?;
 
-            let mut cherry_index = repo.cherrypick_commit(&desc_commit, &onto_commit, 0, None)?;
+            let mut cherry_index = repo.cherrypick_commit(
+                &desc_commit,
+                &onto_commit,
+                0,
+                Some(&self.cherrypick_options()),
+            )?;
             if cherry_index.has_conflicts() {
                 anyhow::bail!(
                     "Conflict rebasing {} onto split result",
@@ -1449,22 +2537,122 @@ impl Git2Repo {
             let new_tree = repo.find_tree(new_tree_oid)?;
 
             let author = desc_commit.author();
-            let committer = desc_commit.committer();
-            tip = repo.commit(
-                None,
+            tip = self.create_commit(
                 &author,
-                &committer,
                 desc_commit.message().unwrap_or(""),
                 &new_tree,
                 &[&onto_commit],
             )?;
+            self.record_rewrite(desc_oid, tip);
         }
 
         Ok(tip)
     }
 
+    /// Hooks of this repository, run from the top of the working tree.
+    fn hooks(&self) -> Hooks {
+        let git_dir = self.inner.path();
+        let workdir = self.inner.workdir().unwrap_or(git_dir);
+        Hooks::new(
+            git_dir,
+            workdir,
+            self.get_config_string("core.hooksPath").as_deref(),
+        )
+    }
+
+    /// Run the `pre-commit` hook on `tree` unless verification is disabled.
+    /// gt builds commits from trees rather than the index, so the hook gets
+    /// a temporary index holding `tree`. A hook that changes that index
+    /// fails the rewrite, since its changes would otherwise be lost.
+    fn run_pre_commit(&self, tree: &git2::Tree) -> Result<()> {
+        let hooks = self.hooks();
+        if !self.verify || hooks.find("pre-commit").is_none() {
+            return Ok(());
+        }
+        let dir = tempfile::tempdir().context("failed to create index for pre-commit hook")?;
+        let index_path = dir.path().join("index");
+        let mut index = git2::Index::open(&index_path)?;
+        index.read_tree(tree)?;
+        index.write()?;
+        hooks.run_on_index("pre-commit", &index_path)?;
+        index.read(true)?;
+        if index.write_tree_to(&self.inner)? != tree.id() {
+            anyhow::bail!("pre-commit hook changed the commit; apply its changes and retry");
+        }
+        Ok(())
+    }
+
+    /// Pass `message` through the `commit-msg` hook unless verification is
+    /// disabled.
+    fn run_commit_msg(&self, message: &str) -> Result<String> {
+        if !self.verify {
+            return Ok(message.to_string());
+        }
+        self.hooks().run_message_hook("commit-msg", message, &[])
+    }
+
+    /// Start collecting the old → new map for a new rewrite operation.
+    fn begin_rewrite(&self) {
+        self.rewritten.borrow_mut().clear();
+    }
+
+    /// Remember that `old` was rewritten as `new`.
+    fn record_rewrite(&self, old: git2::Oid, new: git2::Oid) {
+        self.rewritten.borrow_mut().push((old, new));
+    }
+
+    /// Point the branch at the final tip of a completed rewrite and run the
+    /// `post-rewrite` hook with everything rewritten along the way.
+    fn finish_rewrite(&self, new_tip: git2::Oid, log_msg: &str) -> Result<()> {
+        self.advance_branch_ref(new_tip, log_msg)?;
+        if self.dry_run {
+            return Ok(());
+        }
+        let rewritten = std::mem::take(&mut *self.rewritten.borrow_mut());
+        if !rewritten.is_empty() {
+            let input: String = rewritten
+                .iter()
+                .map(|(old, new)| format!("{old} {new}\n"))
+                .collect();
+            // Like git, ignore the exit status: the rewrite already happened.
+            let _ = self.hooks().run("post-rewrite", &["rebase"], Some(&input));
+        }
+        Ok(())
+    }
+
+    /// The state of a stop at `conflicting_oid` with `tip` built so far,
+    /// with what is read from the repository filled in: the commit's
+    /// summary, the conflicting files and their kinds, and the files
+    /// rerere resolved.
+    fn conflict_state(
+        &self,
+        operation_label: &str,
+        original_branch_oid: &str,
+        tip: git2::Oid,
+        conflicting_oid: &str,
+        remaining_oids: Vec<String>,
+    ) -> super::ConflictState {
+        let repo = &self.inner;
+        super::ConflictState {
+            operation_label: operation_label.to_string(),
+            original_branch_oid: original_branch_oid.to_string(),
+            new_tip_oid: tip.to_string(),
+            conflicting_commit_oid: conflicting_oid.to_string(),
+            conflicting_commit_summary: commit_summary(repo, conflicting_oid),
+            remaining_oids,
+            conflicting_files: collect_conflict_files(repo),
+            conflict_kinds: collect_conflict_kinds(repo),
+            reused_resolutions: self.rerere_resolved.take(),
+            ..Default::default()
+        }
+    }
+
     /// Fast-forward the branch ref that HEAD currently points to.
     fn advance_branch_ref(&self, new_tip: git2::Oid, log_msg: &str) -> Result<()> {
+        if self.dry_run {
+            *self.simulated_tip.borrow_mut() = Some(new_tip);
+            return Ok(());
+        }
         let repo = &self.inner;
         let head_ref = repo.head()?;
         let branch_refname = head_ref
@@ -1504,6 +2692,53 @@ impl Git2Repo {
         Ok(descendants)
     }
 
+    /// Cherry-pick the remaining commits of a conflicted rebase onto `tip`,
+    /// finishing the rewrite or stopping at the next conflict. `action`
+    /// names how the rebase went on in the reflog.
+    fn pick_remaining(
+        &self,
+        state: &super::ConflictState,
+        tip: git2::Oid,
+        action: &str,
+    ) -> Result<super::RebaseOutcome> {
+        let remaining: Vec<git2::Oid> = state
+            .remaining_oids
+            .iter()
+            .map(|s| git2::Oid::from_str(s))
+            .collect::<std::result::Result<_, _>>()
+            .context("Invalid OID in remaining list")?;
+
+        let result = self.cherry_pick_chain(tip, &remaining)?;
+        match result {
+            CherryPickResult::Complete(final_tip) => {
+                let label = state.operation_label.to_lowercase();
+                self.finish_rewrite(final_tip, &format!("git-tailor: {label} ({action})"))?;
+                self.checkout_head()?;
+                Ok(super::RebaseOutcome::Complete)
+            }
+            CherryPickResult::Conflict {
+                tip,
+                conflicting_idx,
+            } => {
+                let conflicting_oid = remaining[conflicting_idx];
+                let new_remaining: Vec<String> = remaining[conflicting_idx + 1..]
+                    .iter()
+                    .map(|oid| oid.to_string())
+                    .collect();
+
+                Ok(super::RebaseOutcome::Conflict(Box::new(
+                    self.conflict_state(
+                        &state.operation_label,
+                        &state.original_branch_oid,
+                        tip,
+                        &conflicting_oid.to_string(),
+                        new_remaining,
+                    ),
+                )))
+            }
+        }
+    }
+
     /// Cherry-pick a sequence of commits onto `tip`, returning the final tip
     /// or the point at which a conflict was detected.
     ///
@@ -1521,7 +2756,12 @@ impl Git2Repo {
             let desc_commit = repo.find_commit(desc_oid)?;
             let onto_commit = repo.find_commit(tip)?;
 
-            let mut cherry_index = repo.cherrypick_commit(&desc_commit, &onto_commit, 0, None)?;
+            let mut cherry_index = repo.cherrypick_commit(
+                &desc_commit,
+                &onto_commit,
+                0,
+                Some(&self.cherrypick_options()),
+            )?;
             if cherry_index.has_conflicts() {
                 self.write_conflicts_to_workdir(&cherry_index, &onto_commit)?;
                 return Ok(CherryPickResult::Conflict {
@@ -1533,14 +2773,13 @@ impl Git2Repo {
             let new_tree_oid = cherry_index.write_tree_to(repo)?;
             let new_tree = repo.find_tree(new_tree_oid)?;
 
-            tip = repo.commit(
-                None,
+            tip = self.create_commit(
                 &desc_commit.author(),
-                &desc_commit.committer(),
                 desc_commit.message().unwrap_or(""),
                 &new_tree,
                 &[&onto_commit],
             )?;
+            self.record_rewrite(desc_oid, tip);
         }
 
         Ok(CherryPickResult::Complete(tip))
@@ -1559,6 +2798,15 @@ impl Git2Repo {
         // rebased chain.
         self.advance_branch_ref(onto_commit.id(), "git-tailor: drop commit (conflict)")?;
 
+        if self.dry_run {
+            let mut index = git2::Index::new()?;
+            for entry in cherry_index.iter() {
+                index.add(&entry)?;
+            }
+            repo.set_index(&mut index)?;
+            return Ok(());
+        }
+
         // Write the conflicted index entries (including conflict markers) into
         // the repo's index so `git status` and the user's editor see them.
         let mut repo_index = repo.index()?;
@@ -1576,11 +2824,128 @@ impl Git2Repo {
         checkout.allow_conflicts(true);
         repo.checkout_index(Some(&mut repo_index), Some(&mut checkout))?;
 
+        *self.rerere_resolved.borrow_mut() = self.rerere_conflicts();
         Ok(())
     }
 
+    /// Whether conflicts are recorded and reused: `rerere.enabled`, or when
+    /// unset, whether `rr-cache` exists, as in git.
+    fn rerere_enabled(&self) -> bool {
+        match self
+            .inner
+            .config()
+            .and_then(|c| c.get_bool("rerere.enabled"))
+        {
+            Ok(enabled) => enabled,
+            Err(_) => self.inner.path().join("rr-cache").is_dir(),
+        }
+    }
+
+    /// Record the preimages of the conflicts in the working tree and resolve
+    /// those seen before with their recorded resolution. Returns the files
+    /// resolved. These are staged unless `rerere.autoUpdate` is false, since
+    /// the conflict dialog has no staging step of its own.
+    ///
+    /// Like in git this is best-effort: on failure the conflicts are left
+    /// for the user.
+    fn rerere_conflicts(&self) -> Vec<String> {
+        if !self.rerere_enabled() {
+            return Vec::new();
+        }
+        self.try_rerere_conflicts().unwrap_or_default()
+    }
+
+    fn try_rerere_conflicts(&self) -> Result<Vec<String>> {
+        let repo = &self.inner;
+        let workdir = repo.workdir().context("repository has no working tree")?;
+        let auto_update = !matches!(repo.config()?.get_bool("rerere.autoUpdate"), Ok(false));
+        let mut pending = Vec::new();
+        let mut resolved = Vec::new();
+        for path in collect_conflict_files(repo) {
+            let Ok(text) = std::fs::read_to_string(workdir.join(&path)) else {
+                continue;
+            };
+            let Some(preimage) = rerere::normalize(&text) else {
+                continue;
+            };
+            let dir = repo.path().join("rr-cache").join(&preimage.id);
+            let recorded = std::fs::read_to_string(dir.join("preimage"))
+                .and_then(|before| Ok((before, std::fs::read_to_string(dir.join("postimage"))?)));
+            match recorded {
+                Ok((before, after)) => {
+                    if let Some(merged) = rerere::replay(&before, &preimage.text, &after) {
+                        std::fs::write(workdir.join(&path), merged)?;
+                        if auto_update {
+                            self.stage_file(&path)?;
+                        }
+                        resolved.push(path.clone());
+                    }
+                }
+                Err(_) => {
+                    std::fs::create_dir_all(&dir)?;
+                    std::fs::write(dir.join("preimage"), &preimage.text)?;
+                }
+            }
+            pending.push((preimage.id, path));
+        }
+        if !pending.is_empty() {
+            std::fs::write(
+                repo.path().join("MERGE_RR"),
+                rerere::format_merge_rr(&pending),
+            )?;
+        }
+        Ok(resolved)
+    }
+
+    /// Record the resolved files of the conflicts listed in `MERGE_RR` as
+    /// their postimages, for reuse the next time the conflicts occur.
+    fn rerere_record(&self) {
+        let _ = self.try_rerere_record();
+    }
+
+    fn try_rerere_record(&self) -> Result<()> {
+        let repo = &self.inner;
+        let merge_rr = repo.path().join("MERGE_RR");
+        let Ok(data) = std::fs::read(&merge_rr) else {
+            return Ok(());
+        };
+        std::fs::remove_file(&merge_rr)?;
+        let workdir = repo.workdir().context("repository has no working tree")?;
+        for (id, path) in rerere::parse_merge_rr(&data) {
+            let Ok(text) = std::fs::read_to_string(workdir.join(&path)) else {
+                continue;
+            };
+            let dir = repo.path().join("rr-cache").join(&id);
+            // Markers left in the file mean it was not resolved.
+            if rerere::normalize(&text).is_none() && dir.join("preimage").is_file() {
+                std::fs::write(dir.join("postimage"), text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget the pending conflicts and their unresolved preimages, like
+    /// `git rerere clear`.
+    fn rerere_clear(&self) {
+        let repo = &self.inner;
+        let merge_rr = repo.path().join("MERGE_RR");
+        let Ok(data) = std::fs::read(&merge_rr) else {
+            return;
+        };
+        for (id, _) in rerere::parse_merge_rr(&data) {
+            let dir = repo.path().join("rr-cache").join(&id);
+            if !dir.join("postimage").exists() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+        let _ = std::fs::remove_file(merge_rr);
+    }
+
     /// Reset the working tree and index to match HEAD.
     fn checkout_head(&self) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
         let mut checkout = git2::build::CheckoutBuilder::new();
         checkout.force();
         self.inner.checkout_head(Some(&mut checkout))?;
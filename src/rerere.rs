@@ -0,0 +1,179 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Reuse recorded conflict resolutions, like `git rerere`.
+//
+// A conflicted file is normalized the way git does it: the labels after
+// the markers and any base section are dropped, and the two sides of each
+// conflict are put in a stable order. The SHA-1 over the sides identifies
+// the conflict, and `.git/rr-cache/<id>/` holds the normalized `preimage`
+// and the resolved `postimage`. Resolutions are thereby shared with git.
+
+use crate::merge3::{self, is_marker, MARKER_SIZE};
+use sha1_smol::Sha1;
+
+/// A conflicted file in normalized form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preimage {
+    /// Hex SHA-1 identifying the conflicts, the `rr-cache` directory name.
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Outside,
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Normalize a file with conflict markers. Returns `None` when the file
+/// has no conflicts or its markers do not nest properly.
+pub fn normalize(text: &str) -> Option<Preimage> {
+    let mut normalized = String::new();
+    let mut hasher = Sha1::new();
+    let (mut one, mut two) = (String::new(), String::new());
+    let mut section = Section::Outside;
+    let mut has_conflicts = false;
+
+    for line in text.split_inclusive('\n') {
+        section = match section {
+            Section::Outside if is_marker(line, '<') => Section::Ours,
+            Section::Outside => {
+                normalized.push_str(line);
+                Section::Outside
+            }
+            Section::Ours if is_marker(line, '|') => Section::Base,
+            Section::Ours | Section::Base if is_marker(line, '=') => Section::Theirs,
+            Section::Theirs if is_marker(line, '>') => {
+                if one > two {
+                    std::mem::swap(&mut one, &mut two);
+                }
+                let marker = |c: char| format!("{}\n", c.to_string().repeat(MARKER_SIZE));
+                normalized.push_str(&marker('<'));
+                normalized.push_str(&one);
+                normalized.push_str(&marker('='));
+                normalized.push_str(&two);
+                normalized.push_str(&marker('>'));
+                for side in [&one, &two] {
+                    hasher.update(side.as_bytes());
+                    hasher.update(&[0]);
+                }
+                one.clear();
+                two.clear();
+                has_conflicts = true;
+                Section::Outside
+            }
+            _ if ['<', '|', '=', '>'].iter().any(|&m| is_marker(line, m)) => return None,
+            Section::Ours => {
+                one.push_str(line);
+                Section::Ours
+            }
+            Section::Base => Section::Base,
+            Section::Theirs => {
+                two.push_str(line);
+                Section::Theirs
+            }
+        };
+    }
+
+    if section != Section::Outside || !has_conflicts {
+        return None;
+    }
+    Some(Preimage {
+        id: hasher.digest().to_string(),
+        text: normalized,
+    })
+}
+
+/// Apply a recorded resolution to the current, normalized conflict:
+/// merge the change from `preimage` to `postimage` into `current`.
+/// Returns `None` when they do not merge cleanly.
+pub fn replay(preimage: &str, current: &str, postimage: &str) -> Option<String> {
+    merge3::merged_text(&merge3::merge(preimage, current, postimage))
+}
+
+/// Parse `.git/MERGE_RR`: NUL-terminated `<id>\t<path>` entries for the
+/// conflicts awaiting a resolution.
+pub fn parse_merge_rr(data: &[u8]) -> Vec<(String, String)> {
+    data.split(|&b| b == 0)
+        .filter_map(|entry| {
+            let entry = std::str::from_utf8(entry).ok()?;
+            let (id, path) = entry.split_once('\t')?;
+            Some((id.to_string(), path.to_string()))
+        })
+        .collect()
+}
+
+/// Format entries for `.git/MERGE_RR`.
+pub fn format_merge_rr(entries: &[(String, String)]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for (id, path) in entries {
+        data.extend_from_slice(format!("{id}\t{path}\0").as_bytes());
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_drops_labels_and_base_and_orders_sides() {
+        let text = "a\n<<<<<<< ours\nzed\n||||||| base\nold\n=======\nalpha\n>>>>>>> theirs\nb\n";
+        let preimage = normalize(text).unwrap();
+        assert_eq!(
+            preimage.text,
+            "a\n<<<<<<<\nalpha\n=======\nzed\n>>>>>>>\nb\n"
+        );
+        assert_eq!(
+            preimage.id,
+            Sha1::from(b"alpha\n\0zed\n\0").digest().to_string()
+        );
+
+        // The same conflict with the sides swapped has the same ID.
+        let swapped = "a\n<<<<<<< HEAD\nalpha\n=======\nzed\n>>>>>>> other\nb\n";
+        assert_eq!(normalize(swapped).unwrap(), preimage);
+    }
+
+    #[test]
+    fn normalize_rejects_clean_and_broken_files() {
+        assert_eq!(normalize("a\nb\n"), None);
+        assert_eq!(normalize("<<<<<<< ours\na\n=======\n"), None);
+        assert_eq!(normalize("=======\n"), None);
+    }
+
+    #[test]
+    fn replay_applies_resolution_to_shifted_file() {
+        let preimage = "a\n<<<<<<<\nx\n=======\ny\n>>>>>>>\nb\n";
+        let postimage = "a\nxy\nb\n";
+        let current = "top\na\n<<<<<<<\nx\n=======\ny\n>>>>>>>\nb\n";
+        assert_eq!(
+            replay(preimage, current, postimage).as_deref(),
+            Some("top\na\nxy\nb\n")
+        );
+    }
+
+    #[test]
+    fn merge_rr_round_trips() {
+        let entries = vec![
+            ("0123abcd".to_string(), "a.txt".to_string()),
+            ("4567ef01".to_string(), "dir/b.txt".to_string()),
+        ];
+        let data = format_merge_rr(&entries);
+        assert_eq!(data, b"0123abcd\ta.txt\x004567ef01\tdir/b.txt\x00");
+        assert_eq!(parse_merge_rr(&data), entries);
+    }
+}
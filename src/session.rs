@@ -0,0 +1,400 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Edit sessions: queue several operations on a virtual stack, apply once.
+//
+// A session starts from the commits of the stack and records reorders,
+// squashes, fixups, drops and rewords against a copy of it. Nothing touches
+// the repository until the session is compiled into plan steps for
+// `GitRepo::execute_plan`, which rewrites the branch in a single rebase.
+
+use crate::repo::PlanStep;
+use crate::CommitInfo;
+use anyhow::{bail, Result};
+
+/// An operation queued in an edit session. Commits are named by their OID
+/// in the original stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionOp {
+    /// Swap the commit with its newer (`newer`) or older neighbour.
+    Move {
+        oid: String,
+        newer: bool,
+    },
+    /// Meld `source` into the older `target`, with `message` for the
+    /// combined commit.
+    Squash {
+        source: String,
+        target: String,
+        message: String,
+    },
+    /// Meld `source` into the older `target`, keeping the target's message.
+    Fixup {
+        source: String,
+        target: String,
+    },
+    Drop {
+        oid: String,
+    },
+    Reword {
+        oid: String,
+        message: String,
+    },
+}
+
+/// One commit of the virtual stack: an original commit and the commits
+/// melded into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    oid: String,
+    /// Message of the resulting commit when it differs from `oid`'s.
+    message: Option<String>,
+    /// Commits melded into this one, in the order they are applied.
+    folded: Vec<String>,
+    /// Whether a squash, rather than only fixups, melded commits in.
+    squashed: bool,
+}
+
+impl Entry {
+    fn new(oid: &str) -> Self {
+        Entry {
+            oid: oid.to_string(),
+            message: None,
+            folded: Vec::new(),
+            squashed: false,
+        }
+    }
+}
+
+/// Operations queued against a virtual copy of the stack.
+#[derive(Debug, Clone)]
+pub struct EditSession {
+    /// The stack the session started from, oldest first.
+    original: Vec<CommitInfo>,
+    entries: Vec<Entry>,
+    ops: Vec<SessionOp>,
+}
+
+impl EditSession {
+    /// Start a session on `commits`, oldest first.
+    pub fn new(commits: Vec<CommitInfo>) -> Self {
+        let entries = commits.iter().map(|c| Entry::new(&c.oid)).collect();
+        EditSession {
+            original: commits,
+            entries,
+            ops: Vec::new(),
+        }
+    }
+
+    /// The stack the session started from, oldest first.
+    pub fn original(&self) -> &[CommitInfo] {
+        &self.original
+    }
+
+    /// The queued operations, in order.
+    pub fn ops(&self) -> &[SessionOp] {
+        &self.ops
+    }
+
+    /// Queue `op`, updating the virtual stack. Fails without changing
+    /// anything when the operation does not fit the stack as it is now.
+    pub fn apply(&mut self, op: SessionOp) -> Result<()> {
+        self.apply_to_entries(&op)?;
+        self.ops.push(op);
+        Ok(())
+    }
+
+    /// Take back the last queued operation and return it.
+    pub fn undo(&mut self) -> Option<SessionOp> {
+        let last = self.ops.pop()?;
+        self.entries = self.original.iter().map(|c| Entry::new(&c.oid)).collect();
+        for op in std::mem::take(&mut self.ops) {
+            self.apply(op)
+                .expect("replaying operations that applied before");
+        }
+        Some(last)
+    }
+
+    fn position(&self, oid: &str) -> Result<usize> {
+        match self.entries.iter().position(|e| e.oid == oid) {
+            Some(index) => Ok(index),
+            None => bail!("{} is not in the stack", short(oid)),
+        }
+    }
+
+    fn apply_to_entries(&mut self, op: &SessionOp) -> Result<()> {
+        match op {
+            SessionOp::Move { oid, newer } => {
+                let from = self.position(oid)?;
+                let to = if *newer {
+                    from + 1
+                } else {
+                    from.wrapping_sub(1)
+                };
+                if to >= self.entries.len() {
+                    bail!("{} cannot move further", short(oid));
+                }
+                self.entries.swap(from, to);
+            }
+            SessionOp::Squash { source, target, .. } | SessionOp::Fixup { source, target } => {
+                let source_index = self.position(source)?;
+                let target_index = self.position(target)?;
+                if target_index >= source_index {
+                    bail!("Can only squash into an older commit");
+                }
+                let melded = self.entries.remove(source_index);
+                let entry = &mut self.entries[target_index];
+                entry.folded.push(melded.oid);
+                entry.folded.extend(melded.folded);
+                if let SessionOp::Squash { message, .. } = op {
+                    entry.message = Some(message.clone());
+                    entry.squashed = true;
+                }
+            }
+            SessionOp::Drop { oid } => {
+                let index = self.position(oid)?;
+                self.entries.remove(index);
+            }
+            SessionOp::Reword { oid, message } => {
+                let index = self.position(oid)?;
+                self.entries[index].message = Some(message.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// The virtual stack, oldest first. Each commit keeps its original OID;
+    /// changed messages show in `message` and `summary`.
+    pub fn commits(&self) -> Vec<CommitInfo> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let mut commit = self.original.iter().find(|c| c.oid == entry.oid)?.clone();
+                if let Some(message) = &entry.message {
+                    commit.summary = message.lines().next().unwrap_or("").to_string();
+                    commit.message = message.clone();
+                }
+                Some(commit)
+            })
+            .collect()
+    }
+
+    /// What the session does to the commit at `oid` itself, for display:
+    /// `squash`, `fixup` or `reword`.
+    pub fn change(&self, oid: &str) -> Option<&'static str> {
+        let entry = self.entries.iter().find(|e| e.oid == oid)?;
+        if entry.squashed {
+            Some("squash")
+        } else if !entry.folded.is_empty() {
+            Some("fixup")
+        } else if entry.message.is_some() {
+            Some("reword")
+        } else {
+            None
+        }
+    }
+
+    /// The oldest commit of the original stack that the session rewrites,
+    /// or `None` when applying it would change nothing.
+    pub fn first_rewritten(&self) -> Option<&str> {
+        let kept = self
+            .entries
+            .iter()
+            .zip(&self.original)
+            .take_while(|(entry, commit)| **entry == Entry::new(&commit.oid))
+            .count();
+        self.original.get(kept).map(|c| c.oid.as_str())
+    }
+
+    /// Compile the virtual stack into plan steps for
+    /// [`GitRepo::execute_plan`](crate::repo::GitRepo::execute_plan) from
+    /// the parent of the oldest original commit.
+    pub fn plan(&self) -> Vec<PlanStep> {
+        let mut steps = Vec::new();
+        for entry in &self.entries {
+            let Some((last, folded)) = entry.folded.split_last() else {
+                steps.push(PlanStep::Pick {
+                    oid: entry.oid.clone(),
+                    message: entry.message.clone(),
+                });
+                continue;
+            };
+            steps.push(PlanStep::Pick {
+                oid: entry.oid.clone(),
+                message: None,
+            });
+            for oid in folded {
+                steps.push(PlanStep::Fold {
+                    oid: oid.clone(),
+                    message: None,
+                });
+            }
+            steps.push(PlanStep::Fold {
+                oid: last.clone(),
+                message: entry.message.clone(),
+            });
+        }
+        steps
+    }
+}
+
+fn short(oid: &str) -> String {
+    oid.chars().take(10).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> EditSession {
+        EditSession::new(vec![
+            CommitInfo::with_message("aaaa", "Add a\n"),
+            CommitInfo::with_message("bbbb", "Add b\n"),
+            CommitInfo::with_message("cccc", "Fix a\n"),
+            CommitInfo::with_message("dddd", "Add d\n"),
+        ])
+    }
+
+    fn pick(oid: &str) -> PlanStep {
+        PlanStep::Pick {
+            oid: oid.to_string(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn queued_operations_compile_to_one_plan() {
+        let mut session = session();
+        session
+            .apply(SessionOp::Move {
+                oid: "dddd".to_string(),
+                newer: false,
+            })
+            .unwrap();
+        session
+            .apply(SessionOp::Fixup {
+                source: "cccc".to_string(),
+                target: "aaaa".to_string(),
+            })
+            .unwrap();
+        session
+            .apply(SessionOp::Drop {
+                oid: "bbbb".to_string(),
+            })
+            .unwrap();
+        session
+            .apply(SessionOp::Reword {
+                oid: "dddd".to_string(),
+                message: "Add d file\n".to_string(),
+            })
+            .unwrap();
+
+        let summaries: Vec<String> = session.commits().into_iter().map(|c| c.summary).collect();
+        assert_eq!(summaries, ["Add a", "Add d file"]);
+        assert_eq!(session.change("aaaa"), Some("fixup"));
+        assert_eq!(session.change("dddd"), Some("reword"));
+        assert_eq!(session.first_rewritten(), Some("aaaa"));
+        assert_eq!(
+            session.plan(),
+            vec![
+                pick("aaaa"),
+                PlanStep::Fold {
+                    oid: "cccc".to_string(),
+                    message: None,
+                },
+                PlanStep::Pick {
+                    oid: "dddd".to_string(),
+                    message: Some("Add d file\n".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn squash_puts_message_on_last_fold_and_carries_melded_commits() {
+        let mut session = session();
+        session
+            .apply(SessionOp::Fixup {
+                source: "dddd".to_string(),
+                target: "cccc".to_string(),
+            })
+            .unwrap();
+        session
+            .apply(SessionOp::Squash {
+                source: "cccc".to_string(),
+                target: "aaaa".to_string(),
+                message: "Add a and d\n".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(session.change("aaaa"), Some("squash"));
+        assert_eq!(session.first_rewritten(), Some("aaaa"));
+        assert_eq!(
+            session.plan(),
+            vec![
+                pick("aaaa"),
+                PlanStep::Fold {
+                    oid: "cccc".to_string(),
+                    message: None,
+                },
+                PlanStep::Fold {
+                    oid: "dddd".to_string(),
+                    message: Some("Add a and d\n".to_string()),
+                },
+                pick("bbbb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_operations_are_rejected_and_undo_replays() {
+        let mut session = session();
+        assert!(session
+            .apply(SessionOp::Squash {
+                source: "aaaa".to_string(),
+                target: "bbbb".to_string(),
+                message: String::new(),
+            })
+            .is_err());
+        assert!(session
+            .apply(SessionOp::Move {
+                oid: "aaaa".to_string(),
+                newer: false,
+            })
+            .is_err());
+        assert!(session.ops().is_empty());
+        assert_eq!(session.first_rewritten(), None);
+
+        session
+            .apply(SessionOp::Drop {
+                oid: "dddd".to_string(),
+            })
+            .unwrap();
+        assert_eq!(session.first_rewritten(), Some("dddd"));
+        assert!(session
+            .apply(SessionOp::Drop {
+                oid: "dddd".to_string(),
+            })
+            .is_err());
+
+        assert_eq!(
+            session.undo(),
+            Some(SessionOp::Drop {
+                oid: "dddd".to_string(),
+            })
+        );
+        assert_eq!(session.commits().len(), 4);
+        assert_eq!(session.undo(), None);
+    }
+}
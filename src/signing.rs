@@ -0,0 +1,211 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Sign commit buffers the way `git commit -S` does.
+//
+// Git's signing contract:
+//   - `commit.gpgsign` turns signing on for every new commit
+//   - `gpg.format` picks the backend: openpgp (default), x509 or ssh
+//   - `gpg.<format>.program` (or `gpg.program` for openpgp) names the binary
+//   - `user.signingkey` selects the key; for openpgp/x509 it defaults to the
+//     committer identity
+//   - gpg/gpgsm read the buffer on stdin and write an armored detached
+//     signature to stdout; ssh-keygen signs a file and writes `<file>.sig`
+//
+// The signature ends up in the commit's `gpgsig` header for all formats.
+
+use crate::repo::{config_bool, GitRepo};
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::Write as _;
+use std::process::{Command, Stdio};
+
+/// Signature backend selected by `gpg.format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningFormat {
+    OpenPgp,
+    X509,
+    Ssh,
+}
+
+impl SigningFormat {
+    fn from_config(value: Option<&str>) -> Result<Self> {
+        match value.map(str::trim) {
+            None | Some("openpgp") => Ok(SigningFormat::OpenPgp),
+            Some("x509") => Ok(SigningFormat::X509),
+            Some("ssh") => Ok(SigningFormat::Ssh),
+            Some(other) => bail!("Unsupported gpg.format '{other}'"),
+        }
+    }
+
+    fn default_program(self) -> &'static str {
+        match self {
+            SigningFormat::OpenPgp => "gpg",
+            SigningFormat::X509 => "gpgsm",
+            SigningFormat::Ssh => "ssh-keygen",
+        }
+    }
+
+    fn config_name(self) -> &'static str {
+        match self {
+            SigningFormat::OpenPgp => "openpgp",
+            SigningFormat::X509 => "x509",
+            SigningFormat::Ssh => "ssh",
+        }
+    }
+}
+
+/// How new commits get signed, read from the repository config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningConfig {
+    pub format: SigningFormat,
+    pub program: String,
+    /// `user.signingkey`, if set.
+    pub key: Option<String>,
+}
+
+impl SigningConfig {
+    /// Read the signing configuration. Returns `None` when
+    /// `commit.gpgsign` is not enabled.
+    pub fn from_repo(repo: &impl GitRepo) -> Result<Option<Self>> {
+        let enabled = repo
+            .get_config_string("commit.gpgsign")
+            .is_some_and(|v| config_bool(&v));
+        if !enabled {
+            return Ok(None);
+        }
+
+        let format = SigningFormat::from_config(repo.get_config_string("gpg.format").as_deref())?;
+        let program = repo
+            .get_config_string(&format!("gpg.{}.program", format.config_name()))
+            .or_else(|| {
+                (format == SigningFormat::OpenPgp)
+                    .then(|| repo.get_config_string("gpg.program"))
+                    .flatten()
+            })
+            .unwrap_or_else(|| format.default_program().to_string());
+        let key = repo
+            .get_config_string("user.signingkey")
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty());
+
+        Ok(Some(SigningConfig {
+            format,
+            program,
+            key,
+        }))
+    }
+
+    /// Sign a commit buffer produced by `commit_create_buffer`, returning the
+    /// armored signature. `committer` is the `Name <email>` identity used as
+    /// key when `user.signingkey` is unset.
+    pub fn sign(&self, buffer: &[u8], committer: &str) -> Result<String> {
+        match self.format {
+            SigningFormat::OpenPgp | SigningFormat::X509 => self.sign_gpg(buffer, committer),
+            SigningFormat::Ssh => self.sign_ssh(buffer),
+        }
+    }
+
+    fn sign_gpg(&self, buffer: &[u8], committer: &str) -> Result<String> {
+        let key = self.key.as_deref().unwrap_or(committer);
+        let mut child = Command::new(&self.program)
+            .args(["--status-fd=2", "-bsau", key])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to run signing program '{}': {e}", self.program))?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(buffer)?;
+        let output = child.wait_with_output()?;
+
+        // Like git, trust the status line rather than the exit code alone.
+        let status = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() || !status.contains("[GNUPG:] SIG_CREATED ") {
+            bail!(
+                "{} failed to sign the data: {}",
+                self.program,
+                first_error_line(&status)
+            );
+        }
+        String::from_utf8(output.stdout).context("Signature is not valid UTF-8")
+    }
+
+    fn sign_ssh(&self, buffer: &[u8]) -> Result<String> {
+        let Some(key) = self.key.as_deref() else {
+            bail!("user.signingkey needs to be set for ssh signing");
+        };
+
+        // A literal public key means the private key lives in ssh-agent;
+        // ssh-keygen wants it in a file and `-U` to use the agent.
+        let literal = key
+            .strip_prefix("key::")
+            .or_else(|| key.starts_with("ssh-").then_some(key));
+        let mut key_file = None;
+        let key_path = match literal {
+            Some(public_key) => {
+                let mut file = tempfile::NamedTempFile::new()?;
+                writeln!(file, "{public_key}")?;
+                let path = file.path().to_path_buf();
+                key_file = Some(file);
+                path
+            }
+            None => std::path::PathBuf::from(key),
+        };
+
+        let mut data_file = tempfile::NamedTempFile::new()?;
+        data_file.write_all(buffer)?;
+        data_file.flush()?;
+
+        let mut command = Command::new(&self.program);
+        command
+            .args(["-Y", "sign", "-n", "git", "-f"])
+            .arg(&key_path);
+        if key_file.is_some() {
+            command.arg("-U");
+        }
+        let output = command
+            .arg(data_file.path())
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| anyhow!("Failed to run signing program '{}': {e}", self.program))?;
+        if !output.status.success() {
+            bail!(
+                "{} failed to sign the data: {}",
+                self.program,
+                first_error_line(&String::from_utf8_lossy(&output.stderr))
+            );
+        }
+
+        let mut sig_path = data_file.path().as_os_str().to_owned();
+        sig_path.push(".sig");
+        let signature = std::fs::read_to_string(&sig_path)
+            .with_context(|| format!("{} did not write a signature", self.program));
+        let _ = std::fs::remove_file(&sig_path);
+        signature
+    }
+}
+
+/// The first non-status line of the signing program's stderr, or a generic
+/// message when it printed nothing useful.
+fn first_error_line(stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with("[GNUPG:]"))
+        .unwrap_or("no signature created")
+        .to_string()
+}
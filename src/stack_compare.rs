@@ -0,0 +1,283 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Compare the local commit stack with its pushed version
+
+use std::collections::HashMap;
+
+use crate::CommitInfo;
+
+/// A commit together with its patch-id, used to align two stacks.
+#[derive(Debug, Clone)]
+pub struct StackCommit {
+    pub info: CommitInfo,
+    /// `None` for merge and root commits, which never match by patch.
+    pub patch_id: Option<String>,
+}
+
+/// How a commit differs between the pushed and the local stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackChange {
+    /// Same patch and same message on both sides.
+    Unchanged,
+    /// Present on both sides, but the patch or the message changed.
+    Modified,
+    /// Only in the local stack.
+    Added,
+    /// Only in the pushed stack.
+    Dropped,
+}
+
+/// One row of the comparison. Indices are stack positions, oldest first.
+#[derive(Debug, Clone)]
+pub struct StackRow {
+    pub remote: Option<(usize, CommitInfo)>,
+    pub local: Option<(usize, CommitInfo)>,
+    pub change: StackChange,
+    /// The commit moved relative to the other commits present on both sides.
+    pub reordered: bool,
+}
+
+/// Align the pushed stack with the local stack (both oldest first).
+///
+/// Commits are first matched by patch-id, then the remaining ones by
+/// summary line. Matched commits whose relative order changed are flagged
+/// as reordered: the longest run of matches that kept their order stays in
+/// place and every other match counts as moved. Rows follow the local
+/// order, with dropped commits inserted where they used to be.
+pub fn compare_stacks(remote: &[StackCommit], local: &[StackCommit]) -> Vec<StackRow> {
+    let mut local_match: Vec<Option<usize>> = vec![None; local.len()];
+    let mut remote_match: Vec<Option<usize>> = vec![None; remote.len()];
+
+    let mut by_patch: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (r, commit) in remote.iter().enumerate() {
+        if let Some(id) = &commit.patch_id {
+            by_patch.entry(id.as_str()).or_default().push(r);
+        }
+    }
+    for (l, commit) in local.iter().enumerate() {
+        let Some(id) = &commit.patch_id else {
+            continue;
+        };
+        if let Some(candidates) = by_patch.get_mut(id.as_str()) {
+            if !candidates.is_empty() {
+                let r = candidates.remove(0);
+                local_match[l] = Some(r);
+                remote_match[r] = Some(l);
+            }
+        }
+    }
+
+    for (l, commit) in local.iter().enumerate() {
+        if local_match[l].is_some() {
+            continue;
+        }
+        if let Some(r) = (0..remote.len())
+            .find(|&r| remote_match[r].is_none() && remote[r].info.summary == commit.info.summary)
+        {
+            local_match[l] = Some(r);
+            remote_match[r] = Some(l);
+        }
+    }
+
+    let matched: Vec<(usize, usize)> = local_match
+        .iter()
+        .enumerate()
+        .filter_map(|(l, r)| r.map(|r| (l, r)))
+        .collect();
+    let in_order = longest_increasing(&matched.iter().map(|&(_, r)| r).collect::<Vec<_>>());
+    let mut reordered = vec![false; local.len()];
+    for (k, &(l, _)) in matched.iter().enumerate() {
+        reordered[l] = !in_order.contains(&k);
+    }
+
+    let mut rows = Vec::new();
+    let mut next_remote = 0;
+    let mut push_dropped_before = |limit: usize, rows: &mut Vec<StackRow>| {
+        while next_remote < limit {
+            if remote_match[next_remote].is_none() {
+                rows.push(StackRow {
+                    remote: Some((next_remote, remote[next_remote].info.clone())),
+                    local: None,
+                    change: StackChange::Dropped,
+                    reordered: false,
+                });
+            }
+            next_remote += 1;
+        }
+    };
+
+    for (l, commit) in local.iter().enumerate() {
+        match local_match[l] {
+            Some(r) => {
+                if !reordered[l] {
+                    push_dropped_before(r, &mut rows);
+                }
+                let same = remote[r].patch_id.is_some()
+                    && remote[r].patch_id == commit.patch_id
+                    && remote[r].info.message == commit.info.message;
+                rows.push(StackRow {
+                    remote: Some((r, remote[r].info.clone())),
+                    local: Some((l, commit.info.clone())),
+                    change: if same {
+                        StackChange::Unchanged
+                    } else {
+                        StackChange::Modified
+                    },
+                    reordered: reordered[l],
+                });
+            }
+            None => rows.push(StackRow {
+                remote: None,
+                local: Some((l, commit.info.clone())),
+                change: StackChange::Added,
+                reordered: false,
+            }),
+        }
+    }
+    push_dropped_before(remote.len(), &mut rows);
+
+    rows
+}
+
+/// Positions (into `values`) of one longest strictly increasing subsequence.
+fn longest_increasing(values: &[usize]) -> Vec<usize> {
+    // tails[k] = position of the smallest tail of an increasing run of length k+1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; values.len()];
+    for (i, &v) in values.iter().enumerate() {
+        let k = tails.partition_point(|&t| values[t] < v);
+        prev[i] = if k > 0 { Some(tails[k - 1]) } else { None };
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut result = Vec::with_capacity(tails.len());
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        result.push(i);
+        cursor = prev[i];
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack_commit(oid: &str, summary: &str, patch_id: &str) -> StackCommit {
+        StackCommit {
+            info: CommitInfo {
+                oid: oid.to_string(),
+                summary: summary.to_string(),
+                author: None,
+                date: None,
+                parent_oids: vec![],
+                message: summary.to_string(),
+                author_email: None,
+                author_date: None,
+                committer: None,
+                committer_email: None,
+                commit_date: None,
+            },
+            patch_id: Some(patch_id.to_string()),
+        }
+    }
+
+    fn changes(rows: &[StackRow]) -> Vec<(StackChange, bool)> {
+        rows.iter().map(|r| (r.change, r.reordered)).collect()
+    }
+
+    #[test]
+    fn identical_stacks_are_unchanged() {
+        let remote = vec![stack_commit("a", "A", "pa"), stack_commit("b", "B", "pb")];
+        let local = remote.clone();
+
+        let rows = compare_stacks(&remote, &local);
+
+        assert_eq!(
+            changes(&rows),
+            vec![
+                (StackChange::Unchanged, false),
+                (StackChange::Unchanged, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn added_dropped_and_modified() {
+        let remote = vec![
+            stack_commit("a", "A", "pa"),
+            stack_commit("b", "B", "pb"),
+            stack_commit("c", "C", "pc"),
+        ];
+        let local = vec![
+            stack_commit("a", "A", "pa"),
+            stack_commit("c2", "C", "pc-amended"),
+            stack_commit("d", "D", "pd"),
+        ];
+
+        let rows = compare_stacks(&remote, &local);
+
+        assert_eq!(
+            changes(&rows),
+            vec![
+                (StackChange::Unchanged, false),
+                (StackChange::Dropped, false),
+                (StackChange::Modified, false),
+                (StackChange::Added, false),
+            ]
+        );
+        assert_eq!(rows[1].remote.as_ref().unwrap().1.oid, "b");
+    }
+
+    #[test]
+    fn moved_commit_is_reordered() {
+        let remote = vec![
+            stack_commit("a", "A", "pa"),
+            stack_commit("b", "B", "pb"),
+            stack_commit("c", "C", "pc"),
+        ];
+        let local = vec![
+            stack_commit("c", "C", "pc"),
+            stack_commit("a", "A", "pa"),
+            stack_commit("b", "B", "pb"),
+        ];
+
+        let rows = compare_stacks(&remote, &local);
+
+        assert_eq!(
+            changes(&rows),
+            vec![
+                (StackChange::Unchanged, true),
+                (StackChange::Unchanged, false),
+                (StackChange::Unchanged, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn reworded_commit_matches_by_patch() {
+        let remote = vec![stack_commit("a", "Old title", "pa")];
+        let local = vec![stack_commit("a2", "New title", "pa")];
+
+        let rows = compare_stacks(&remote, &local);
+
+        assert_eq!(changes(&rows), vec![(StackChange::Modified, false)]);
+    }
+}
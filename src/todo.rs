@@ -0,0 +1,564 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// `git rebase -i` style todo lists for the stack.
+//
+// The stack is written oldest first as `<command> <oid> <summary>` lines,
+// followed by git's command help as comments. After editing, the lines are
+// parsed back and turned into plan steps for `GitRepo::execute_plan`, with
+// reword and squash messages decided up front through the editor.
+
+use crate::message::MessageOptions;
+use crate::repo::PlanStep;
+use crate::CommitInfo;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+const SHORT_OID_LENGTH: usize = 10;
+
+const HELP: &str = "\
+Commands:
+p, pick <commit> = use commit
+r, reword <commit> = use commit, but edit the commit message
+e, edit <commit> = use commit, but stop for amending
+s, squash <commit> = use commit, but meld into previous commit
+f, fixup <commit> = like \"squash\" but keep only the previous commit's message
+x, exec <command> = run command (the rest of the line) using shell
+d, drop <commit> = remove commit
+
+These lines can be re-ordered; they are executed from top to bottom.
+
+If you remove a line here THAT COMMIT WILL BE LOST.
+
+However, if you remove everything, the plan will be aborted.";
+
+/// What to do with a commit in the todo list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoCommand {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl TodoCommand {
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "p" | "pick" => Some(TodoCommand::Pick),
+            "r" | "reword" => Some(TodoCommand::Reword),
+            "e" | "edit" => Some(TodoCommand::Edit),
+            "s" | "squash" => Some(TodoCommand::Squash),
+            "f" | "fixup" => Some(TodoCommand::Fixup),
+            "d" | "drop" => Some(TodoCommand::Drop),
+            _ => None,
+        }
+    }
+
+    /// The command's name in a todo list.
+    pub fn name(self) -> &'static str {
+        match self {
+            TodoCommand::Pick => "pick",
+            TodoCommand::Reword => "reword",
+            TodoCommand::Edit => "edit",
+            TodoCommand::Squash => "squash",
+            TodoCommand::Fixup => "fixup",
+            TodoCommand::Drop => "drop",
+        }
+    }
+}
+
+/// One parsed todo line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoLine {
+    Commit { command: TodoCommand, oid: String },
+    Exec(String),
+}
+
+/// Build the todo list for `commits` (oldest first), every commit picked.
+pub fn format_todo(commits: &[CommitInfo], comment: &str) -> String {
+    let mut text = String::new();
+    for commit in commits {
+        let short: String = commit.oid.chars().take(SHORT_OID_LENGTH).collect();
+        text.push_str(&format!("pick {short} {}\n", commit.summary));
+    }
+    let options = MessageOptions {
+        comment: comment.to_string(),
+        ..MessageOptions::default()
+    };
+    text.push('\n');
+    text.push_str(&options.comment_lines(&format!(
+        "Rebase plan for {} commit(s)\n\n{HELP}",
+        commits.len()
+    )));
+    text
+}
+
+/// Parse an edited todo list. Commits are named by full OID or a unique
+/// prefix of one of `commits`; every invalid line is reported.
+pub fn parse_todo(
+    text: &str,
+    commits: &[CommitInfo],
+    comment: &str,
+) -> Result<Vec<TodoLine>, Vec<String>> {
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut has_commit = false;
+
+    for (number, line) in text.lines().enumerate() {
+        let number = number + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(comment) {
+            continue;
+        }
+        let (word, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        if word == "x" || word == "exec" {
+            if rest.is_empty() {
+                errors.push(format!("line {number}: exec needs a command"));
+            } else {
+                lines.push(TodoLine::Exec(rest.to_string()));
+            }
+            continue;
+        }
+        let Some(command) = TodoCommand::parse(word) else {
+            errors.push(format!("line {number}: unknown command '{word}'"));
+            continue;
+        };
+        let name = rest.split_whitespace().next().unwrap_or("");
+        let matches: Vec<&CommitInfo> = commits
+            .iter()
+            .filter(|c| !name.is_empty() && c.oid.starts_with(name))
+            .collect();
+        let oid = match matches[..] {
+            [commit] => commit.oid.as_str(),
+            [] => {
+                errors.push(format!(
+                    "line {number}: '{name}' is not one of the listed commits"
+                ));
+                continue;
+            }
+            _ => {
+                errors.push(format!("line {number}: '{name}' is ambiguous"));
+                continue;
+            }
+        };
+        if seen.contains(&oid) {
+            errors.push(format!("line {number}: '{name}' is listed more than once"));
+            continue;
+        }
+        seen.push(oid);
+        if matches!(command, TodoCommand::Squash | TodoCommand::Fixup) && !has_commit {
+            errors.push(format!("line {number}: '{word}' needs a previous commit"));
+        }
+        if command != TodoCommand::Drop {
+            has_commit = true;
+        }
+        lines.push(TodoLine::Commit {
+            command,
+            oid: oid.to_string(),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(lines)
+    } else {
+        Err(errors)
+    }
+}
+
+/// The commits named by a todo list written by `git rebase -i`, in order.
+///
+/// Only commit lines can be arranged; lines such as `exec`, `label` or
+/// `merge` are reported as errors.
+pub fn todo_commit_names(text: &str, comment: &str) -> Result<Vec<String>, Vec<String>> {
+    let mut names = Vec::new();
+    let mut errors = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        // git writes `noop` when there is nothing to rebase.
+        if line.is_empty() || line.starts_with(comment) || line == "noop" {
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let word = words.next().unwrap_or("");
+        match (TodoCommand::parse(word), words.next()) {
+            (Some(_), Some(name)) => names.push(name.to_string()),
+            (Some(_), None) => errors.push(format!("line {}: '{word}' needs a commit", number + 1)),
+            (None, _) => errors.push(format!("line {}: '{word}' is not supported", number + 1)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(names)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Write a todo list for git: one line per commit in the given order, with
+/// its command from `commands` (`pick` when absent).
+///
+/// Fails when the first commit that is kept would be a squash or fixup,
+/// which git would reject.
+pub fn write_todo(
+    commits: &[CommitInfo],
+    commands: &HashMap<String, TodoCommand>,
+) -> Result<String, String> {
+    let command_of = |c: &CommitInfo| commands.get(&c.oid).copied().unwrap_or(TodoCommand::Pick);
+    let first_kept = commits
+        .iter()
+        .map(command_of)
+        .find(|command| *command != TodoCommand::Drop);
+    if let Some(command @ (TodoCommand::Squash | TodoCommand::Fixup)) = first_kept {
+        return Err(format!(
+            "Cannot '{}' without a previous commit",
+            command.name()
+        ));
+    }
+    Ok(commits
+        .iter()
+        .map(|c| format!("{} {} {}\n", command_of(c).name(), c.oid, c.summary))
+        .collect())
+}
+
+/// Whether the todo keeps the stack exactly as it is.
+pub fn is_unchanged(lines: &[TodoLine], commits: &[CommitInfo]) -> bool {
+    lines.len() == commits.len()
+        && lines.iter().zip(commits).all(|(line, commit)| {
+            matches!(line, TodoLine::Commit { command: TodoCommand::Pick, oid } if *oid == commit.oid)
+        })
+}
+
+/// The first commit of `commits` (oldest first) that the todo rewrites:
+/// everything before it stays as it is.
+pub fn first_rewritten<'a>(lines: &[TodoLine], commits: &'a [CommitInfo]) -> Option<&'a str> {
+    let kept = lines
+        .iter()
+        .zip(commits)
+        .take_while(|(line, commit)| {
+            matches!(line, TodoLine::Commit { command: TodoCommand::Pick, oid } if *oid == commit.oid)
+        })
+        .count();
+    commits.get(kept).map(|c| c.oid.as_str())
+}
+
+/// Turn parsed todo lines into plan steps.
+///
+/// `edit` is called with a default message, the `prepare-commit-msg`
+/// source and the commit OID, and returns the message the user wrote: once
+/// per reworded commit and once per group of commits that contains a
+/// `squash`. An empty message aborts the plan.
+pub fn build_plan(
+    lines: &[TodoLine],
+    commits: &[CommitInfo],
+    options: &MessageOptions,
+    mut edit: impl FnMut(&str, &str, Option<&str>) -> Result<String>,
+) -> Result<Vec<PlanStep>> {
+    let message_of = |oid: &str| {
+        commits
+            .iter()
+            .find(|c| c.oid == oid)
+            .map(|c| c.message.clone())
+            .unwrap_or_default()
+    };
+    let mut steps = Vec::new();
+    // Messages of the current squash group (pick + squashes) and whether a
+    // squash, rather than only fixups, joined it.
+    let mut group: Vec<String> = Vec::new();
+    let mut group_has_squash = false;
+
+    for line in lines {
+        let TodoLine::Commit { command, oid } = line else {
+            // Like git, an exec between a pick and its squashes does not
+            // end the squash group.
+            if let TodoLine::Exec(command) = line {
+                steps.push(PlanStep::Exec(command.clone()));
+            }
+            continue;
+        };
+        match command {
+            TodoCommand::Squash | TodoCommand::Fixup => {
+                if *command == TodoCommand::Squash {
+                    group.push(message_of(oid));
+                    group_has_squash = true;
+                }
+                steps.push(PlanStep::Fold {
+                    oid: oid.clone(),
+                    message: None,
+                });
+            }
+            TodoCommand::Drop => {}
+            TodoCommand::Pick | TodoCommand::Reword | TodoCommand::Edit => {
+                finish_group(
+                    &mut steps,
+                    &mut group,
+                    &mut group_has_squash,
+                    options,
+                    &mut edit,
+                )?;
+                let message = if *command == TodoCommand::Reword {
+                    let original = message_of(oid);
+                    Some(edited(&mut edit, &original, "commit", Some(oid))?)
+                } else {
+                    None
+                };
+                group.push(message.clone().unwrap_or_else(|| message_of(oid)));
+                steps.push(PlanStep::Pick {
+                    oid: oid.clone(),
+                    message,
+                });
+                if *command == TodoCommand::Edit {
+                    steps.push(PlanStep::Stop);
+                }
+            }
+        }
+    }
+    finish_group(
+        &mut steps,
+        &mut group,
+        &mut group_has_squash,
+        options,
+        &mut edit,
+    )?;
+    Ok(steps)
+}
+
+type EditFn<'a> = dyn FnMut(&str, &str, Option<&str>) -> Result<String> + 'a;
+
+/// Run the editor callback, rejecting empty messages.
+fn edited(edit: &mut EditFn, message: &str, source: &str, oid: Option<&str>) -> Result<String> {
+    let edited = edit(message, source, oid)?;
+    if edited.trim().is_empty() {
+        bail!("empty commit message");
+    }
+    Ok(edited)
+}
+
+/// Close a squash group: when a `squash` joined it, ask for the combined
+/// message and put it on the group's last fold, which may be followed by
+/// `exec` steps.
+fn finish_group(
+    steps: &mut [PlanStep],
+    group: &mut Vec<String>,
+    has_squash: &mut bool,
+    options: &MessageOptions,
+    edit: &mut EditFn,
+) -> Result<()> {
+    if std::mem::take(has_squash) {
+        let refs: Vec<&str> = group.iter().map(String::as_str).collect();
+        let message = edited(edit, &options.squash_message(&refs), "squash", None)?;
+        let last_fold = steps.iter_mut().rev().find_map(|step| match step {
+            PlanStep::Fold { message, .. } => Some(message),
+            _ => None,
+        });
+        if let Some(slot) = last_fold {
+            *slot = Some(message);
+        }
+    }
+    group.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack() -> Vec<CommitInfo> {
+        vec![
+            CommitInfo::with_message("aaaa000000001111", "Add a\n"),
+            CommitInfo::with_message("bbbb000000002222", "Add b\n"),
+            CommitInfo::with_message("cccc000000003333", "Fix a\n"),
+        ]
+    }
+
+    #[test]
+    fn formatted_todo_parses_back_unchanged() {
+        let commits = stack();
+        let text = format_todo(&commits, "#");
+        assert!(text.starts_with("pick aaaa000000 Add a\n"));
+        let lines = parse_todo(&text, &commits, "#").unwrap();
+        assert!(is_unchanged(&lines, &commits));
+        assert_eq!(first_rewritten(&lines, &commits), None);
+    }
+
+    #[test]
+    fn git_todo_names_and_written_todo() {
+        let text = "pick aaaa Add a\n# comment\nfixup cccc Fix a\nexec make\nbreak\n";
+        assert_eq!(
+            todo_commit_names(text, "#"),
+            Err(vec![
+                "line 4: 'exec' is not supported".to_string(),
+                "line 5: 'break' is not supported".to_string(),
+            ])
+        );
+
+        let commits = stack();
+        let mut commands = HashMap::new();
+        commands.insert(commits[1].oid.clone(), TodoCommand::Drop);
+        commands.insert(commits[2].oid.clone(), TodoCommand::Fixup);
+        assert_eq!(
+            write_todo(&commits, &commands),
+            Ok("pick aaaa000000001111 Add a\n\
+                drop bbbb000000002222 Add b\n\
+                fixup cccc000000003333 Fix a\n"
+                .to_string())
+        );
+
+        commands.insert(commits[0].oid.clone(), TodoCommand::Drop);
+        commands.insert(commits[1].oid.clone(), TodoCommand::Squash);
+        assert_eq!(
+            write_todo(&commits, &commands),
+            Err("Cannot 'squash' without a previous commit".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_reports_every_bad_line() {
+        let text = "squash aaaa Add a\nfrobnicate bbbb\npick ffff\npick aaaa\nexec\n";
+        assert_eq!(
+            parse_todo(text, &stack(), "#"),
+            Err(vec![
+                "line 1: 'squash' needs a previous commit".to_string(),
+                "line 2: unknown command 'frobnicate'".to_string(),
+                "line 3: 'ffff' is not one of the listed commits".to_string(),
+                "line 4: 'aaaa' is listed more than once".to_string(),
+                "line 5: exec needs a command".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn plan_moves_fixups_and_rewords() {
+        let commits = stack();
+        let text = "r aaaa\nf cccc\nx make test\nd bbbb\n";
+        let lines = parse_todo(text, &commits, "#").unwrap();
+        assert_eq!(first_rewritten(&lines, &commits), Some("aaaa000000001111"));
+
+        let mut calls = Vec::new();
+        let steps = build_plan(
+            &lines,
+            &commits,
+            &MessageOptions::default(),
+            |msg, src, _| {
+                calls.push((msg.to_string(), src.to_string()));
+                Ok("Add a file\n".to_string())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(calls, vec![("Add a\n".to_string(), "commit".to_string())]);
+        assert_eq!(
+            steps,
+            vec![
+                PlanStep::Pick {
+                    oid: "aaaa000000001111".to_string(),
+                    message: Some("Add a file\n".to_string()),
+                },
+                PlanStep::Fold {
+                    oid: "cccc000000003333".to_string(),
+                    message: None,
+                },
+                PlanStep::Exec("make test".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn squash_group_opens_editor_once_with_header() {
+        let commits = stack();
+        let lines = parse_todo("p aaaa\ns cccc\ne bbbb\n", &commits, "#").unwrap();
+
+        let mut seen = Vec::new();
+        let steps = build_plan(
+            &lines,
+            &commits,
+            &MessageOptions::default(),
+            |msg, src, _| {
+                seen.push(msg.to_string());
+                assert_eq!(src, "squash");
+                Ok("Add a\n".to_string())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].starts_with("# This is a combination of 2 commits."));
+        assert!(seen[0].contains("Fix a"));
+        assert_eq!(steps.len(), 4);
+        assert_eq!(
+            steps[1],
+            PlanStep::Fold {
+                oid: "cccc000000003333".to_string(),
+                message: Some("Add a\n".to_string()),
+            }
+        );
+        assert_eq!(steps[3], PlanStep::Stop);
+    }
+
+    #[test]
+    fn exec_inside_squash_group_keeps_the_pick_message() {
+        let commits = stack();
+        let lines = parse_todo("p aaaa\nx make\ns cccc\np bbbb\n", &commits, "#").unwrap();
+
+        let mut seen = Vec::new();
+        let steps = build_plan(&lines, &commits, &MessageOptions::default(), |msg, _, _| {
+            seen.push(msg.to_string());
+            Ok("Add a\n".to_string())
+        })
+        .unwrap();
+
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].starts_with("# This is a combination of 2 commits."));
+        assert!(seen[0].contains("Add a") && seen[0].contains("Fix a"));
+        assert_eq!(steps[1], PlanStep::Exec("make".to_string()));
+        assert_eq!(
+            steps[2],
+            PlanStep::Fold {
+                oid: "cccc000000003333".to_string(),
+                message: Some("Add a\n".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn squash_message_survives_a_following_exec() {
+        let commits = stack();
+        let lines = parse_todo("p aaaa\ns cccc\nx make\np bbbb\n", &commits, "#").unwrap();
+        let steps = build_plan(&lines, &commits, &MessageOptions::default(), |_, _, _| {
+            Ok("Add a\n".to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            steps[1],
+            PlanStep::Fold {
+                oid: "cccc000000003333".to_string(),
+                message: Some("Add a\n".to_string()),
+            }
+        );
+        assert_eq!(steps[2], PlanStep::Exec("make".to_string()));
+    }
+
+    #[test]
+    fn empty_message_aborts_plan() {
+        let commits = stack();
+        let lines = parse_todo("r aaaa\n", &commits, "#").unwrap();
+        let result = build_plan(&lines, &commits, &MessageOptions::default(), |_, _, _| {
+            Ok(String::new())
+        });
+        assert!(result.is_err());
+    }
+}
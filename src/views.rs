@@ -17,8 +17,17 @@
 pub mod commit_detail;
 pub mod commit_list;
 pub mod conflict;
+pub mod conflict_resolve;
 pub mod dialog;
 pub mod drop;
+pub mod edit_session;
 pub mod help;
+pub mod metadata;
+pub mod preview;
+pub mod pushed;
+pub mod range_diff;
+pub mod sequence_edit;
 pub mod split_select;
 pub mod squash_select;
+pub mod stack_compare;
+pub mod strategy_select;
@@ -126,6 +126,20 @@ pub fn render(repo: &impl GitRepo, frame: &mut Frame, app: &mut AppState, area:
             )));
         }
 
+        if let Some(warnings) = app.lint_warnings.get(&selected.oid) {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Lint Warnings:",
+                Style::default().fg(Color::Yellow),
+            )));
+            for warning in warnings {
+                content.push(Line::from(Span::styled(
+                    format!("  ! {warning}"),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+        }
+
         content.push(Line::from(""));
         if let (Some(author), Some(author_email)) = (&selected.author, &selected.author_email) {
             content.push(Line::from(vec![
@@ -205,61 +219,7 @@ pub fn render(repo: &impl GitRepo, frame: &mut Frame, app: &mut AppState, area:
             )));
             content.push(Line::from(""));
 
-            for file in &diff.files {
-                // File headers (unified diff format)
-                let old_path = file
-                    .old_path
-                    .as_ref()
-                    .map(|s| format!("a/{}", s))
-                    .unwrap_or_else(|| "/dev/null".to_string());
-                let new_path = file
-                    .new_path
-                    .as_ref()
-                    .map(|s| format!("b/{}", s))
-                    .unwrap_or_else(|| "/dev/null".to_string());
-
-                content.push(Line::from(Span::styled(
-                    format!("--- {}", old_path),
-                    Style::default().fg(Color::White),
-                )));
-                content.push(Line::from(Span::styled(
-                    format!("+++ {}", new_path),
-                    Style::default().fg(Color::White),
-                )));
-
-                // Render each hunk
-                for hunk in &file.hunks {
-                    // Hunk header
-                    let hunk_header = format!(
-                        "@@ -{},{} +{},{} @@",
-                        hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
-                    );
-                    content.push(Line::from(Span::styled(
-                        hunk_header,
-                        Style::default().fg(Color::Cyan),
-                    )));
-
-                    // Render each line
-                    for line in &hunk.lines {
-                        use crate::DiffLineKind;
-
-                        let (prefix, style) = match line.kind {
-                            DiffLineKind::Addition => ("+", Style::default().fg(Color::Green)),
-                            DiffLineKind::Deletion => ("-", Style::default().fg(Color::Red)),
-                            DiffLineKind::Context => (" ", Style::default().fg(Color::White)),
-                        };
-
-                        // Remove trailing newline if present
-                        let content_str = line.content.trim_end_matches('\n');
-                        content.push(Line::from(Span::styled(
-                            format!("{}{}", prefix, content_str),
-                            style,
-                        )));
-                    }
-                }
-
-                content.push(Line::from(""));
-            }
+            content.extend(diff_lines(&diff.files));
         }
 
         // Calculate scrolling bounds
@@ -309,6 +269,73 @@ pub fn render(repo: &impl GitRepo, frame: &mut Frame, app: &mut AppState, area:
     frame.render_widget(footer, footer_area);
 }
 
+/// Render the files of a diff in unified diff format: `---`/`+++` file
+/// headers, `@@` hunk headers and colored added/removed/context lines.
+pub fn diff_lines(files: &[crate::FileDiff]) -> Vec<Line<'static>> {
+    let mut content = Vec::new();
+    for file in files {
+        // File headers (unified diff format)
+        let old_path = file
+            .old_path
+            .as_ref()
+            .map(|s| format!("a/{}", s))
+            .unwrap_or_else(|| "/dev/null".to_string());
+        let new_path = file
+            .new_path
+            .as_ref()
+            .map(|s| format!("b/{}", s))
+            .unwrap_or_else(|| "/dev/null".to_string());
+
+        content.push(Line::from(Span::styled(
+            format!("--- {}", old_path),
+            Style::default().fg(Color::White),
+        )));
+        content.push(Line::from(Span::styled(
+            format!("+++ {}", new_path),
+            Style::default().fg(Color::White),
+        )));
+
+        // Render each hunk
+        for hunk in &file.hunks {
+            // Hunk header
+            let hunk_header = format!(
+                "@@ -{},{} +{},{} @@",
+                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+            );
+            content.push(Line::from(Span::styled(
+                hunk_header,
+                Style::default().fg(Color::Cyan),
+            )));
+
+            // Render each line
+            for line in &hunk.lines {
+                let (prefix, style) = diff_line_prefix_and_style(line.kind);
+
+                // Remove trailing newline if present
+                let content_str = line.content.trim_end_matches('\n');
+                content.push(Line::from(Span::styled(
+                    format!("{}{}", prefix, content_str),
+                    style,
+                )));
+            }
+        }
+
+        content.push(Line::from(""));
+    }
+    content
+}
+
+/// The `+`/`-`/` ` prefix and color used for a diff line of the given kind.
+pub fn diff_line_prefix_and_style(kind: crate::DiffLineKind) -> (&'static str, Style) {
+    use crate::DiffLineKind;
+
+    match kind {
+        DiffLineKind::Addition => ("+", Style::default().fg(Color::Green)),
+        DiffLineKind::Deletion => ("-", Style::default().fg(Color::Red)),
+        DiffLineKind::Context => (" ", Style::default().fg(Color::White)),
+    }
+}
+
 /// Determine file status and display path from a FileDiff.
 fn get_file_status_and_path(file: &crate::FileDiff) -> (FileStatus, String) {
     use crate::DeltaStatus;
@@ -358,7 +385,7 @@ fn get_status_color(status: FileStatus) -> Color {
 }
 
 /// Render a vertical scrollbar indicating scroll position.
-fn render_scrollbar(
+pub(crate) fn render_scrollbar(
     frame: &mut Frame,
     area: Rect,
     scroll_offset: usize,
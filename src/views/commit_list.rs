@@ -14,12 +14,13 @@
 
 // Commit list view rendering
 
-use crate::app::{AppAction, AppMode, AppState};
+use crate::app::{AppAction, AppMode, AppState, PreviewState};
 use crate::event::KeyCommand;
 use crate::fragmap::{self, TouchKind};
+use crate::todo::TodoCommand;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table},
     Frame,
@@ -114,15 +115,135 @@ pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
                 }
             }
         }
+        KeyCommand::BulkReword => {
+            if app
+                .commits
+                .iter()
+                .all(|c| c.oid == "staged" || c.oid == "unstaged")
+            {
+                app.set_error_message("No commits to reword");
+                AppAction::Handled
+            } else {
+                AppAction::PrepareBulkReword
+            }
+        }
+        KeyCommand::EditPlan => {
+            if app
+                .commits
+                .iter()
+                .any(|c| c.oid == "staged" || c.oid == "unstaged")
+            {
+                app.set_error_message("Plan needs a clean working tree");
+                AppAction::Handled
+            } else if app.commits.is_empty() {
+                app.set_error_message("No commits to plan");
+                AppAction::Handled
+            } else {
+                AppAction::PrepareEditPlan
+            }
+        }
+        KeyCommand::EditSession => {
+            if app
+                .commits
+                .iter()
+                .any(|c| c.oid == "staged" || c.oid == "unstaged")
+            {
+                app.set_error_message("Session needs a clean working tree");
+                AppAction::Handled
+            } else if app.commits.is_empty() {
+                app.set_error_message("No commits to edit");
+                AppAction::Handled
+            } else {
+                app.start_edit_session();
+                AppAction::Handled
+            }
+        }
+        KeyCommand::EditMetadata => {
+            let commit = &app.commits[app.selection_index];
+            if commit.oid == "staged" || commit.oid == "unstaged" {
+                app.set_error_message("Cannot edit staged/unstaged changes");
+                AppAction::Handled
+            } else {
+                AppAction::PrepareMetadataEdit {
+                    commit_oid: commit.oid.clone(),
+                }
+            }
+        }
+        KeyCommand::DropUpstreamed => {
+            let upstreamed: Vec<&crate::CommitInfo> = app
+                .commits
+                .iter()
+                .filter(|c| app.upstreamed_oids.contains(&c.oid))
+                .collect();
+            if upstreamed.is_empty() {
+                app.set_error_message("No commits found upstream");
+                AppAction::Handled
+            } else {
+                AppAction::PrepareDropUpstreamedConfirm {
+                    commit_oids: upstreamed.iter().map(|c| c.oid.clone()).collect(),
+                    commit_summaries: upstreamed.iter().map(|c| c.summary.clone()).collect(),
+                }
+            }
+        }
+        KeyCommand::ToggleMark => {
+            let commit = &app.commits[app.selection_index];
+            if commit.oid == "staged" || commit.oid == "unstaged" {
+                app.set_error_message("Cannot compare staged/unstaged changes");
+            } else {
+                app.toggle_mark();
+            }
+            AppAction::Handled
+        }
+        KeyCommand::Difftool => {
+            let commit = &app.commits[app.selection_index];
+            if commit.oid == "staged" || commit.oid == "unstaged" {
+                app.set_error_message("Cannot compare staged/unstaged changes");
+                AppAction::Handled
+            } else {
+                app.difftool_action()
+            }
+        }
+        KeyCommand::RangeDiff => AppAction::ShowRangeDiff,
+        KeyCommand::ComparePushed => AppAction::ComparePushed,
         KeyCommand::Update => AppAction::ReloadCommits,
         KeyCommand::Quit => AppAction::Quit,
-        KeyCommand::Mergetool | KeyCommand::None => AppAction::Handled,
+        KeyCommand::Mergetool
+        | KeyCommand::MoveCommitUp
+        | KeyCommand::MoveCommitDown
+        | KeyCommand::TakeOurs
+        | KeyCommand::TakeTheirs
+        | KeyCommand::TakeBoth
+        | KeyCommand::RetryStrategy
+        | KeyCommand::Preview
+        | KeyCommand::InsertChar(_)
+        | KeyCommand::DeleteBackward
+        | KeyCommand::DeleteForward
+        | KeyCommand::AddField
+        | KeyCommand::RemoveField
+        | KeyCommand::Complete
+        | KeyCommand::None => AppAction::Handled,
     }
 }
 
 /// Number of characters to display for short SHA.
 const SHORT_SHA_LENGTH: usize = 8;
 
+/// Marker shown after the SHA of a commit whose patch is already upstream.
+const UPSTREAMED_MARKER: &str = "=";
+const COLOR_UPSTREAMED: Color = Color::Green;
+/// Marker shown after the SHA of a commit that is already pushed.
+const PUSHED_MARKER: &str = "\u{2191}";
+const COLOR_PUSHED: Color = Color::Magenta;
+/// Background of the short SHA of the commit marked for comparison.
+const COLOR_MARKED_BG: Color = Color::Blue;
+/// Marker shown in the last SHA column slot of a commit with lint warnings.
+const LINT_MARKER: &str = "!";
+const COLOR_LINT: Color = Color::Yellow;
+/// Todo command prefix in sequence editor mode, and its color for dropped
+/// commits.
+const COLOR_TODO_COMMAND: Color = Color::Cyan;
+const COLOR_DROPPED: Color = Color::DarkGray;
+
 const HEADER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Green);
 const FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Blue);
 const SEPARATOR_STYLE: Style = Style::new().fg(Color::White).bg(Color::Blue);
@@ -154,6 +275,11 @@ const COLOR_SYNTHETIC_LABEL: Color = Color::Cyan;
 const COLOR_SQUASH_SOURCE_BG: Color = Color::Rgb(0, 120, 120);
 const COLOR_SQUASH_TARGET_BG: Color = Color::Rgb(0, 40, 50);
 
+// Preview: the commit a simulated rewrite stops at, and the commits after
+// it that the rewrite would not reach.
+const COLOR_PREVIEW_CONFLICT: Color = Color::Red;
+const COLOR_PREVIEW_UNAPPLIED: Color = Color::DarkGray;
+
 /// Maximum width for the title column, keeping fragmap adjacent to titles.
 const MAX_TITLE_WIDTH: u16 = 60;
 
@@ -521,6 +647,10 @@ fn build_rows<'a>(app: &AppState, layout: &LayoutInfo) -> Vec<Row<'a>> {
         AppMode::SquashSelect { source_index, .. } => Some(source_index),
         _ => None,
     };
+    let preview = match &app.mode {
+        AppMode::Preview(state) => Some(state.as_ref()),
+        _ => None,
+    };
 
     visible_commits
         .iter()
@@ -544,31 +674,38 @@ fn build_rows<'a>(app: &AppState, layout: &LayoutInfo) -> Vec<Row<'a>> {
             let is_squash_source = squash_source_idx.is_some_and(|si| commit_idx_in_fragmap == si);
 
             // Determine text style based on mode and position.
-            let text_style = if let Some(source_idx) = squash_source_idx {
-                // SquashSelect mode: color by relation to squash source.
-                if is_squash_source {
-                    Style::new().fg(Color::White)
-                } else if commit_idx_in_fragmap > source_idx {
-                    Style::new().fg(Color::DarkGray)
-                } else if is_synthetic {
-                    Style::new().fg(COLOR_SYNTHETIC_LABEL)
-                } else if let Some(ref fm) = app.fragmap {
-                    commit_text_style(fm, source_idx, commit_idx_in_fragmap)
+            let text_style =
+                if preview.is_some_and(|p| p.conflicting_oid.as_ref() == Some(&commit.oid)) {
+                    Style::new()
+                        .fg(COLOR_PREVIEW_CONFLICT)
+                        .add_modifier(Modifier::BOLD)
+                } else if preview.is_some_and(|p| p.unapplied_oids.contains(&commit.oid)) {
+                    Style::new().fg(COLOR_PREVIEW_UNAPPLIED)
+                } else if let Some(source_idx) = squash_source_idx {
+                    // SquashSelect mode: color by relation to squash source.
+                    if is_squash_source {
+                        Style::new().fg(Color::White)
+                    } else if commit_idx_in_fragmap > source_idx {
+                        Style::new().fg(Color::DarkGray)
+                    } else if is_synthetic {
+                        Style::new().fg(COLOR_SYNTHETIC_LABEL)
+                    } else if let Some(ref fm) = app.fragmap {
+                        commit_text_style(fm, source_idx, commit_idx_in_fragmap)
+                    } else {
+                        Style::default()
+                    }
+                } else if !is_selected {
+                    // Normal CommitList mode coloring for non-selected rows.
+                    if is_synthetic {
+                        Style::new().fg(COLOR_SYNTHETIC_LABEL)
+                    } else if let Some(ref fm) = app.fragmap {
+                        commit_text_style(fm, app.selection_index, commit_idx_in_fragmap)
+                    } else {
+                        Style::default()
+                    }
                 } else {
                     Style::default()
-                }
-            } else if !is_selected {
-                // Normal CommitList mode coloring for non-selected rows.
-                if is_synthetic {
-                    Style::new().fg(COLOR_SYNTHETIC_LABEL)
-                } else if let Some(ref fm) = app.fragmap {
-                    commit_text_style(fm, app.selection_index, commit_idx_in_fragmap)
-                } else {
-                    Style::default()
-                }
-            } else {
-                Style::default()
-            };
+                };
 
             // Apply highlight: source gets cyan bg, selection gets reversed,
             // in squash mode the selected target gets a subtle bg tint.
@@ -582,10 +719,63 @@ fn build_rows<'a>(app: &AppState, layout: &LayoutInfo) -> Vec<Row<'a>> {
                 text_style
             };
 
-            let mut cells = vec![
-                Cell::from(Span::styled(short_sha, text_cell_style)),
-                Cell::from(Span::styled(commit.summary.clone(), text_cell_style)),
-            ];
+            let sha_style = if app.marked_oid.as_ref() == Some(&commit.oid) {
+                text_cell_style.bg(COLOR_MARKED_BG)
+            } else {
+                text_cell_style
+            };
+            let mut sha_spans = vec![Span::styled(short_sha, sha_style)];
+            // Upstream takes precedence: such a commit can simply be dropped.
+            if app.upstreamed_oids.contains(&commit.oid) {
+                sha_spans.push(Span::styled(
+                    UPSTREAMED_MARKER,
+                    text_cell_style.fg(COLOR_UPSTREAMED),
+                ));
+            } else if app.pushed_oids.contains(&commit.oid) {
+                sha_spans.push(Span::styled(
+                    PUSHED_MARKER,
+                    text_cell_style.fg(COLOR_PUSHED),
+                ));
+            }
+            if app.lint_warnings.contains_key(&commit.oid) {
+                if sha_spans.len() == 1 {
+                    sha_spans.push(Span::styled(" ", text_cell_style));
+                }
+                sha_spans.push(Span::styled(LINT_MARKER, text_cell_style.fg(COLOR_LINT)));
+            }
+
+            // In sequence editor mode the title is prefixed with the todo
+            // command; dropped commits are struck through. An edit session
+            // prefixes the commits it melds or rewords.
+            let session_change = app.session.as_ref().and_then(|s| s.change(&commit.oid));
+            let title = match app.todo_commands.get(&commit.oid) {
+                Some(&command) => {
+                    let (command_style, summary_style) = if command == TodoCommand::Drop {
+                        let dropped = text_cell_style
+                            .fg(COLOR_DROPPED)
+                            .add_modifier(Modifier::CROSSED_OUT);
+                        (text_cell_style.fg(COLOR_DROPPED), dropped)
+                    } else {
+                        (text_cell_style.fg(COLOR_TODO_COMMAND), text_cell_style)
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("{:<7}", command.name()), command_style),
+                        Span::styled(commit.summary.clone(), summary_style),
+                    ])
+                }
+                None => match session_change {
+                    Some(change) => Line::from(vec![
+                        Span::styled(
+                            format!("{change:<7}"),
+                            text_cell_style.fg(COLOR_TODO_COMMAND),
+                        ),
+                        Span::styled(commit.summary.clone(), text_cell_style),
+                    ]),
+                    None => Line::from(Span::styled(commit.summary.clone(), text_cell_style)),
+                },
+            };
+
+            let mut cells = vec![Cell::from(Line::from(sha_spans)), Cell::from(title)];
 
             if let Some(ref fragmap) = app.fragmap {
                 if !layout.display_clusters.is_empty() {
@@ -625,18 +815,80 @@ fn render_footer(frame: &mut Frame, app: &AppState, area: Rect) {
         return;
     }
 
+    if let AppMode::Preview(state) = &app.mode {
+        render_preview_footer(frame, app, area, state);
+        return;
+    }
+
+    if !app.todo_commands.is_empty() {
+        let footer =
+            Paragraph::new(Span::styled(SEQUENCE_FOOTER, FOOTER_STYLE)).style(FOOTER_STYLE);
+        frame.render_widget(footer, area);
+        return;
+    }
+
+    if let Some(session) = &app.session {
+        let text = format!(
+            " Session: {} queued \u{b7} Enter apply  u undo  Esc discard  J/K move  s/f/r/d queue",
+            session.ops().len()
+        );
+        let footer =
+            Paragraph::new(Span::styled(text, SESSION_FOOTER_STYLE)).style(SESSION_FOOTER_STYLE);
+        frame.render_widget(footer, area);
+        return;
+    }
+
     let text = if app.commits.is_empty() {
         String::from("No commits")
     } else {
         let commit = &app.commits[app.selection_index];
         let position = app.commits.len() - app.selection_index;
-        format!(" {} {}/{}", commit.oid, position, app.commits.len())
+        let mut text = format!(" {} {}/{}", commit.oid, position, app.commits.len());
+        if !app.reference_name.is_empty() {
+            text.push_str(&format!("  base: {}", app.reference_name));
+            if let Some(merge_base) = &app.fork_point_merge_base {
+                let short: String = merge_base.chars().take(SHORT_SHA_LENGTH).collect();
+                text.push_str(&format!(" (fork point, not merge-base {short})"));
+            }
+        }
+        text
     };
 
     let footer = Paragraph::new(Span::styled(text, FOOTER_STYLE)).style(FOOTER_STYLE);
     frame.render_widget(footer, area);
 }
 
+const PREVIEW_FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Magenta);
+const PREVIEW_CONFLICT_FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Red);
+
+/// Footer of the preview: what was simulated and whether it would stop.
+fn render_preview_footer(frame: &mut Frame, app: &AppState, area: Rect, state: &PreviewState) {
+    let (outcome, style) = match &state.conflicting_oid {
+        Some(oid) => {
+            let short: String = oid.chars().take(SHORT_SHA_LENGTH).collect();
+            (
+                format!(
+                    "conflicts at {short} ({})",
+                    state.conflicting_files.join(", ")
+                ),
+                PREVIEW_CONFLICT_FOOTER_STYLE,
+            )
+        }
+        None => (
+            format!("{} commit(s), no conflicts", app.commits.len()),
+            PREVIEW_FOOTER_STYLE,
+        ),
+    };
+    let text = format!(" Preview {}: {outcome} \u{b7} Esc back", state.label);
+    let footer = Paragraph::new(Span::styled(text, style)).style(style);
+    frame.render_widget(footer, area);
+}
+
+const SESSION_FOOTER_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+const SEQUENCE_FOOTER: &str =
+    " Enter write todo  Esc abort rebase  J/K move  p/r/e/s/f/d set command";
+
 const SQUASH_FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Cyan);
 const SQUASH_FOOTER_ACCENT: Style = Style::new().fg(Color::Gray).bg(Color::Cyan);
 
@@ -662,7 +914,10 @@ fn render_squash_footer(
 
     let max_summary_len = (area.width as usize)
         .saturating_sub(
-            format!(" {label}  \"\" into\u{2026} \u{b7} Enter confirm \u{b7} Esc cancel").len(),
+            format!(
+                " {label}  \"\" into\u{2026} \u{b7} Enter confirm \u{b7} P preview \u{b7} Esc cancel"
+            )
+            .len(),
         )
         .saturating_sub(short_oid.len());
 
@@ -672,16 +927,22 @@ fn render_squash_footer(
         source.summary.clone()
     };
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(format!(" {label} "), SQUASH_FOOTER_STYLE),
         Span::styled(short_oid, SQUASH_FOOTER_ACCENT),
         Span::styled(format!(" \"{summary}\" into\u{2026}"), SQUASH_FOOTER_STYLE),
         Span::styled(" \u{b7} ", SQUASH_FOOTER_STYLE),
         Span::styled("Enter", SQUASH_FOOTER_ACCENT),
         Span::styled(" confirm \u{b7} ", SQUASH_FOOTER_STYLE),
-        Span::styled("Esc", SQUASH_FOOTER_ACCENT),
-        Span::styled(" cancel", SQUASH_FOOTER_STYLE),
-    ]);
+    ];
+    // An edit session only queues the squash, so there is nothing to preview.
+    if app.session.is_none() {
+        spans.push(Span::styled("P", SQUASH_FOOTER_ACCENT));
+        spans.push(Span::styled(" preview \u{b7} ", SQUASH_FOOTER_STYLE));
+    }
+    spans.push(Span::styled("Esc", SQUASH_FOOTER_ACCENT));
+    spans.push(Span::styled(" cancel", SQUASH_FOOTER_STYLE));
+    let line = Line::from(spans);
 
     let footer = Paragraph::new(line).style(SQUASH_FOOTER_STYLE);
     frame.render_widget(footer, area);
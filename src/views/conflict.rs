@@ -17,12 +17,15 @@
 use super::dialog::{inner_width, render_centered_dialog, wrap_text};
 use crate::app::{AppAction, AppMode, AppState};
 use crate::event::KeyCommand;
+use crate::repo::{ConflictKind, ConflictSide, ConflictState, PlanStep};
+use crate::CommitInfo;
 use ratatui::{
     layout::Alignment,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     Frame,
 };
+use std::path::Path;
 
 /// Handle an action while in RebaseConflict mode.
 pub fn handle_conflict_key(action: KeyCommand, app: &mut AppState) -> AppAction {
@@ -36,10 +39,67 @@ pub fn handle_conflict_key(action: KeyCommand, app: &mut AppState) -> AppAction
                 AppAction::Handled
             }
         }
+        // 's' drops the conflicting commit and goes on, like `git rebase --skip`.
+        KeyCommand::Squash => {
+            if let AppMode::RebaseConflict(state) =
+                std::mem::replace(&mut app.mode, AppMode::CommitList)
+            {
+                AppAction::RebaseSkip(state)
+            } else {
+                AppAction::Handled
+            }
+        }
+        // 'r' resolves the first conflicting file in place.
+        KeyCommand::Reword => {
+            if let AppMode::RebaseConflict(ref state) = app.mode {
+                if state.conflicting_files.is_empty() {
+                    app.set_error_message("No conflicting files to resolve");
+                    AppAction::Handled
+                } else {
+                    AppAction::PrepareConflictResolve(state.clone())
+                }
+            } else {
+                AppAction::Handled
+            }
+        }
+        // 'o' / 't' take one side of the first file that is not text.
+        KeyCommand::TakeOurs | KeyCommand::TakeTheirs => {
+            let AppMode::RebaseConflict(ref state) = app.mode else {
+                return AppAction::Handled;
+            };
+            match state.choice_file() {
+                Some(path) => AppAction::TakeConflictSide {
+                    path: path.to_string(),
+                    side: if action == KeyCommand::TakeOurs {
+                        ConflictSide::Ours
+                    } else {
+                        ConflictSide::Theirs
+                    },
+                    conflict_state: state.clone(),
+                },
+                None => AppAction::Handled,
+            }
+        }
+        KeyCommand::RetryStrategy => {
+            let stopped = matches!(&app.mode, AppMode::RebaseConflict(state)
+                if state.conflicting_files.is_empty());
+            if stopped {
+                app.set_error_message("No conflicting files to retry");
+            } else {
+                app.enter_strategy_select();
+            }
+            AppAction::Handled
+        }
         KeyCommand::Mergetool => {
             if let AppMode::RebaseConflict(ref state) = app.mode {
+                // Files that are not text are resolved by picking a side.
                 AppAction::RunMergetool {
-                    files: state.conflicting_files.clone(),
+                    files: state
+                        .conflicting_files
+                        .iter()
+                        .filter(|path| state.kind(path).is_text())
+                        .cloned()
+                        .collect(),
                     conflict_state: state.clone(),
                 }
             } else {
@@ -83,32 +143,74 @@ pub fn render_conflict(app: &AppState, frame: &mut Frame) {
     let label = &state.operation_label;
     let label_lower = label.to_lowercase();
 
-    // Look up the commit summary from the loaded commit list so the user can
-    // see which commit is conflicting without having to remember the OID.
-    let commit_summary = app
-        .commits
-        .iter()
-        .find(|c| c.oid == state.conflicting_commit_oid)
-        .map(|c| c.summary.as_str())
-        .unwrap_or("");
+    let commit_summary = state.conflicting_commit_summary.as_str();
 
     const PREFERRED_WIDTH: u16 = 62;
     let iw = inner_width(PREFERRED_WIDTH, frame.area().width);
 
     let remaining = state.remaining_oids.len();
 
-    let mut lines: Vec<Line> = vec![
-        Line::from(""),
-        Line::from(Span::styled(
+    // A plan can also stop on an `edit` or a failed `exec` line, which are
+    // not merge conflicts.
+    let plan_step = state.plan_context.as_ref().map(|c| &c.step);
+    let stopped = matches!(plan_step, Some(PlanStep::Stop | PlanStep::Exec(_)));
+    let files_heading = if stopped {
+        " Unstaged changes (stage them to amend):"
+    } else {
+        " Conflicting files:"
+    };
+    let (heading, commit_prefix, instruction) = match plan_step {
+        Some(PlanStep::Stop) => (
+            " Stopped for editing".to_string(),
+            " Stopped at ",
+            " Amend the commit or add commits, then:",
+        ),
+        Some(PlanStep::Exec(command)) => (
+            format!(" Command failed: {command}"),
+            " Last commit ",
+            " Fix the problem in your working tree, then:",
+        ),
+        Some(_) => (
+            format!(" Merge conflict during {label_lower}"),
+            " Conflict in ",
+            " Resolve conflicts in your working tree, then:",
+        ),
+        // Squashing first applies the squashed commit itself; everything
+        // else only replays the commits after the one changed.
+        None if state.is_squash_tree_conflict() => (
             format!(" Merge conflict during {label_lower}"),
+            " Conflict in squashed commit ",
+            " Resolve conflicts in your working tree, then:",
+        ),
+        None => (
+            format!(" Merge conflict during {label_lower}"),
+            " Conflict in descendant ",
+            " Resolve conflicts in your working tree, then:",
+        ),
+    };
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    for chunk in wrap_text(&heading, iw) {
+        lines.push(Line::from(Span::styled(
+            chunk,
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(vec![
-            Span::raw(" Conflict in "),
+        )));
+    }
+    if let Some(failure) = state.plan_context.as_ref().and_then(|c| c.failure.as_ref()) {
+        for chunk in wrap_text(&format!(" {failure}"), iw) {
+            lines.push(Line::from(Span::styled(
+                chunk,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+    lines.push(Line::from(""));
+    if !short_oid.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw(commit_prefix),
             Span::styled(short_oid, Style::default().fg(Color::Cyan)),
-        ]),
-    ];
+        ]));
+    }
 
     if !commit_summary.is_empty() {
         for chunk in wrap_text(commit_summary, iw.saturating_sub(1)) {
@@ -126,23 +228,71 @@ pub fn render_conflict(app: &AppState, frame: &mut Frame) {
     if !state.conflicting_files.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            " Conflicting files:",
+            files_heading,
             Style::default().fg(Color::Yellow),
         )));
         const MAX_FILES: usize = 5;
         let shown = state.conflicting_files.len().min(MAX_FILES);
         for path in &state.conflicting_files[..shown] {
-            let truncated = if path.len() + 3 > iw {
-                format!(" \u{2026}{}", &path[path.len().saturating_sub(iw - 3)..])
+            // Plain content conflicts need no label.
+            let kind = state.kind(path);
+            let label = match kind {
+                ConflictKind::Content => String::new(),
+                _ if stopped => String::new(),
+                _ => format!(" ({})", kind.label()),
+            };
+            let room = iw.saturating_sub(label.len());
+            let truncated = if path.len() + 3 > room {
+                format!(
+                    " \u{2026}{}",
+                    &path[path.len().saturating_sub(room.saturating_sub(3))..]
+                )
             } else {
                 format!(" {path}")
             };
+            lines.push(Line::from(vec![
+                Span::styled(truncated, Style::default().fg(Color::Red)),
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        let extra = state.conflicting_files.len().saturating_sub(MAX_FILES);
+        if extra > 0 {
             lines.push(Line::from(Span::styled(
-                truncated,
-                Style::default().fg(Color::Red),
+                format!(" ... {extra} more"),
+                Style::default().fg(Color::DarkGray),
             )));
         }
-        let extra = state.conflicting_files.len().saturating_sub(MAX_FILES);
+    }
+
+    if !state.reused_resolutions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Resolved as recorded before (review them):",
+            Style::default().fg(Color::Green),
+        )));
+        for path in &state.reused_resolutions {
+            lines.push(Line::from(Span::raw(format!(" {path}"))));
+        }
+    }
+
+    let colliding = colliding_commits(app, state);
+    if !colliding.is_empty() && !stopped {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Earlier commits changing the same code:",
+            Style::default().fg(Color::Yellow),
+        )));
+        const MAX_COMMITS: usize = 3;
+        for commit in colliding.iter().take(MAX_COMMITS) {
+            let short = &commit.oid[..commit.oid.len().min(10)];
+            let width = iw.saturating_sub(short.len() + 2);
+            let summary: String = commit.summary.chars().take(width).collect();
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {short}"), Style::default().fg(Color::Cyan)),
+                Span::raw(format!(" {summary}")),
+            ]));
+        }
+        let extra = colliding.len().saturating_sub(MAX_COMMITS);
         if extra > 0 {
             lines.push(Line::from(Span::styled(
                 format!(" ... {extra} more"),
@@ -159,23 +309,87 @@ pub fn render_conflict(app: &AppState, frame: &mut Frame) {
         )));
         lines.push(Line::from(""));
     }
-    lines.push(Line::from(Span::raw(
-        " Resolve conflicts in your working tree, then:",
-    )));
+    lines.push(Line::from(Span::raw(instruction)));
     lines.push(Line::from(""));
-    lines.push(
-        Line::from(vec![
-            Span::styled("Enter ", Style::default().fg(Color::Green)),
-            Span::raw("Continue   "),
-            Span::styled("m ", Style::default().fg(Color::Cyan)),
-            Span::raw("Mergetool   "),
-            Span::styled("Esc ", Style::default().fg(Color::Red)),
-            Span::raw(format!("Abort entire {label_lower}")),
-        ])
-        .alignment(Alignment::Center),
-    );
+    if let Some(path) = state.choice_file().filter(|_| !stopped) {
+        let kind = state.kind(path);
+        let name = Path::new(path)
+            .file_name()
+            .map_or(path.into(), |name| name.to_string_lossy());
+        lines.push(
+            Line::from(vec![
+                Span::raw(format!("{name}: ")),
+                Span::styled("o ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{}   ", kind.choice(ConflictSide::Ours))),
+                Span::styled("t ", Style::default().fg(Color::Cyan)),
+                Span::raw(kind.choice(ConflictSide::Theirs)),
+            ])
+            .alignment(Alignment::Center),
+        );
+    }
+    let mut keys = vec![
+        Span::styled("Enter ", Style::default().fg(Color::Green)),
+        Span::raw("Continue   "),
+    ];
+    let abort = [
+        Span::styled("Esc ", Style::default().fg(Color::Red)),
+        Span::raw(format!("Abort entire {label_lower}")),
+    ];
+    // The squash itself has no commit to skip.
+    let can_skip = state.squash_context.is_none();
+    if !state.conflicting_files.is_empty() && !stopped {
+        // Too many keys for one line; abort goes on its own.
+        keys.push(Span::styled("r ", Style::default().fg(Color::Cyan)));
+        keys.push(Span::raw("Resolve here   "));
+        keys.push(Span::styled("m ", Style::default().fg(Color::Cyan)));
+        keys.push(Span::raw("Mergetool"));
+        lines.push(Line::from(keys).alignment(Alignment::Center));
+        let mut second = vec![
+            Span::styled("x ", Style::default().fg(Color::Cyan)),
+            Span::raw("Strategy   "),
+        ];
+        if can_skip {
+            second.push(Span::styled("s ", Style::default().fg(Color::Cyan)));
+            second.push(Span::raw("Skip   "));
+        }
+        second.extend(abort);
+        lines.push(Line::from(second).alignment(Alignment::Center));
+    } else {
+        keys.push(Span::styled("m ", Style::default().fg(Color::Cyan)));
+        keys.push(Span::raw("Mergetool"));
+        if can_skip {
+            keys.push(Span::raw("   "));
+            keys.push(Span::styled("s ", Style::default().fg(Color::Cyan)));
+            keys.push(Span::raw("Skip"));
+        }
+        lines.push(Line::from(keys).alignment(Alignment::Center));
+        lines.push(Line::from(abort.to_vec()).alignment(Alignment::Center));
+    }
     lines.push(Line::from(""));
 
-    let title = format!(" {label} Conflict ");
+    let title = if stopped {
+        format!(" {label} Stopped ")
+    } else {
+        format!(" {label} Conflict ")
+    };
     render_centered_dialog(frame, &title, Color::Red, PREFERRED_WIDTH, lines);
 }
+
+/// Commits before the conflicting one that touch the conflict's fragmap
+/// clusters, oldest first.
+fn colliding_commits<'a>(app: &'a AppState, state: &ConflictState) -> Vec<&'a CommitInfo> {
+    let conflicting = app
+        .commits
+        .iter()
+        .position(|c| c.oid == state.conflicting_commit_oid)
+        .unwrap_or(app.commits.len());
+    app.commits[..conflicting]
+        .iter()
+        .filter(|commit| {
+            state
+                .conflict_clusters
+                .iter()
+                .any(|cluster| cluster.commit_oids.contains(&commit.oid))
+        })
+        .collect()
+}
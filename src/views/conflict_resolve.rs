@@ -0,0 +1,248 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Conflict resolution view: pick ours, theirs or both per conflict region
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{AppAction, AppMode, AppState},
+    event::KeyCommand,
+    merge3::{ConflictRegion, MergeChunk, Resolution},
+};
+
+const HEADER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Green);
+const FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Blue);
+const CONTEXT_STYLE: Style = Style::new().fg(Color::DarkGray);
+const OURS_STYLE: Style = Style::new().fg(Color::Green);
+const BASE_STYLE: Style = Style::new().fg(Color::Yellow);
+const THEIRS_STYLE: Style = Style::new().fg(Color::Cyan);
+
+/// Unchanged lines shown around the selected region.
+const CONTEXT_LINES: usize = 3;
+
+/// Handle an action while resolving conflict regions.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    let AppMode::ConflictResolve(state) = &mut app.mode else {
+        return AppAction::Handled;
+    };
+    let resolution = match action {
+        KeyCommand::MoveUp => {
+            state.selected = state.selected.saturating_sub(1);
+            return AppAction::Handled;
+        }
+        KeyCommand::MoveDown => {
+            if state.selected + 1 < state.region_count() {
+                state.selected += 1;
+            }
+            return AppAction::Handled;
+        }
+        KeyCommand::TakeOurs => Resolution::Ours,
+        KeyCommand::TakeTheirs => Resolution::Theirs,
+        KeyCommand::TakeBoth => Resolution::Both,
+        // 'e' edits the region in the editor.
+        KeyCommand::EditPlan => return AppAction::EditConflictRegion,
+        KeyCommand::Confirm => {
+            return match state.unresolved_count() {
+                0 => AppAction::WriteResolution,
+                n => {
+                    app.set_error_message(format!("{n} conflict(s) still unresolved"));
+                    AppAction::Handled
+                }
+            };
+        }
+        KeyCommand::Quit => {
+            let conflict = state.conflict.clone();
+            app.enter_rebase_conflict(conflict);
+            return AppAction::Handled;
+        }
+        _ => return AppAction::Handled,
+    };
+    if let Some(region) = state.selected_region_mut() {
+        region.resolution = Some(resolution);
+    }
+    // Move on to the next region, like working through a list.
+    if state.selected + 1 < state.region_count() {
+        state.selected += 1;
+    }
+    AppAction::Handled
+}
+
+/// Render the selected conflict region with ours, base and theirs side by
+/// side, and what it currently resolves to below.
+pub fn render(app: &AppState, frame: &mut Frame) {
+    let AppMode::ConflictResolve(state) = &app.mode else {
+        return;
+    };
+
+    let area = frame.area();
+    let [header_area, sides_area, result_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length((area.height / 3).max(3)),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    let header = Paragraph::new(format!(
+        " Resolve {} \u{b7} conflict {}/{} \u{b7} {} unresolved",
+        state.path,
+        state.selected + 1,
+        state.region_count(),
+        state.unresolved_count(),
+    ))
+    .style(HEADER_STYLE);
+    frame.render_widget(header, header_area);
+
+    let Some(index) = state.selected_chunk() else {
+        return;
+    };
+    let MergeChunk::Conflict(region) = &state.chunks[index] else {
+        return;
+    };
+    let before = match index.checked_sub(1).map(|i| &state.chunks[i]) {
+        Some(MergeChunk::Resolved(lines)) => &lines[lines.len().saturating_sub(CONTEXT_LINES)..],
+        _ => &[],
+    };
+    let after = match state.chunks.get(index + 1) {
+        Some(MergeChunk::Resolved(lines)) => &lines[..lines.len().min(CONTEXT_LINES)],
+        _ => &[],
+    };
+
+    // The side(s) the region currently resolves to.
+    let chosen = |side: Resolution| match &region.resolution {
+        Some(Resolution::Both) => true,
+        Some(resolution) => *resolution == side,
+        None => false,
+    };
+    let [ours_area, base_area, theirs_area] = Layout::horizontal([
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ])
+    .areas(sides_area);
+    render_side(
+        frame,
+        ours_area,
+        "Ours",
+        chosen(Resolution::Ours),
+        (before, &region.ours, after),
+        OURS_STYLE,
+    );
+    render_side(
+        frame,
+        base_area,
+        "Base",
+        false,
+        (before, &region.base, after),
+        BASE_STYLE,
+    );
+    render_side(
+        frame,
+        theirs_area,
+        "Theirs",
+        chosen(Resolution::Theirs),
+        (before, &region.theirs, after),
+        THEIRS_STYLE,
+    );
+
+    render_result(frame, result_area, region);
+
+    let footer = match &app.status_message {
+        Some(message) => {
+            let bg = if app.status_is_error {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            Paragraph::new(format!(" {message}")).style(Style::new().fg(Color::White).bg(bg))
+        }
+        None => Paragraph::new(
+            " o ours \u{b7} t theirs \u{b7} b both \u{b7} e edit \u{b7} \u{2191}/\u{2193} conflict \u{b7} Enter stage \u{b7} Esc back",
+        )
+        .style(FOOTER_STYLE),
+    };
+    frame.render_widget(footer, footer_area);
+}
+
+fn render_side(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    chosen: bool,
+    (before, region, after): (&[String], &[String], &[String]),
+    style: Style,
+) {
+    let mut lines: Vec<Line> = before.iter().map(|l| context_line(l)).collect();
+    lines.extend(
+        region
+            .iter()
+            .map(|l| Line::from(Span::styled(display(l), style))),
+    );
+    lines.extend(after.iter().map(|l| context_line(l)));
+
+    let title = if chosen {
+        format!(" {title} \u{2713} ")
+    } else {
+        format!(" {title} ")
+    };
+    let border_style = if chosen {
+        style.add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(title);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_result(frame: &mut Frame, area: Rect, region: &ConflictRegion) {
+    let (title, lines) = match region.resolved_lines() {
+        Some(resolved) => {
+            let label = match region.resolution {
+                Some(Resolution::Ours) => "ours",
+                Some(Resolution::Theirs) => "theirs",
+                Some(Resolution::Both) => "both",
+                _ => "edited",
+            };
+            let lines: Vec<Line> = resolved.iter().map(|l| Line::from(display(l))).collect();
+            (format!(" Result ({label}) "), lines)
+        }
+        None => (
+            " Result ".to_string(),
+            vec![Line::from(Span::styled(
+                "Unresolved \u{2014} press o, t, b or e",
+                CONTEXT_STYLE,
+            ))],
+        ),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn context_line(line: &str) -> Line<'static> {
+    Line::from(Span::styled(display(line), CONTEXT_STYLE))
+}
+
+fn display(line: &str) -> String {
+    line.trim_end_matches(['\n', '\r']).replace('\t', "    ")
+}
@@ -15,7 +15,7 @@
 // Drop commit confirmation dialog
 
 use super::dialog::{inner_width, render_centered_dialog, wrap_text};
-use crate::app::{AppAction, AppMode, AppState};
+use crate::app::{AppAction, AppMode, AppState, PreviewOp};
 use crate::event::KeyCommand;
 use ratatui::{
     layout::Alignment,
@@ -39,6 +39,13 @@ pub fn handle_confirm_key(action: KeyCommand, app: &mut AppState) -> AppAction {
                 AppAction::Handled
             }
         }
+        KeyCommand::Preview => match &app.mode {
+            AppMode::DropConfirm(pending) => AppAction::Preview(PreviewOp::Drop {
+                commit_oid: pending.commit_oid.clone(),
+                head_oid: pending.head_oid.clone(),
+            }),
+            _ => AppAction::Handled,
+        },
         KeyCommand::ShowHelp => {
             app.toggle_help();
             AppAction::Handled
@@ -93,6 +100,8 @@ pub fn render_drop_confirm(app: &AppState, frame: &mut Frame) {
         Line::from(vec![
             Span::styled("Enter ", Style::default().fg(Color::Cyan)),
             Span::raw("Confirm   "),
+            Span::styled("P ", Style::default().fg(Color::Cyan)),
+            Span::raw("Preview   "),
             Span::styled("Esc ", Style::default().fg(Color::Cyan)),
             Span::raw("Cancel"),
         ])
@@ -108,3 +117,93 @@ pub fn render_drop_confirm(app: &AppState, frame: &mut Frame) {
         lines,
     );
 }
+
+/// Handle an action while in DropUpstreamedConfirm mode.
+pub fn handle_upstreamed_confirm_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    match action {
+        KeyCommand::Confirm => {
+            if let AppMode::DropUpstreamedConfirm(pending) =
+                std::mem::replace(&mut app.mode, AppMode::CommitList)
+            {
+                AppAction::ExecuteDropUpstreamed {
+                    commit_oids: pending.commit_oids,
+                    head_oid: pending.head_oid,
+                }
+            } else {
+                AppAction::Handled
+            }
+        }
+        KeyCommand::Preview => match &app.mode {
+            AppMode::DropUpstreamedConfirm(pending) => AppAction::Preview(PreviewOp::DropMany {
+                commit_oids: pending.commit_oids.clone(),
+                head_oid: pending.head_oid.clone(),
+            }),
+            _ => AppAction::Handled,
+        },
+        KeyCommand::ShowHelp => {
+            app.toggle_help();
+            AppAction::Handled
+        }
+        KeyCommand::Quit => {
+            app.cancel_drop_confirm();
+            AppAction::Handled
+        }
+        _ => AppAction::Handled,
+    }
+}
+
+/// Render the confirmation dialog for dropping every commit that is already
+/// present upstream, listing each of them.
+pub fn render_drop_upstreamed_confirm(app: &AppState, frame: &mut Frame) {
+    let pending = match &app.mode {
+        AppMode::DropUpstreamedConfirm(p) => p,
+        _ => return,
+    };
+
+    const PREFERRED_WIDTH: u16 = 70;
+    let iw = inner_width(PREFERRED_WIDTH, frame.area().width);
+
+    let count = pending.commit_oids.len();
+    let noun = if count == 1 { "commit" } else { "commits" };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" Drop {count} {noun} already present upstream?"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for (oid, summary) in pending.commit_oids.iter().zip(&pending.commit_summaries) {
+        let short_oid = if oid.len() >= 10 { &oid[..10] } else { oid };
+        let max_summary = iw.saturating_sub(short_oid.len() + 2);
+        let summary: String = summary.chars().take(max_summary).collect();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {short_oid}"), Style::default().fg(Color::Cyan)),
+            Span::raw(format!(" {summary}")),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from(vec![
+            Span::styled("Enter ", Style::default().fg(Color::Cyan)),
+            Span::raw("Confirm   "),
+            Span::styled("P ", Style::default().fg(Color::Cyan)),
+            Span::raw("Preview   "),
+            Span::styled("Esc ", Style::default().fg(Color::Cyan)),
+            Span::raw("Cancel"),
+        ])
+        .alignment(Alignment::Center),
+    );
+    lines.push(Line::from(""));
+
+    render_centered_dialog(
+        frame,
+        " Confirm Drop Upstreamed ",
+        Color::Yellow,
+        PREFERRED_WIDTH,
+        lines,
+    );
+}
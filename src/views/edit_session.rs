@@ -0,0 +1,84 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Edit session mode — key handling only; the virtual stack is rendered by
+// the commit list (see `render_footer` in commit_list.rs).
+
+use crate::{
+    app::{AppAction, AppState},
+    event::KeyCommand,
+    session::SessionOp,
+};
+
+/// Handle an action while in an edit session.
+///
+/// Navigation works like the commit list. The operation keys queue their
+/// operation on the virtual stack instead of rewriting the branch; Enter
+/// applies everything in one rebase and Esc discards the session.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    // Dropping every commit leaves nothing selected.
+    let selected = app
+        .commits
+        .get(app.selection_index)
+        .map(|c| (c.oid.clone(), c.message.clone()));
+    let queued = match (action, selected) {
+        (KeyCommand::MoveCommitUp | KeyCommand::MoveCommitDown, Some((oid, _))) => {
+            // Up is towards HEAD when the list is shown reversed.
+            let newer = (action == KeyCommand::MoveCommitDown) != app.reverse;
+            let neighbour = if newer {
+                app.selection_index + 1
+            } else {
+                app.selection_index.wrapping_sub(1)
+            };
+            neighbour < app.commits.len() && app.queue_session_op(SessionOp::Move { oid, newer })
+        }
+        (KeyCommand::Drop, Some((oid, _))) => app.queue_session_op(SessionOp::Drop { oid }),
+        (KeyCommand::Reword, Some((commit_oid, current_message))) => {
+            return AppAction::QueueReword {
+                commit_oid,
+                current_message,
+            }
+        }
+        (KeyCommand::Squash, Some(_)) => {
+            app.enter_squash_select();
+            false
+        }
+        (KeyCommand::Fixup, Some(_)) => {
+            app.enter_fixup_select();
+            false
+        }
+        (KeyCommand::Update, _) => app.undo_session_op(),
+        (KeyCommand::Confirm, _) => return app.finish_edit_session(),
+        (KeyCommand::Quit, _) => {
+            app.discard_edit_session();
+            false
+        }
+        (
+            KeyCommand::MoveUp
+            | KeyCommand::MoveDown
+            | KeyCommand::PageUp
+            | KeyCommand::PageDown
+            | KeyCommand::ScrollLeft
+            | KeyCommand::ScrollRight
+            | KeyCommand::ShowHelp,
+            _,
+        ) => return super::commit_list::handle_key(action, app),
+        _ => false,
+    };
+    if queued {
+        AppAction::RefreshSession
+    } else {
+        AppAction::Handled
+    }
+}
@@ -15,7 +15,7 @@
 // Help dialog view showing keybindings
 
 use super::dialog::render_centered_dialog;
-use crate::app::AppAction;
+use crate::app::{AppAction, AppState};
 use crate::event::KeyCommand;
 
 use ratatui::{
@@ -36,9 +36,10 @@ pub fn handle_key(action: KeyCommand, app: &mut crate::app::AppState) -> AppActi
 }
 
 /// Render the help dialog as a centered overlay.
-pub fn render(frame: &mut Frame) {
+pub fn render(app: &AppState, frame: &mut Frame) {
     // Build help content first to calculate required size
-    let help_lines = vec![
+    let mut help_lines = base_lines(app);
+    help_lines.extend([
         Line::from(""),
         Line::from(Span::styled(
             " Navigation",
@@ -71,6 +72,22 @@ pub fn render(frame: &mut Frame) {
             Span::styled("   Enter, i  ", Style::default().fg(Color::Cyan)),
             Span::raw("Toggle commit detail view"),
         ]),
+        Line::from(vec![
+            Span::styled("   c         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Compare with commits at startup"),
+        ]),
+        Line::from(vec![
+            Span::styled("   C         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Compare with pushed version"),
+        ]),
+        Line::from(vec![
+            Span::styled("   Space     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Mark commit to compare with"),
+        ]),
+        Line::from(vec![
+            Span::styled("   v         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Open commit (or marked range) in diff tool"),
+        ]),
         Line::from(vec![
             Span::styled("   p         ", Style::default().fg(Color::Cyan)),
             Span::raw("Split commit (choose strategy)"),
@@ -87,14 +104,54 @@ pub fn render(frame: &mut Frame) {
             Span::styled("   r         ", Style::default().fg(Color::Cyan)),
             Span::raw("Reword commit message"),
         ]),
+        Line::from(vec![
+            Span::styled("   R         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Reword all commit messages in one editor"),
+        ]),
+        Line::from(vec![
+            Span::styled("   a         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Edit author, date and trailers"),
+        ]),
         Line::from(vec![
             Span::styled("   d         ", Style::default().fg(Color::Cyan)),
             Span::raw("Drop commit"),
         ]),
+        Line::from(vec![
+            Span::styled("   D         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Drop all commits marked = (upstream)"),
+        ]),
+        Line::from(vec![
+            Span::styled("   e         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Edit the stack as a rebase todo list"),
+        ]),
+        Line::from(vec![
+            Span::styled("   B         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Queue J/K, s, f, r, d in a session; Enter applies, u undoes"),
+        ]),
         Line::from(vec![
             Span::styled("   m         ", Style::default().fg(Color::Cyan)),
             Span::raw("Launch merge tool (during drop conflict)"),
         ]),
+        Line::from(vec![
+            Span::styled("   r         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Resolve conflicts region by region (during conflict)"),
+        ]),
+        Line::from(vec![
+            Span::styled("   o/t       ", Style::default().fg(Color::Cyan)),
+            Span::raw("Take our/their side of a deleted or binary file"),
+        ]),
+        Line::from(vec![
+            Span::styled("   s         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Skip the conflicting commit (during conflict)"),
+        ]),
+        Line::from(vec![
+            Span::styled("   x         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Retry a conflict with a merge strategy"),
+        ]),
+        Line::from(vec![
+            Span::styled("   P         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Preview the result (in drop, split and squash dialogs)"),
+        ]),
         Line::from(vec![
             Span::styled("   h         ", Style::default().fg(Color::Cyan)),
             Span::raw("Show this help dialog"),
@@ -115,8 +172,46 @@ pub fn render(frame: &mut Frame) {
             Span::styled("   Esc, q    ", Style::default().fg(Color::Cyan)),
             Span::raw("Close dialog / Quit application"),
         ]),
+        Line::from(vec![
+            Span::styled("   =         ", Style::default().fg(Color::Green)),
+            Span::raw("Marks a commit already upstream"),
+        ]),
+        Line::from(vec![
+            Span::styled("   \u{2191}         ", Style::default().fg(Color::Magenta)),
+            Span::raw("Marks a commit already pushed"),
+        ]),
+        Line::from(vec![
+            Span::styled("   !         ", Style::default().fg(Color::Yellow)),
+            Span::raw("Marks a message with lint warnings"),
+        ]),
         Line::from(""),
-    ];
+    ]);
 
     render_centered_dialog(frame, " Help - Keybindings ", Color::White, 48, help_lines);
 }
+
+/// Why the commit list starts where it does, when the fork point used as
+/// base differs from the merge-base.
+fn base_lines(app: &AppState) -> Vec<Line<'static>> {
+    let Some(merge_base) = &app.fork_point_merge_base else {
+        return Vec::new();
+    };
+    let short: String = merge_base.chars().take(8).collect();
+    vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " Base",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "   Fork point of '{}', not its",
+            app.reference_name
+        )),
+        Line::from(format!("   merge-base {short}: the commits between")),
+        Line::from("   them were rewritten upstream and are"),
+        Line::from("   hidden."),
+    ]
+}
@@ -0,0 +1,149 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Metadata editor dialog: author, author date and trailers
+
+use super::dialog::render_centered_dialog;
+use crate::app::{AppAction, AppMode, AppState, MetadataEditState, METADATA_FIXED_ROWS};
+use crate::event::KeyCommand;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+const DIALOG_WIDTH: u16 = 72;
+const LABEL_WIDTH: usize = 10;
+
+/// Handle an action while in MetadataEdit mode. Keys arrive through
+/// `event::parse_text_key`, so letters are text rather than commands.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    let AppMode::MetadataEdit(state) = &mut app.mode else {
+        return AppAction::Handled;
+    };
+    match action {
+        KeyCommand::InsertChar(c) => state.insert_char(c),
+        KeyCommand::DeleteBackward => state.delete_backward(),
+        KeyCommand::DeleteForward => state.delete_forward(),
+        KeyCommand::ScrollLeft => state.move_cursor(-1),
+        KeyCommand::ScrollRight => state.move_cursor(1),
+        KeyCommand::MoveUp => state.move_focus(-1),
+        KeyCommand::MoveDown => state.move_focus(1),
+        KeyCommand::AddField => state.add_trailer(),
+        KeyCommand::RemoveField => state.remove_trailer(),
+        KeyCommand::Complete => state.complete(),
+        KeyCommand::Confirm => match state.to_metadata() {
+            Ok(metadata) => {
+                let commit_oid = state.commit_oid.clone();
+                app.cancel_metadata_edit();
+                return AppAction::ApplyMetadata {
+                    commit_oid,
+                    metadata,
+                };
+            }
+            Err(message) => app.set_error_message(message),
+        },
+        KeyCommand::Quit => app.cancel_metadata_edit(),
+        _ => {}
+    }
+    AppAction::Handled
+}
+
+/// Render the metadata editor as a centered overlay.
+pub fn render(app: &AppState, frame: &mut Frame) {
+    let AppMode::MetadataEdit(state) = &app.mode else {
+        return;
+    };
+
+    let short_oid: String = state.commit_oid.chars().take(10).collect();
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(" Commit "),
+            Span::styled(short_oid, Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(""),
+    ];
+    lines.push(field_line(state, 0, "Author"));
+    lines.push(field_line(state, 1, "Date"));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " Trailers",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )));
+    if state.trailers.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "   (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for i in 0..state.trailers.len() {
+        lines.push(field_line(state, METADATA_FIXED_ROWS + i, ""));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(" Tab ", Style::default().fg(Color::Cyan)),
+        Span::raw("suggest  "),
+        Span::styled("Ctrl+N ", Style::default().fg(Color::Cyan)),
+        Span::raw("add trailer  "),
+        Span::styled("Ctrl+D ", Style::default().fg(Color::Cyan)),
+        Span::raw("remove trailer"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Enter ", Style::default().fg(Color::Green)),
+        Span::raw("Apply   "),
+        Span::styled("Esc ", Style::default().fg(Color::Red)),
+        Span::raw("Cancel"),
+    ]));
+
+    render_centered_dialog(
+        frame,
+        " Edit Commit Metadata ",
+        Color::Cyan,
+        DIALOG_WIDTH,
+        lines,
+    );
+}
+
+/// One editable row; the focused row is highlighted and shows the cursor.
+fn field_line(state: &MetadataEditState, row: usize, label: &str) -> Line<'static> {
+    let text = match row {
+        0 => &state.author,
+        1 => &state.date,
+        n => &state.trailers[n - METADATA_FIXED_ROWS],
+    };
+    let label = Span::styled(
+        format!(" {label:<width$}", width = LABEL_WIDTH),
+        Style::default().fg(Color::Yellow),
+    );
+    if row != state.focus {
+        return Line::from(vec![label, Span::raw(text.clone())]);
+    }
+
+    let focused = Style::default().bg(Color::DarkGray);
+    let before: String = text.chars().take(state.cursor).collect();
+    let at: String = text
+        .chars()
+        .nth(state.cursor)
+        .map_or(" ".into(), String::from);
+    let after: String = text.chars().skip(state.cursor + 1).collect();
+    Line::from(vec![
+        label,
+        Span::styled(before, focused),
+        Span::styled(at, focused.add_modifier(Modifier::REVERSED)),
+        Span::styled(after, focused),
+    ])
+}
@@ -0,0 +1,64 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Preview of a simulated rewrite — key handling only; the simulated commits
+// are rendered by the commit list (see `render_footer` in commit_list.rs).
+
+use crate::app::{AppAction, AppState};
+use crate::event::KeyCommand;
+
+/// Handle an action while in Preview mode.
+///
+/// The simulated stack can be browsed like the commit list; Esc or P goes
+/// back to the dialog the preview was opened from.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    match action {
+        KeyCommand::MoveUp => {
+            if app.reverse {
+                app.move_down();
+            } else {
+                app.move_up();
+            }
+        }
+        KeyCommand::MoveDown => {
+            if app.reverse {
+                app.move_up();
+            } else {
+                app.move_down();
+            }
+        }
+        KeyCommand::PageUp => {
+            let h = app.commit_list_visible_height;
+            if app.reverse {
+                app.page_down(h);
+            } else {
+                app.page_up(h);
+            }
+        }
+        KeyCommand::PageDown => {
+            let h = app.commit_list_visible_height;
+            if app.reverse {
+                app.page_up(h);
+            } else {
+                app.page_down(h);
+            }
+        }
+        KeyCommand::ScrollLeft => app.scroll_fragmap_left(),
+        KeyCommand::ScrollRight => app.scroll_fragmap_right(),
+        KeyCommand::ShowHelp => app.toggle_help(),
+        KeyCommand::Quit | KeyCommand::Preview => app.leave_preview(),
+        _ => {}
+    }
+    AppAction::Handled
+}
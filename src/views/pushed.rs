@@ -0,0 +1,110 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Confirmation dialog before rewriting commits that are already pushed
+
+use super::dialog::{inner_width, render_centered_dialog, wrap_text};
+use crate::app::{AppAction, AppMode, AppState};
+use crate::event::KeyCommand;
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+/// Handle an action while in PushedConfirm mode.
+pub fn handle_confirm_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    match action {
+        KeyCommand::Confirm => {
+            if let AppMode::PushedConfirm(pending) =
+                std::mem::replace(&mut app.mode, AppMode::CommitList)
+            {
+                AppAction::ConfirmedPushed(pending.action)
+            } else {
+                AppAction::Handled
+            }
+        }
+        KeyCommand::ShowHelp => {
+            app.toggle_help();
+            AppAction::Handled
+        }
+        KeyCommand::Quit => {
+            app.cancel_pushed_confirm();
+            AppAction::Handled
+        }
+        _ => AppAction::Handled,
+    }
+}
+
+/// Render the pushed-commit confirmation dialog as a centered overlay.
+pub fn render_pushed_confirm(app: &AppState, frame: &mut Frame) {
+    let pending = match &app.mode {
+        AppMode::PushedConfirm(p) => p,
+        _ => return,
+    };
+
+    const PREFERRED_WIDTH: u16 = 64;
+    let iw = inner_width(PREFERRED_WIDTH, frame.area().width);
+
+    let count = pending.commit_summaries.len();
+    let noun = if count == 1 { "commit" } else { "commits" };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" Rewrite {count} pushed {noun}?"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for summary in &pending.commit_summaries {
+        let summary: String = summary.chars().take(iw.saturating_sub(3)).collect();
+        lines.push(Line::from(vec![
+            Span::styled(" \u{2191} ", Style::default().fg(Color::Magenta)),
+            Span::raw(summary),
+        ]));
+    }
+    lines.push(Line::from(""));
+    for chunk in wrap_text(
+        "Publishing the result requires a force-push, which affects anyone who already fetched these commits.",
+        iw.saturating_sub(1),
+    ) {
+        lines.push(Line::from(Span::styled(
+            format!(" {chunk}"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from(vec![
+            Span::styled("Enter ", Style::default().fg(Color::Cyan)),
+            Span::raw("Rewrite anyway   "),
+            Span::styled("Esc ", Style::default().fg(Color::Cyan)),
+            Span::raw("Cancel"),
+        ])
+        .alignment(Alignment::Center),
+    );
+    lines.push(Line::from(""));
+
+    render_centered_dialog(
+        frame,
+        " Pushed Commits ",
+        Color::Yellow,
+        PREFERRED_WIDTH,
+        lines,
+    );
+}
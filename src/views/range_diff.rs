@@ -0,0 +1,253 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Range-diff view — commits at startup versus the current commits
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::commit_detail::{diff_line_prefix_and_style, diff_lines, render_scrollbar};
+use crate::{
+    app::{AppAction, AppMode, AppState},
+    event::KeyCommand,
+    range_diff::{PairStatus, RangeDiffEntry},
+    repo::GitRepo,
+    CommitInfo, DiffLine, DiffLineKind,
+};
+
+const HEADER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Green);
+const FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Blue);
+const SECTION_STYLE: Style = Style::new().fg(Color::Yellow);
+
+/// Number of characters to display for short SHA.
+const SHORT_SHA_LENGTH: usize = 8;
+
+/// Handle an action while in RangeDiff mode.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    match action {
+        KeyCommand::MoveUp => {
+            app.range_diff_move(-1);
+            AppAction::Handled
+        }
+        KeyCommand::MoveDown => {
+            app.range_diff_move(1);
+            AppAction::Handled
+        }
+        KeyCommand::PageUp => {
+            app.scroll_detail_page_up(app.detail_visible_height);
+            AppAction::Handled
+        }
+        KeyCommand::PageDown => {
+            app.scroll_detail_page_down(app.detail_visible_height);
+            AppAction::Handled
+        }
+        KeyCommand::ShowHelp => {
+            app.toggle_help();
+            AppAction::Handled
+        }
+        KeyCommand::Quit | KeyCommand::RangeDiff => {
+            app.close_range_diff();
+            AppAction::Handled
+        }
+        _ => AppAction::Handled,
+    }
+}
+
+/// Render the range-diff view: the list of paired commits on top and the
+/// interdiff (or full diff for added/removed commits) of the selected entry
+/// below.
+pub fn render(repo: &impl GitRepo, app: &mut AppState, frame: &mut Frame) {
+    let selection = match app.mode {
+        AppMode::RangeDiff { selection } => selection,
+        _ => return,
+    };
+
+    let area = frame.area();
+    let list_height = (app.range_diff.len() as u16)
+        .clamp(1, (area.height / 3).max(1))
+        .min(area.height.saturating_sub(4));
+    let [header_area, list_area, title_area, detail_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(list_height),
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    let header = Paragraph::new(" Range diff: session start \u{2194} current").style(HEADER_STYLE);
+    frame.render_widget(header, header_area);
+
+    render_list(app, selection, frame, list_area);
+
+    let Some(entry) = app.range_diff.get(selection) else {
+        let placeholder = Paragraph::new("No commits").style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(placeholder, detail_area);
+        return;
+    };
+
+    let (title, content) = detail_content(repo, entry);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            title,
+            SECTION_STYLE.add_modifier(Modifier::BOLD),
+        )),
+        title_area,
+    );
+
+    let total_lines = content.len();
+    let visible_height = detail_area.height as usize;
+    let max_scroll = total_lines.saturating_sub(visible_height);
+    app.max_detail_scroll = max_scroll;
+    app.detail_visible_height = visible_height;
+    let scroll_offset = app.detail_scroll_offset.min(max_scroll);
+
+    let scrollbar_width = if max_scroll > 0 { 1 } else { 0 };
+    let scrollbar_area = Rect {
+        width: scrollbar_width,
+        ..detail_area
+    };
+    let text_area = Rect {
+        x: detail_area.x + scrollbar_width,
+        width: detail_area.width.saturating_sub(scrollbar_width),
+        ..detail_area
+    };
+    frame.render_widget(
+        Paragraph::new(content).scroll((scroll_offset as u16, 0)),
+        text_area,
+    );
+    if max_scroll > 0 && visible_height > 0 {
+        render_scrollbar(
+            frame,
+            scrollbar_area,
+            scroll_offset,
+            total_lines,
+            visible_height,
+        );
+    }
+
+    let changed = app
+        .range_diff
+        .iter()
+        .filter(|e| e.status != PairStatus::Same)
+        .count();
+    let footer = Paragraph::new(format!(
+        " {changed} of {} changed \u{b7} \u{2191}/\u{2193} select \u{b7} PgUp/PgDn scroll \u{b7} Esc back",
+        app.range_diff.len()
+    ))
+    .style(FOOTER_STYLE);
+    frame.render_widget(footer, footer_area);
+}
+
+/// Render the `git range-diff`-style list, keeping the selection visible.
+fn render_list(app: &AppState, selection: usize, frame: &mut Frame, area: Rect) {
+    let height = area.height as usize;
+    let scroll = (selection + 1).saturating_sub(height);
+
+    let lines: Vec<Line> = app
+        .range_diff
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(i, entry)| {
+            let line = entry_line(entry);
+            if i == selection {
+                line.reversed()
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Format one entry as `1: abcdef12 ! 1: 12345678 Summary`.
+fn entry_line(entry: &RangeDiffEntry) -> Line<'static> {
+    let side = |commit: &Option<(usize, CommitInfo)>| match commit {
+        Some((idx, info)) => {
+            let sha: String = info.oid.chars().take(SHORT_SHA_LENGTH).collect();
+            format!("{:>2}: {sha}", idx + 1)
+        }
+        None => format!(" -: {}", "-".repeat(SHORT_SHA_LENGTH)),
+    };
+    let (symbol, color) = match entry.status {
+        PairStatus::Same => ("=", Color::Gray),
+        PairStatus::Changed => ("!", Color::Yellow),
+        PairStatus::Removed => ("<", Color::Red),
+        PairStatus::Added => (">", Color::Green),
+    };
+    let summary = entry
+        .new
+        .as_ref()
+        .or(entry.old.as_ref())
+        .map(|(_, c)| c.summary.clone())
+        .unwrap_or_default();
+
+    Line::from(vec![
+        Span::raw(format!(" {} ", side(&entry.old))),
+        Span::styled(symbol, Style::new().fg(color).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(" {} ", side(&entry.new))),
+        Span::styled(summary, Style::new().fg(color)),
+    ])
+}
+
+/// Styled lines for an interdiff, with `~` separators dimmed.
+pub(crate) fn interdiff_lines(interdiff: &[DiffLine]) -> Vec<Line<'static>> {
+    interdiff
+        .iter()
+        .map(|line| {
+            if line.kind == DiffLineKind::Context && line.content == "~" {
+                return Line::from(Span::styled("~", Style::default().fg(Color::DarkGray)));
+            }
+            let (prefix, style) = diff_line_prefix_and_style(line.kind);
+            Line::from(Span::styled(format!("{prefix}{}", line.content), style))
+        })
+        .collect()
+}
+
+/// Title and body lines for the selected entry.
+fn detail_content(repo: &impl GitRepo, entry: &RangeDiffEntry) -> (String, Vec<Line<'static>>) {
+    match entry.status {
+        PairStatus::Same => (
+            " Unchanged".to_string(),
+            vec![Line::from(Span::styled(
+                " Message and patch are identical.",
+                Style::default().fg(Color::DarkGray),
+            ))],
+        ),
+        PairStatus::Changed => (
+            " Interdiff (old \u{2192} new)".to_string(),
+            interdiff_lines(&entry.interdiff),
+        ),
+        PairStatus::Removed | PairStatus::Added => {
+            let (title, commit) = if entry.status == PairStatus::Removed {
+                (" Removed commit", &entry.old)
+            } else {
+                (" Added commit", &entry.new)
+            };
+            let lines = commit
+                .as_ref()
+                .and_then(|(_, c)| repo.commit_diff(&c.oid).ok())
+                .map(|diff| diff_lines(&diff.files))
+                .unwrap_or_default();
+            (title.to_string(), lines)
+        }
+    }
+}
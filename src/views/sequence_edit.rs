@@ -0,0 +1,57 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Sequence editor mode: arrange a `git rebase -i` todo list in the commit list
+
+use crate::{
+    app::{AppAction, AppState},
+    event::KeyCommand,
+    todo::TodoCommand,
+};
+
+/// Handle an action while arranging a todo list. Navigation works like the
+/// commit list; the operation keys set the command of the selected commit.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    let command = match action {
+        KeyCommand::Split => TodoCommand::Pick,
+        KeyCommand::Reword => TodoCommand::Reword,
+        KeyCommand::EditPlan => TodoCommand::Edit,
+        KeyCommand::Squash => TodoCommand::Squash,
+        KeyCommand::Fixup => TodoCommand::Fixup,
+        KeyCommand::Drop => TodoCommand::Drop,
+        KeyCommand::MoveCommitUp | KeyCommand::MoveCommitDown => {
+            // Up is towards HEAD when the list is shown reversed.
+            let newer = (action == KeyCommand::MoveCommitDown) != app.reverse;
+            return if app.move_selected_commit(newer) {
+                AppAction::RefreshFragmap
+            } else {
+                AppAction::Handled
+            };
+        }
+        KeyCommand::Confirm => return AppAction::WriteTodo,
+        KeyCommand::Quit => return AppAction::AbortTodo,
+        KeyCommand::MoveUp
+        | KeyCommand::MoveDown
+        | KeyCommand::PageUp
+        | KeyCommand::PageDown
+        | KeyCommand::ScrollLeft
+        | KeyCommand::ScrollRight
+        | KeyCommand::ShowHelp => return super::commit_list::handle_key(action, app),
+        _ => return AppAction::Handled,
+    };
+    if let Some(commit) = app.commits.get(app.selection_index) {
+        app.todo_commands.insert(commit.oid.clone(), command);
+    }
+    AppAction::Handled
+}
@@ -15,7 +15,7 @@
 // Split strategy selection dialog
 
 use super::dialog::render_centered_dialog;
-use crate::app::{AppAction, AppMode, AppState, SplitStrategy};
+use crate::app::{AppAction, AppMode, AppState, PreviewOp, SplitStrategy};
 use crate::event::KeyCommand;
 use ratatui::{
     layout::Alignment,
@@ -44,6 +44,10 @@ pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
                 commit_oid,
             }
         }
+        KeyCommand::Preview => AppAction::Preview(PreviewOp::Split {
+            strategy: app.selected_split_strategy(),
+            commit_oid: app.commits[app.selection_index].oid.clone(),
+        }),
         KeyCommand::ShowHelp => {
             app.toggle_help();
             AppAction::Handled
@@ -72,6 +76,13 @@ pub fn handle_confirm_key(action: KeyCommand, app: &mut AppState) -> AppAction {
                 AppAction::Handled
             }
         }
+        KeyCommand::Preview => match &app.mode {
+            AppMode::SplitConfirm(pending) => AppAction::Preview(PreviewOp::Split {
+                strategy: pending.strategy,
+                commit_oid: pending.commit_oid.clone(),
+            }),
+            _ => AppAction::Handled,
+        },
         KeyCommand::ShowHelp => {
             app.toggle_help();
             AppAction::Handled
@@ -157,6 +168,8 @@ pub fn render(app: &AppState, frame: &mut Frame) {
         Line::from(vec![
             Span::styled("Enter ", Style::default().fg(Color::Cyan)),
             Span::raw("Select   "),
+            Span::styled("P ", Style::default().fg(Color::Cyan)),
+            Span::raw("Preview   "),
             Span::styled("Esc ", Style::default().fg(Color::Cyan)),
             Span::raw("Cancel"),
         ])
@@ -198,6 +211,8 @@ pub fn render_split_confirm(app: &AppState, frame: &mut Frame) {
         Line::from(vec![
             Span::styled("Enter ", Style::default().fg(Color::Cyan)),
             Span::raw("Confirm   "),
+            Span::styled("P ", Style::default().fg(Color::Cyan)),
+            Span::raw("Preview   "),
             Span::styled("Esc ", Style::default().fg(Color::Cyan)),
             Span::raw("Cancel"),
         ])
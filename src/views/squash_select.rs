@@ -15,8 +15,9 @@
 // Squash target selection — key handling only; rendering is done via the
 // commit list footer (see `render_footer` in commit_list.rs).
 
-use crate::app::{AppAction, AppMode, AppState};
+use crate::app::{AppAction, AppMode, AppState, PreviewOp};
 use crate::event::KeyCommand;
+use crate::session::SessionOp;
 
 /// Handle an action while in SquashSelect mode.
 ///
@@ -73,21 +74,13 @@ pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
             AppAction::Handled
         }
         KeyCommand::Confirm => {
-            let target_index = app.selection_index;
-
-            // Cannot squash onto itself
-            if target_index == source_index {
-                app.set_error_message("Cannot squash a commit into itself");
+            if !valid_target(app, source_index) {
                 return AppAction::Handled;
             }
-
-            // Cannot squash onto staged/unstaged
-            let target = &app.commits[target_index];
-            if target.oid == "staged" || target.oid == "unstaged" {
-                app.set_error_message("Cannot squash into staged/unstaged changes");
-                return AppAction::Handled;
+            if app.session.is_some() {
+                return queue_in_session(app, source_index, is_fixup);
             }
-
+            let target = &app.commits[app.selection_index];
             let source = &app.commits[source_index];
             let result = AppAction::PrepareSquash {
                 source_oid: source.oid.clone(),
@@ -100,6 +93,17 @@ pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
             app.mode = AppMode::CommitList;
             result
         }
+        // A preview would set aside the edit session's stack.
+        KeyCommand::Preview if app.session.is_some() => AppAction::Handled,
+        KeyCommand::Preview => {
+            if !valid_target(app, source_index) {
+                return AppAction::Handled;
+            }
+            AppAction::Preview(PreviewOp::Squash {
+                source_oid: app.commits[source_index].oid.clone(),
+                target_oid: app.commits[app.selection_index].oid.clone(),
+            })
+        }
         KeyCommand::ShowHelp => {
             app.toggle_help();
             AppAction::Handled
@@ -111,3 +115,44 @@ pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
         _ => AppAction::Handled,
     }
 }
+
+/// Queue the squash or fixup in the edit session instead of running it. A
+/// squash still needs its combined message from the editor.
+fn queue_in_session(app: &mut AppState, source_index: usize, is_fixup: bool) -> AppAction {
+    let source = app.commits[source_index].clone();
+    let target = app.commits[app.selection_index].clone();
+    app.mode = AppMode::EditSession;
+    if !is_fixup {
+        return AppAction::QueueSquash {
+            source_oid: source.oid,
+            target_oid: target.oid,
+            source_message: source.message,
+            target_message: target.message,
+        };
+    }
+    if app.queue_session_op(SessionOp::Fixup {
+        source: source.oid,
+        target: target.oid,
+    }) {
+        AppAction::RefreshSession
+    } else {
+        AppAction::Handled
+    }
+}
+
+/// Whether the selected commit can take the squash, reporting why not.
+fn valid_target(app: &mut AppState, source_index: usize) -> bool {
+    // Cannot squash onto itself
+    if app.selection_index == source_index {
+        app.set_error_message("Cannot squash a commit into itself");
+        return false;
+    }
+
+    // Cannot squash onto staged/unstaged
+    let target = &app.commits[app.selection_index];
+    if target.oid == "staged" || target.oid == "unstaged" {
+        app.set_error_message("Cannot squash into staged/unstaged changes");
+        return false;
+    }
+    true
+}
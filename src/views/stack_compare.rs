@@ -0,0 +1,312 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Stack comparison view — pushed commits versus local commits
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::commit_detail::{diff_lines, render_scrollbar};
+use super::range_diff::interdiff_lines;
+use crate::{
+    app::{AppAction, AppMode, AppState},
+    event::KeyCommand,
+    range_diff::interdiff,
+    repo::GitRepo,
+    stack_compare::{StackChange, StackRow},
+    CommitInfo,
+};
+
+const HEADER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Green);
+const FOOTER_STYLE: Style = Style::new().fg(Color::White).bg(Color::Blue);
+const SECTION_STYLE: Style = Style::new().fg(Color::Yellow);
+
+/// Number of characters to display for short SHA.
+const SHORT_SHA_LENGTH: usize = 8;
+
+/// Width of the symbol column between the two stacks.
+const SYMBOL_WIDTH: u16 = 3;
+
+/// Handle an action while in StackCompare mode.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    let detail = matches!(app.mode, AppMode::StackCompare { detail: true, .. });
+    match action {
+        KeyCommand::MoveUp if !detail => {
+            app.stack_compare_move(-1);
+            AppAction::Handled
+        }
+        KeyCommand::MoveDown if !detail => {
+            app.stack_compare_move(1);
+            AppAction::Handled
+        }
+        KeyCommand::MoveUp => {
+            app.scroll_detail_up();
+            AppAction::Handled
+        }
+        KeyCommand::MoveDown => {
+            app.scroll_detail_down();
+            AppAction::Handled
+        }
+        KeyCommand::PageUp if detail => {
+            app.scroll_detail_page_up(app.detail_visible_height);
+            AppAction::Handled
+        }
+        KeyCommand::PageDown if detail => {
+            app.scroll_detail_page_down(app.detail_visible_height);
+            AppAction::Handled
+        }
+        KeyCommand::Confirm | KeyCommand::ToggleDetail => {
+            app.toggle_stack_compare_detail();
+            AppAction::Handled
+        }
+        KeyCommand::ShowHelp => {
+            app.toggle_help();
+            AppAction::Handled
+        }
+        KeyCommand::Quit if detail => {
+            app.toggle_stack_compare_detail();
+            AppAction::Handled
+        }
+        KeyCommand::Quit | KeyCommand::ComparePushed => {
+            app.close_stack_compare();
+            AppAction::Handled
+        }
+        _ => AppAction::Handled,
+    }
+}
+
+/// Render the stack comparison: either the two-column summary or, when
+/// drilled down, the diff of the selected row.
+pub fn render(repo: &impl GitRepo, app: &mut AppState, frame: &mut Frame) {
+    let (selection, detail) = match app.mode {
+        AppMode::StackCompare { selection, detail } => (selection, detail),
+        _ => return,
+    };
+
+    let area = frame.area();
+    let [header_area, body_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    let header = Paragraph::new(format!(
+        " Compare: {} \u{2194} local",
+        app.stack_compare_remote
+    ))
+    .style(HEADER_STYLE);
+    frame.render_widget(header, header_area);
+
+    let footer_text = if detail {
+        " \u{2191}/\u{2193} PgUp/PgDn scroll \u{b7} Esc back to summary".to_string()
+    } else {
+        let differing = app
+            .stack_compare
+            .iter()
+            .filter(|r| r.change != StackChange::Unchanged || r.reordered)
+            .count();
+        format!(
+            " {differing} of {} differ \u{b7} \u{2191}/\u{2193} select \u{b7} Enter diff \u{b7} Esc back",
+            app.stack_compare.len()
+        )
+    };
+    frame.render_widget(Paragraph::new(footer_text).style(FOOTER_STYLE), footer_area);
+
+    match app.stack_compare.get(selection) {
+        Some(row) if detail => {
+            let row = row.clone();
+            render_detail(repo, app, &row, frame, body_area);
+        }
+        Some(_) => render_summary(app, selection, frame, body_area),
+        None => {
+            let placeholder =
+                Paragraph::new("No commits").style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(placeholder, body_area);
+        }
+    }
+}
+
+/// Render the pushed stack on the left and the local stack on the right,
+/// one aligned row per commit, keeping the selection visible.
+fn render_summary(app: &AppState, selection: usize, frame: &mut Frame, area: Rect) {
+    let [title_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    let column_width = area.width.saturating_sub(SYMBOL_WIDTH) / 2;
+
+    let titles = Line::from(vec![
+        Span::styled(
+            pad(&format!(" {}", app.stack_compare_remote), column_width),
+            SECTION_STYLE.add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" ".repeat(SYMBOL_WIDTH as usize)),
+        Span::styled(" local", SECTION_STYLE.add_modifier(Modifier::BOLD)),
+    ]);
+    frame.render_widget(Paragraph::new(titles), title_area);
+
+    let height = list_area.height as usize;
+    let scroll = (selection + 1).saturating_sub(height);
+    let lines: Vec<Line> = app
+        .stack_compare
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(i, row)| {
+            let line = row_line(row, column_width);
+            if i == selection {
+                line.reversed()
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), list_area);
+}
+
+/// Format one row as `abcdef12 Summary | ! | 12345678 Summary`.
+fn row_line(row: &StackRow, column_width: u16) -> Line<'static> {
+    let (symbol, color) = row_symbol(row);
+    let side = |commit: &Option<(usize, CommitInfo)>| match commit {
+        Some((_, info)) => {
+            let sha: String = info.oid.chars().take(SHORT_SHA_LENGTH).collect();
+            pad(&format!(" {sha} {}", info.summary), column_width)
+        }
+        None => " ".repeat(column_width as usize),
+    };
+
+    Line::from(vec![
+        Span::styled(side(&row.remote), Style::new().fg(color)),
+        Span::styled(
+            format!(" {symbol} "),
+            Style::new().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(side(&row.local), Style::new().fg(color)),
+    ])
+}
+
+/// Symbol and color for a row: `=` unchanged, `!` modified, `<` dropped,
+/// `>` added, and `~` for an otherwise unchanged commit that moved.
+fn row_symbol(row: &StackRow) -> (&'static str, Color) {
+    match row.change {
+        StackChange::Unchanged if row.reordered => ("~", Color::Cyan),
+        StackChange::Unchanged => ("=", Color::Gray),
+        StackChange::Modified => ("!", Color::Yellow),
+        StackChange::Dropped => ("<", Color::Red),
+        StackChange::Added => (">", Color::Green),
+    }
+}
+
+/// Truncate or pad `text` to exactly `width` characters.
+fn pad(text: &str, width: u16) -> String {
+    format!("{:<width$.width$}", text, width = width as usize)
+}
+
+/// Render the diff of the selected row: the interdiff for commits present
+/// on both sides, the full diff for added and dropped commits.
+fn render_detail(
+    repo: &impl GitRepo,
+    app: &mut AppState,
+    row: &StackRow,
+    frame: &mut Frame,
+    area: Rect,
+) {
+    let [title_area, detail_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+
+    let (title, content) = detail_content(repo, row);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            title,
+            SECTION_STYLE.add_modifier(Modifier::BOLD),
+        )),
+        title_area,
+    );
+
+    let total_lines = content.len();
+    let visible_height = detail_area.height as usize;
+    let max_scroll = total_lines.saturating_sub(visible_height);
+    app.max_detail_scroll = max_scroll;
+    app.detail_visible_height = visible_height;
+    let scroll_offset = app.detail_scroll_offset.min(max_scroll);
+
+    let scrollbar_width = if max_scroll > 0 { 1 } else { 0 };
+    let scrollbar_area = Rect {
+        width: scrollbar_width,
+        ..detail_area
+    };
+    let text_area = Rect {
+        x: detail_area.x + scrollbar_width,
+        width: detail_area.width.saturating_sub(scrollbar_width),
+        ..detail_area
+    };
+    frame.render_widget(
+        Paragraph::new(content).scroll((scroll_offset as u16, 0)),
+        text_area,
+    );
+    if max_scroll > 0 && visible_height > 0 {
+        render_scrollbar(
+            frame,
+            scrollbar_area,
+            scroll_offset,
+            total_lines,
+            visible_height,
+        );
+    }
+}
+
+/// Title and body lines for the selected row.
+fn detail_content(repo: &impl GitRepo, row: &StackRow) -> (String, Vec<Line<'static>>) {
+    match (&row.remote, &row.local) {
+        (Some((_, old)), Some((_, new))) => {
+            let lines = match (repo.commit_diff(&old.oid), repo.commit_diff(&new.oid)) {
+                (Ok(old), Ok(new)) => interdiff_lines(&interdiff(&old, &new)),
+                _ => Vec::new(),
+            };
+            if lines.is_empty() {
+                let note = if row.reordered {
+                    " Moved; message and patch are identical."
+                } else {
+                    " Message and patch are identical."
+                };
+                return (
+                    " Unchanged".to_string(),
+                    vec![Line::from(Span::styled(
+                        note,
+                        Style::default().fg(Color::DarkGray),
+                    ))],
+                );
+            }
+            (" Interdiff (pushed \u{2192} local)".to_string(), lines)
+        }
+        (Some((_, commit)), None) | (None, Some((_, commit))) => {
+            let title = if row.change == StackChange::Dropped {
+                " Only in pushed version"
+            } else {
+                " Only in local version"
+            };
+            let lines = repo
+                .commit_diff(&commit.oid)
+                .map(|diff| diff_lines(&diff.files))
+                .unwrap_or_default();
+            (title.to_string(), lines)
+        }
+        (None, None) => (String::new(), Vec::new()),
+    }
+}
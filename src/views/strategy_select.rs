@@ -0,0 +1,136 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Merge strategy selection for retrying a conflicting step
+
+use super::dialog::render_centered_dialog;
+use crate::app::{AppAction, AppMode, AppState, RETRY_STRATEGIES};
+use crate::event::KeyCommand;
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+/// Handle an action while in StrategySelect mode.
+pub fn handle_key(action: KeyCommand, app: &mut AppState) -> AppAction {
+    match action {
+        KeyCommand::MoveUp => {
+            app.strategy_select_up();
+            AppAction::Handled
+        }
+        KeyCommand::MoveDown => {
+            app.strategy_select_down();
+            AppAction::Handled
+        }
+        KeyCommand::Confirm => {
+            let strategy = app.selected_merge_strategy();
+            if let AppMode::StrategySelect { conflict, .. } =
+                std::mem::replace(&mut app.mode, AppMode::CommitList)
+            {
+                AppAction::RetryConflict {
+                    strategy,
+                    conflict_state: conflict,
+                }
+            } else {
+                AppAction::Handled
+            }
+        }
+        KeyCommand::ShowHelp => {
+            app.toggle_help();
+            AppAction::Handled
+        }
+        KeyCommand::Quit => {
+            if let AppMode::StrategySelect { conflict, .. } =
+                std::mem::replace(&mut app.mode, AppMode::CommitList)
+            {
+                app.mode = AppMode::RebaseConflict(conflict);
+            }
+            AppAction::Handled
+        }
+        _ => AppAction::Handled,
+    }
+}
+
+/// Render the merge strategy selection dialog as a centered overlay.
+pub fn render(app: &AppState, frame: &mut Frame) {
+    let AppMode::StrategySelect {
+        conflict,
+        strategy_index,
+    } = &app.mode
+    else {
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                " {} conflicting file(s) in {}",
+                conflict.conflicting_files.len(),
+                conflict
+                    .conflicting_commit_oid
+                    .get(..10)
+                    .unwrap_or(&conflict.conflicting_commit_oid)
+            ),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::DIM),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            " Retry the merge with strategy:",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for (i, strategy) in RETRY_STRATEGIES.iter().enumerate() {
+        let selected = i == *strategy_index;
+        let marker = if selected { "▸ " } else { "  " };
+        let style = if selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        lines.push(Line::from(Span::styled(
+            format!(" {}  {}", marker, strategy.label()),
+            style,
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("        {}", strategy.description()),
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    lines.push(
+        Line::from(vec![
+            Span::styled("Enter ", Style::default().fg(Color::Cyan)),
+            Span::raw("Retry   "),
+            Span::styled("Esc ", Style::default().fg(Color::Cyan)),
+            Span::raw("Back"),
+        ])
+        .alignment(Alignment::Center),
+    );
+    lines.push(Line::from(""));
+
+    render_centered_dialog(frame, " Merge Strategy ", Color::Cyan, 58, lines);
+}
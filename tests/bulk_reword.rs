@@ -0,0 +1,63 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::repo::GitRepo;
+
+#[test]
+fn reword_commits_rewrites_messages_in_one_pass() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let b = test.commit_file("b.txt", "b\n", "Add b");
+    let c = test.commit_file("c.txt", "c\n", "Add c");
+    let d = test.commit_file("d.txt", "d\n", "Add d");
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commits(
+            &[
+                (b.to_string(), "Add b file\n".to_string()),
+                (d.to_string(), "Add d file\n".to_string()),
+            ],
+            &d.to_string(),
+        )
+        .unwrap();
+
+    let head = test.repo.head().unwrap().peel_to_commit().unwrap();
+    let middle = head.parent(0).unwrap();
+    let bottom = middle.parent(0).unwrap();
+    assert_eq!(head.message(), Some("Add d file\n"));
+    assert_eq!(middle.message(), Some("Add c"));
+    assert_eq!(bottom.message(), Some("Add b file\n"));
+    assert_eq!(bottom.parent(0).unwrap().summary(), Some("Initial"));
+
+    // Only messages change: every tree is kept.
+    for (new, old) in [(&head, d), (&middle, c), (&bottom, b)] {
+        assert_ne!(new.id(), old);
+        assert_eq!(new.tree_id(), test.repo.find_commit(old).unwrap().tree_id());
+    }
+}
+
+#[test]
+fn reword_commits_without_changes_keeps_head() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let head = test.commit_file("b.txt", "b\n", "Add b");
+    let git_repo = test.git_repo();
+
+    git_repo.reword_commits(&[], &head.to_string()).unwrap();
+
+    assert_eq!(git_repo.head_oid().unwrap(), head.to_string());
+}
@@ -0,0 +1,93 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git2::{Signature, Time};
+use git_tailor::repo::GitRepo;
+
+/// Commit `path` on top of HEAD with the given author and committer.
+fn commit_as(
+    test: &common::TestRepo,
+    path: &str,
+    message: &str,
+    author: &Signature,
+    committer: &Signature,
+) -> git2::Oid {
+    let repo = &test.repo;
+    std::fs::write(repo.workdir().unwrap().join(path), format!("{path}\n")).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), author, committer, message, &tree, &parents)
+        .unwrap()
+}
+
+fn setup() -> (common::TestRepo, git2::Oid, git2::Oid) {
+    let test = common::TestRepo::new();
+    let old_time = Time::new(1_600_000_000, 60);
+    let author = Signature::new("Original Author", "author@example.com", &old_time).unwrap();
+    let committer =
+        Signature::new("Original Committer", "committer@example.com", &old_time).unwrap();
+    commit_as(&test, "a.txt", "Initial", &author, &committer);
+    let target = commit_as(&test, "b.txt", "Add b", &author, &committer);
+    let descendant = commit_as(&test, "c.txt", "Add c", &author, &committer);
+    test.set_config("user.name", "Current User");
+    test.set_config("user.email", "current@example.com");
+    (test, target, descendant)
+}
+
+fn head_commit(test: &common::TestRepo) -> git2::Commit<'_> {
+    test.repo.head().unwrap().peel_to_commit().unwrap()
+}
+
+#[test]
+fn rewritten_commits_get_current_committer() {
+    let (test, target, descendant) = setup();
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &descendant.to_string())
+        .unwrap();
+
+    let head = head_commit(&test);
+    for commit in [head.clone(), head.parent(0).unwrap()] {
+        assert_eq!(commit.author().name(), Some("Original Author"));
+        assert_eq!(commit.author().when().seconds(), 1_600_000_000);
+        assert_eq!(commit.committer().name(), Some("Current User"));
+        assert_eq!(commit.committer().email(), Some("current@example.com"));
+        assert!(commit.committer().when().seconds() > 1_600_000_000);
+    }
+}
+
+#[test]
+fn committer_date_is_author_date_keeps_author_time() {
+    let (test, target, descendant) = setup();
+    let mut git_repo = test.git_repo();
+    git_repo.set_committer_date_is_author_date(true);
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &descendant.to_string())
+        .unwrap();
+
+    let head = head_commit(&test);
+    for commit in [head.clone(), head.parent(0).unwrap()] {
+        assert_eq!(commit.committer().name(), Some("Current User"));
+        assert_eq!(commit.committer().when().seconds(), 1_600_000_000);
+        assert_eq!(commit.committer().when().offset_minutes(), 60);
+    }
+}
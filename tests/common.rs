@@ -173,6 +173,35 @@ impl TestRepo {
         config.set_str(key, value).unwrap();
     }
 
+    /// Create `refs/remotes/<remote>/<branch>` pointing at `target`, adding a
+    /// matching `remote.<remote>` configuration if it is not there yet.
+    #[allow(dead_code)]
+    pub fn create_remote_branch(&self, remote: &str, branch: &str, target: git2::Oid) {
+        if self.repo.find_remote(remote).is_err() {
+            self.repo
+                .remote(remote, &format!("https://example.com/{remote}.git"))
+                .unwrap();
+        }
+        self.repo
+            .reference(
+                &format!("refs/remotes/{remote}/{branch}"),
+                target,
+                true,
+                "test: create remote branch",
+            )
+            .unwrap();
+    }
+
+    /// Configure `local` to track `<remote>/<branch>` as its upstream.
+    #[allow(dead_code)]
+    pub fn set_upstream(&self, local: &str, remote: &str, branch: &str) {
+        self.set_config(&format!("branch.{local}.remote"), remote);
+        self.set_config(
+            &format!("branch.{local}.merge"),
+            &format!("refs/heads/{branch}"),
+        );
+    }
+
     #[allow(dead_code)]
     pub fn checkout(&self, refname: &str) {
         self.repo.set_head(refname).unwrap();
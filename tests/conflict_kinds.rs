@@ -0,0 +1,289 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for classifying conflicts and resolving them by taking one side.
+
+mod common;
+
+use git_tailor::{
+    mergetool,
+    repo::{ConflictKind, ConflictSide, GitRepo, RebaseOutcome},
+};
+
+/// Merge three trees given as `(path, content, mode)` and write the result,
+/// conflicts included, to the repository's index.
+fn merge_into_index(
+    test: &common::TestRepo,
+    base: &[(&str, &[u8], i32)],
+    ours: &[(&str, &[u8], i32)],
+    theirs: &[(&str, &[u8], i32)],
+) {
+    let repo = &test.repo;
+    let tree = |files: &[(&str, &[u8], i32)]| {
+        let mut builder = repo.treebuilder(None).unwrap();
+        for (path, content, mode) in files {
+            builder
+                .insert(path, repo.blob(content).unwrap(), *mode)
+                .unwrap();
+        }
+        repo.find_tree(builder.write().unwrap()).unwrap()
+    };
+    let merged = repo
+        .merge_trees(&tree(base), &tree(ours), &tree(theirs), None)
+        .unwrap();
+    let mut index = repo.index().unwrap();
+    for entry in merged.iter() {
+        index.add(&entry).unwrap();
+    }
+    index.write().unwrap();
+}
+
+const TEXT: &[u8] = b"one\ntwo\nthree\nfour\nfive\n";
+
+#[test]
+fn classifies_content_add_add_and_delete_modify() {
+    let test = common::TestRepo::new();
+    merge_into_index(
+        &test,
+        &[("both.txt", TEXT, 0o100644), ("del.txt", TEXT, 0o100644)],
+        &[
+            ("both.txt", b"ours\n", 0o100644),
+            ("added.txt", b"a\n", 0o100644),
+        ],
+        &[
+            ("both.txt", b"theirs\n", 0o100644),
+            ("added.txt", b"b\n", 0o100644),
+            ("del.txt", b"changed\n", 0o100644),
+        ],
+    );
+    let kinds = test.git_repo().read_conflict_kinds();
+    assert_eq!(kinds["both.txt"], ConflictKind::Content);
+    assert_eq!(kinds["added.txt"], ConflictKind::AddAdd);
+    assert_eq!(
+        kinds["del.txt"],
+        ConflictKind::DeleteModify {
+            deleted_by_us: true
+        }
+    );
+}
+
+#[test]
+fn classifies_rename_binary_and_mode_change() {
+    let test = common::TestRepo::new();
+    merge_into_index(
+        &test,
+        &[
+            ("a.txt", TEXT, 0o100644),
+            ("bin", b"\0base", 0o100644),
+            ("tool", TEXT, 0o100644),
+        ],
+        &[
+            ("b.txt", TEXT, 0o100644),
+            ("bin", b"\0ours", 0o100644),
+            ("tool", TEXT, 0o100755),
+        ],
+        &[
+            ("c.txt", TEXT, 0o100644),
+            ("bin", b"\0theirs", 0o100644),
+            ("tool", TEXT, 0o120000),
+        ],
+    );
+    let kinds = test.git_repo().read_conflict_kinds();
+    assert_eq!(
+        kinds["a.txt"],
+        ConflictKind::Rename {
+            ours: false,
+            theirs: false
+        }
+    );
+    assert_eq!(
+        kinds["b.txt"],
+        ConflictKind::Rename {
+            ours: true,
+            theirs: false
+        }
+    );
+    assert_eq!(kinds["bin"], ConflictKind::Binary);
+    assert_eq!(
+        kinds["tool"],
+        ConflictKind::ModeChange {
+            ours: 0o100755,
+            theirs: 0o120000
+        }
+    );
+}
+
+/// Write a conflict for `path` with the given stages straight to the index.
+fn add_conflict(
+    test: &common::TestRepo,
+    path: &str,
+    base: Option<&[u8]>,
+    ours: Option<&[u8]>,
+    theirs: Option<&[u8]>,
+) {
+    let repo = &test.repo;
+    let entry = |content: Option<&[u8]>, stage: u16| {
+        content.map(|content| git2::IndexEntry {
+            ctime: git2::IndexTime::new(0, 0),
+            mtime: git2::IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: 0o100644,
+            uid: 0,
+            gid: 0,
+            file_size: content.len() as u32,
+            id: repo.blob(content).unwrap(),
+            flags: stage << 12,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        })
+    };
+    let mut index = repo.index().unwrap();
+    for entry in [entry(base, 1), entry(ours, 2), entry(theirs, 3)]
+        .iter()
+        .flatten()
+    {
+        index.add(entry).unwrap();
+    }
+    index.write().unwrap();
+}
+
+#[test]
+fn classifies_one_sided_paths_without_a_rename_source() {
+    let test = common::TestRepo::new();
+    add_conflict(&test, "ours.txt", None, Some(b"ours\n"), None);
+    add_conflict(&test, "theirs.txt", None, None, Some(b"theirs\n"));
+    add_conflict(&test, "gone.txt", Some(TEXT), None, None);
+
+    let kinds = test.git_repo().read_conflict_kinds();
+    assert_eq!(kinds["ours.txt"], ConflictKind::AddedByUs);
+    assert_eq!(kinds["theirs.txt"], ConflictKind::AddedByThem);
+    assert_eq!(kinds["gone.txt"], ConflictKind::BothDeleted);
+}
+
+#[test]
+fn one_sided_kinds_have_matching_labels_and_choices() {
+    let added_by_us = ConflictKind::AddedByUs;
+    assert_eq!(added_by_us.label(), "added by us");
+    assert_eq!(added_by_us.choice(ConflictSide::Ours), "keep");
+    assert_eq!(added_by_us.choice(ConflictSide::Theirs), "remove");
+
+    let added_by_them = ConflictKind::AddedByThem;
+    assert_eq!(added_by_them.label(), "added by them");
+    assert_eq!(added_by_them.choice(ConflictSide::Ours), "remove");
+    assert_eq!(added_by_them.choice(ConflictSide::Theirs), "keep");
+
+    let both_deleted = ConflictKind::BothDeleted;
+    assert_eq!(both_deleted.label(), "both deleted");
+    assert_eq!(both_deleted.choice(ConflictSide::Ours), "keep deleted");
+    assert_eq!(both_deleted.choice(ConflictSide::Theirs), "keep deleted");
+}
+
+#[test]
+fn taking_a_side_of_an_added_by_them_conflict() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    add_conflict(&test, "theirs.txt", None, None, Some(b"theirs\n"));
+    let git_repo = test.git_repo();
+
+    git_repo
+        .take_conflict_side("theirs.txt", ConflictSide::Theirs)
+        .unwrap();
+    assert!(git_repo.read_conflicting_files().is_empty());
+    let content = std::fs::read(test.repo.workdir().unwrap().join("theirs.txt")).unwrap();
+    assert_eq!(content, b"theirs\n");
+}
+
+/// Drop a commit modifying `a.txt` below one deleting it, which leaves a
+/// delete/modify conflict when the deletion is replayed.
+fn make_delete_modify_conflict(test: &common::TestRepo) -> git_tailor::repo::ConflictState {
+    test.commit_file("a.txt", "base\n", "base");
+    let to_drop = test.commit_file("a.txt", "base\nchanged\n", "change a");
+    let head = test.delete_file("a.txt", "remove a");
+    let git_repo = test.git_repo();
+    match git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    {
+        RebaseOutcome::Conflict(state) => *state,
+        RebaseOutcome::Complete => panic!("expected conflict"),
+    }
+}
+
+#[test]
+fn drop_conflict_records_delete_modify_kind() {
+    let test = common::TestRepo::new();
+    let state = make_delete_modify_conflict(&test);
+    assert_eq!(
+        state.kind("a.txt"),
+        ConflictKind::DeleteModify {
+            deleted_by_us: false
+        }
+    );
+    assert_eq!(state.choice_file(), Some("a.txt"));
+}
+
+#[test]
+fn taking_deleted_side_removes_file_and_continues() {
+    let test = common::TestRepo::new();
+    let state = make_delete_modify_conflict(&test);
+    let git_repo = test.git_repo();
+
+    git_repo
+        .take_conflict_side("a.txt", ConflictSide::Theirs)
+        .unwrap();
+    assert!(git_repo.read_conflicting_files().is_empty());
+    assert!(!test.repo.workdir().unwrap().join("a.txt").exists());
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    let tree = test.repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(tree.get_name("a.txt").is_none());
+}
+
+#[test]
+fn taking_binary_side_writes_its_content() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.bin", "\0base\n", "base");
+    let to_drop = test.commit_file("a.bin", "\0dropped\n", "change a");
+    let head = test.commit_file("a.bin", "\0head\n", "change a again");
+    let git_repo = test.git_repo();
+    let RebaseOutcome::Conflict(state) = git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    else {
+        panic!("expected conflict");
+    };
+    assert_eq!(state.kind("a.bin"), ConflictKind::Binary);
+
+    git_repo
+        .take_conflict_side("a.bin", ConflictSide::Theirs)
+        .unwrap();
+    assert!(git_repo.read_conflicting_files().is_empty());
+    let content = std::fs::read(test.repo.workdir().unwrap().join("a.bin")).unwrap();
+    assert_eq!(content, b"\0head\n");
+}
+
+#[test]
+fn mergetool_skips_files_that_are_not_text() {
+    let test = common::TestRepo::new();
+    let state = make_delete_modify_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+
+    // A tool that would fail if it ran.
+    let tool = mergetool::MergeTool::from_cmd("false", "false");
+    mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files).unwrap();
+    assert_eq!(git_repo.read_conflicting_files(), ["a.txt"]);
+}
@@ -0,0 +1,140 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for resolving conflicts region by region from the index stages.
+
+mod common;
+
+use git_tailor::{
+    merge3::{self, MergeChunk, Resolution},
+    repo::{GitRepo, RebaseOutcome},
+};
+
+fn resolve_all(chunks: &mut [MergeChunk], resolution: Resolution) {
+    for chunk in chunks {
+        if let MergeChunk::Conflict(region) = chunk {
+            region.resolution = Some(resolution.clone());
+        }
+    }
+}
+
+#[test]
+fn merge_index_stages_finds_conflict_region() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "base\n", "base");
+    let to_drop = test.commit_file("a.txt", "base\ndropped\n", "add dropped line");
+    let head = test.commit_file("a.txt", "base\ndropped\nhead\n", "add head line");
+    let git_repo = test.git_repo();
+
+    let RebaseOutcome::Conflict(state) = git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    else {
+        panic!("expected conflict");
+    };
+
+    let chunks = merge3::merge_index_stages(&git_repo, &state.conflicting_files[0]).unwrap();
+    let regions: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| match chunk {
+            MergeChunk::Conflict(region) => Some(region),
+            MergeChunk::Resolved(_) => None,
+        })
+        .collect();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].base, ["dropped\n"]);
+    assert!(regions[0].ours.is_empty());
+    assert_eq!(regions[0].theirs, ["dropped\n", "head\n"]);
+}
+
+#[test]
+fn resolved_file_is_staged_and_rebase_continues() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "base\n", "base");
+    let to_drop = test.commit_file("a.txt", "base\ndropped\n", "add dropped line");
+    let head = test.commit_file("a.txt", "base\ndropped\nhead\n", "add head line");
+    let git_repo = test.git_repo();
+
+    let RebaseOutcome::Conflict(state) = git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    else {
+        panic!("expected conflict");
+    };
+
+    let mut chunks = merge3::merge_index_stages(&git_repo, "a.txt").unwrap();
+    resolve_all(
+        &mut chunks,
+        Resolution::Edited(merge3::split_lines("head\n")),
+    );
+    let text = merge3::merged_text(&chunks).unwrap();
+    assert_eq!(text, "base\nhead\n");
+
+    std::fs::write(test.repo.workdir().unwrap().join("a.txt"), text).unwrap();
+    git_repo.stage_file("a.txt").unwrap();
+    assert!(git_repo.read_conflicting_files().is_empty());
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    let tree = test.repo.head().unwrap().peel_to_tree().unwrap();
+    let blob = test
+        .repo
+        .find_blob(tree.get_path(std::path::Path::new("a.txt")).unwrap().id())
+        .unwrap();
+    assert_eq!(blob.content(), b"base\nhead\n");
+}
+
+#[test]
+fn merge_index_stages_rejects_file_deleted_on_one_side() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "base\n", "base");
+    let to_drop = test.commit_file("a.txt", "base\nchanged\n", "change a");
+    let head = test.commit_file("b.txt", "b\n", "add b");
+    let workdir = test.repo.workdir().unwrap().to_path_buf();
+    std::fs::remove_file(workdir.join("a.txt")).unwrap();
+    let mut index = test.repo.index().unwrap();
+    index.read(true).unwrap();
+    index.remove_path(std::path::Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+    let tree = test.repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = test.repo.find_commit(head).unwrap();
+    let sig = test.repo.signature().unwrap();
+    let head = test
+        .repo
+        .commit(Some("HEAD"), &sig, &sig, "remove a", &tree, &[&parent])
+        .unwrap();
+    let git_repo = test.git_repo();
+
+    let RebaseOutcome::Conflict(_) = git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    else {
+        panic!("expected conflict");
+    };
+
+    let err = merge3::merge_index_stages(&git_repo, "a.txt").unwrap_err();
+    assert!(err.to_string().contains("deleted on one side"), "{err}");
+}
+
+#[test]
+fn edited_region_may_contain_underlines_and_quotes() {
+    let mut chunks = merge3::merge("x\n", "x\nours\n", "x\ntheirs\n");
+    let edited = merge3::split_lines("Heading\n==========\n>>>>>>>> quoted\n");
+    assert!(!merge3::has_conflict_markers(&edited));
+    resolve_all(&mut chunks, Resolution::Edited(edited));
+    assert_eq!(
+        merge3::merged_text(&chunks).unwrap(),
+        "x\nHeading\n==========\n>>>>>>>> quoted\n"
+    );
+}
@@ -0,0 +1,151 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for opening commits in the configured diff tool.
+
+mod common;
+
+use git_tailor::{
+    difftool,
+    repo::{FileBlobs, GitRepo},
+};
+
+#[test]
+fn resolve_diff_tool_returns_none_when_no_config() {
+    let test = common::TestRepo::new();
+    // Override any global configuration with empty tool names.
+    test.set_config("diff.tool", "");
+    test.set_config("merge.tool", "");
+    let git_repo = test.git_repo();
+    assert!(difftool::resolve_diff_tool(&git_repo).is_none());
+}
+
+#[test]
+fn resolve_diff_tool_returns_builtin_for_meld() {
+    let test = common::TestRepo::new();
+    test.set_config("diff.tool", "meld");
+    let git_repo = test.git_repo();
+    let tool = difftool::resolve_diff_tool(&git_repo).unwrap();
+    assert_eq!(tool.cmd, "meld $LOCAL $REMOTE");
+    assert!(!tool.trust_exit_code);
+}
+
+#[test]
+fn resolve_diff_tool_falls_back_to_merge_tool() {
+    let test = common::TestRepo::new();
+    test.set_config("merge.tool", "kdiff3");
+    let git_repo = test.git_repo();
+    let tool = difftool::resolve_diff_tool(&git_repo).unwrap();
+    assert_eq!(tool.name, "kdiff3");
+}
+
+#[test]
+fn resolve_diff_tool_prefers_custom_cmd_and_uses_path() {
+    let test = common::TestRepo::new();
+    test.set_config("diff.tool", "my-diff");
+    test.set_config("difftool.my-diff.cmd", "my-diff $LOCAL $REMOTE");
+    let git_repo = test.git_repo();
+    let tool = difftool::resolve_diff_tool(&git_repo).unwrap();
+    assert_eq!(tool.cmd, "my-diff $LOCAL $REMOTE");
+
+    test.set_config("diff.tool", "meld");
+    test.set_config("difftool.meld.path", "/opt/meld/bin/meld");
+    let tool = difftool::resolve_diff_tool(&git_repo).unwrap();
+    assert_eq!(tool.cmd, "'/opt/meld/bin/meld' $LOCAL $REMOTE");
+}
+
+#[test]
+fn diff_file_blobs_compares_commit_with_parent() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.rs", "old\n", "base");
+    let commit = test.commit_files(&[("a.rs", "new\n"), ("b.txt", "added\n")], "change");
+    let git_repo = test.git_repo();
+
+    let files = git_repo.diff_file_blobs(None, &commit.to_string()).unwrap();
+    assert_eq!(
+        files,
+        [
+            FileBlobs {
+                path: "a.rs".to_string(),
+                old: b"old\n".to_vec(),
+                new: b"new\n".to_vec(),
+            },
+            FileBlobs {
+                path: "b.txt".to_string(),
+                old: vec![],
+                new: b"added\n".to_vec(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn diff_file_blobs_compares_two_commits() {
+    let test = common::TestRepo::new();
+    let first = test.commit_file("a.rs", "one\n", "first");
+    test.commit_file("a.rs", "two\n", "second");
+    let third = test.commit_file("a.rs", "three\n", "third");
+    let git_repo = test.git_repo();
+
+    let files = git_repo
+        .diff_file_blobs(Some(&first.to_string()), &third.to_string())
+        .unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].old, b"one\n");
+    assert_eq!(files[0].new, b"three\n");
+}
+
+#[test]
+fn run_for_all_files_passes_blobs_with_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log");
+    let tool = difftool::DiffTool {
+        name: "log".to_string(),
+        cmd: format!(
+            "{{ echo \"$MERGED\"; cat \"$LOCAL\" \"$REMOTE\"; case \"$LOCAL\" in *.LOCAL.rs) echo ext;; esac; }} >> '{}'",
+            log.display()
+        ),
+        trust_exit_code: false,
+    };
+    let files = [FileBlobs {
+        path: "src/a.rs".to_string(),
+        old: b"old\n".to_vec(),
+        new: b"new\n".to_vec(),
+    }];
+
+    difftool::run_for_all_files(&tool, &files).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&log).unwrap(),
+        "src/a.rs\nold\nnew\next\n"
+    );
+}
+
+#[test]
+fn run_for_all_files_fails_on_exit_code_only_when_trusted() {
+    let files = [FileBlobs {
+        path: "a.txt".to_string(),
+        old: vec![],
+        new: b"new\n".to_vec(),
+    }];
+    let mut tool = difftool::DiffTool {
+        name: "false".to_string(),
+        cmd: "false".to_string(),
+        trust_exit_code: false,
+    };
+    difftool::run_for_all_files(&tool, &files).unwrap();
+
+    tool.trust_exit_code = true;
+    let err = difftool::run_for_all_files(&tool, &files).unwrap_err();
+    assert!(format!("{err:#}").contains("exited with"), "{err:#}");
+}
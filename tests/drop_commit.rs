@@ -196,6 +196,7 @@ fn drop_returns_conflict_when_descendant_depends_on_dropped_commit() {
     match result {
         RebaseOutcome::Conflict(state) => {
             assert_eq!(state.conflicting_commit_oid, head.to_string());
+            assert_eq!(state.conflicting_commit_summary, "add line3");
             assert!(
                 state.remaining_oids.is_empty(),
                 "no commits after the conflicting one"
@@ -373,6 +374,68 @@ fn drop_abort_restores_original_branch() {
     );
 }
 
+#[test]
+fn drop_skip_drops_conflicting_commit_and_picks_remaining() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "v1\n", "base");
+    let to_drop = test.commit_file("a.txt", "v2\n", "change a");
+    let _child1 = test.commit_file("a.txt", "v3\n", "change a again");
+    let head = test.commit_file("b.txt", "b1\n", "add b");
+
+    let git_repo = test.git_repo();
+    let state = match git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    {
+        RebaseOutcome::Conflict(s) => s,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+
+    let result = git_repo.rebase_skip(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete));
+
+    let new_head = test.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(new_head.summary(), Some("add b"));
+    assert_eq!(new_head.parent_id(0).unwrap(), base);
+    assert_eq!(file_content_at(&test.repo, new_head.id(), "a.txt"), "v1\n");
+    assert!(git_repo.read_conflicting_files().is_empty());
+    let workdir = test.repo.workdir().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(workdir.join("a.txt")).unwrap(),
+        "v1\n"
+    );
+}
+
+#[test]
+fn drop_skip_stops_at_next_conflict() {
+    let test = common::TestRepo::new();
+
+    let _base = test.commit_file("a.txt", "v1\n", "base");
+    let to_drop = test.commit_file("a.txt", "v2\n", "change a");
+    let child1 = test.commit_file("a.txt", "v3\n", "change a again");
+    let head = test.commit_file("a.txt", "v4\n", "change a once more");
+
+    let git_repo = test.git_repo();
+    let state = match git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    {
+        RebaseOutcome::Conflict(s) => s,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+    assert_eq!(state.conflicting_commit_oid, child1.to_string());
+
+    match git_repo.rebase_skip(&state).unwrap() {
+        RebaseOutcome::Conflict(next) => {
+            assert_eq!(next.conflicting_commit_oid, head.to_string());
+            assert!(next.remaining_oids.is_empty());
+            assert_eq!(next.conflicting_files, ["a.txt"]);
+        }
+        RebaseOutcome::Complete => panic!("expected second Conflict"),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Error cases
 // ---------------------------------------------------------------------------
@@ -482,3 +545,86 @@ fn drop_commit_with_no_descendants() {
         "HEAD should point to base after dropping the only commit above it"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Dropping several commits at once
+// ---------------------------------------------------------------------------
+
+#[test]
+fn drop_commits_removes_all_listed() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "v1\n", "base");
+    let drop_a = test.commit_file("b.txt", "b\n", "add b");
+    let _keep = test.commit_file("c.txt", "c\n", "add c");
+    let drop_b = test.commit_file("d.txt", "d\n", "add d");
+    let head = test.commit_file("e.txt", "e\n", "add e");
+
+    let git_repo = test.git_repo();
+    let result = git_repo
+        .drop_commits(&[drop_b.to_string(), drop_a.to_string()], &head.to_string())
+        .unwrap();
+
+    assert!(
+        matches!(result, RebaseOutcome::Complete),
+        "expected Complete, got {result:?}"
+    );
+
+    let summaries: Vec<String> = commits_from_head(&test.repo, base)
+        .into_iter()
+        .map(|oid| {
+            let commit = test.repo.find_commit(oid).unwrap();
+            commit.summary().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(summaries, vec!["add c", "add e"]);
+
+    let head_oid = test.repo.head().unwrap().target().unwrap();
+    let tree = test.repo.find_commit(head_oid).unwrap().tree().unwrap();
+    assert!(tree.get_path(std::path::Path::new("b.txt")).is_err());
+    assert!(tree.get_path(std::path::Path::new("d.txt")).is_err());
+}
+
+#[test]
+fn drop_commits_conflict_reports_descendant() {
+    let test = common::TestRepo::new();
+
+    test.commit_file("a.txt", "v1\n", "base");
+    let to_drop = test.commit_file("a.txt", "v2\n", "change a");
+    let _other = test.commit_file("b.txt", "b\n", "add b");
+    let head = test.commit_file("a.txt", "v3\n", "change a again");
+
+    let git_repo = test.git_repo();
+    let result = git_repo
+        .drop_commits(&[to_drop.to_string()], &head.to_string())
+        .unwrap();
+
+    match result {
+        RebaseOutcome::Conflict(state) => {
+            assert_eq!(state.conflicting_commit_oid, head.to_string());
+            assert_eq!(state.original_branch_oid, head.to_string());
+            assert!(state.remaining_oids.is_empty());
+        }
+        other => panic!("expected Conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn drop_commits_rejects_commit_not_on_branch() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "v1\n", "base");
+    test.create_branch("other", base);
+    test.checkout("refs/heads/other");
+    let elsewhere = test.commit_file("x.txt", "x\n", "elsewhere");
+    test.checkout("refs/heads/master");
+    let head = test.commit_file("b.txt", "b\n", "add b");
+
+    let git_repo = test.git_repo();
+    let result = git_repo.drop_commits(&[elsewhere.to_string()], &head.to_string());
+
+    assert!(
+        result.is_err(),
+        "dropping a commit off the branch should fail"
+    );
+}
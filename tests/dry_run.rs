@@ -0,0 +1,75 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for simulating rewrites without touching refs or the working tree.
+
+mod common;
+
+use git_tailor::repo::{GitRepo, RebaseOutcome};
+
+#[test]
+fn dry_run_drop_leaves_branch_and_objects_untouched() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("a.txt", "a\n", "base");
+    let to_drop = test.commit_file("b.txt", "b\n", "add b");
+    let head = test.commit_file("c.txt", "c\n", "add c");
+
+    let git_repo = test.git_repo();
+    let sim = git_repo.dry_run().unwrap();
+    let result = sim
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete));
+
+    let tip = sim.simulated_tip().expect("simulated tip");
+    let commits = sim.list_commits(&tip, &base.to_string()).unwrap();
+    let summaries: Vec<&str> = commits.iter().map(|c| c.summary.as_str()).collect();
+    assert_eq!(summaries, ["base", "add c"]);
+
+    // The branch, working tree and object database are unchanged.
+    assert_eq!(git_repo.head_oid().unwrap(), head.to_string());
+    let workdir = test.repo.workdir().unwrap();
+    assert!(workdir.join("b.txt").exists());
+    let tip_oid = git2::Oid::from_str(&tip).unwrap();
+    assert!(test.repo.find_commit(tip_oid).is_err());
+}
+
+#[test]
+fn dry_run_conflict_is_kept_out_of_index_and_working_tree() {
+    let test = common::TestRepo::new();
+    let _base = test.commit_file("a.txt", "line1\n", "base");
+    let to_drop = test.commit_file("a.txt", "line1\nline2\n", "add line2");
+    let head = test.commit_file("a.txt", "line1\nline2\nline3\n", "add line3");
+
+    let git_repo = test.git_repo();
+    let sim = git_repo.dry_run().unwrap();
+    let state = match sim
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+    assert_eq!(state.conflicting_commit_oid, head.to_string());
+    assert_eq!(state.conflicting_files, ["a.txt"]);
+    assert_eq!(sim.read_conflicting_files(), ["a.txt"]);
+
+    assert!(git_repo.read_conflicting_files().is_empty());
+    assert_eq!(git_repo.head_oid().unwrap(), head.to_string());
+    let workdir = test.repo.workdir().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(workdir.join("a.txt")).unwrap(),
+        "line1\nline2\nline3\n"
+    );
+}
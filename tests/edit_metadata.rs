@@ -0,0 +1,56 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::repo::{CommitMetadata, GitRepo};
+
+#[test]
+fn edit_metadata_rewrites_author_date_and_message() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let head = test.commit_file("c.txt", "c\n", "Add c");
+    let git_repo = test.git_repo();
+
+    let metadata = CommitMetadata {
+        author_name: "Jane Doe".to_string(),
+        author_email: "jane@example.com".to_string(),
+        author_time: 1_700_000_000,
+        author_offset_minutes: -90,
+        message: "Add b\n\nCo-authored-by: Bob <bob@example.com>\n".to_string(),
+    };
+    git_repo
+        .edit_commit_metadata(&target.to_string(), &metadata, &head.to_string())
+        .unwrap();
+
+    let new_head = test.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(new_head.summary(), Some("Add c"));
+    assert_ne!(new_head.id(), head);
+
+    let edited = new_head.parent(0).unwrap();
+    assert_ne!(edited.id(), target);
+    assert_eq!(edited.author().name(), Some("Jane Doe"));
+    assert_eq!(edited.author().email(), Some("jane@example.com"));
+    assert_eq!(edited.author().when().seconds(), 1_700_000_000);
+    assert_eq!(edited.author().when().offset_minutes(), -90);
+    assert_eq!(
+        edited.message(),
+        Some("Add b\n\nCo-authored-by: Bob <bob@example.com>\n")
+    );
+    assert_eq!(
+        edited.tree_id(),
+        test.repo.find_commit(target).unwrap().tree_id()
+    );
+}
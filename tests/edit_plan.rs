@@ -0,0 +1,279 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::repo::{GitRepo, PlanStep, RebaseOutcome};
+
+fn pick(oid: git2::Oid) -> PlanStep {
+    PlanStep::Pick {
+        oid: oid.to_string(),
+        message: None,
+    }
+}
+
+fn summaries(repo: &git2::Repository, count: usize) -> Vec<String> {
+    let mut commit = repo.head().unwrap().peel_to_commit().unwrap();
+    let mut summaries = Vec::new();
+    for i in 0..count {
+        summaries.push(commit.summary().unwrap_or("").to_string());
+        if i + 1 < count {
+            commit = commit.parent(0).unwrap();
+        }
+    }
+    summaries.reverse();
+    summaries
+}
+
+fn file_at_head(repo: &git2::Repository, path: &str) -> Option<String> {
+    let tree = repo.head().unwrap().peel_to_tree().unwrap();
+    let entry = tree.get_path(std::path::Path::new(path)).ok()?;
+    let blob = repo.find_blob(entry.id()).unwrap();
+    Some(String::from_utf8_lossy(blob.content()).into_owned())
+}
+
+#[test]
+fn plan_reorders_drops_folds_and_rewords() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "Initial");
+    let a = test.commit_file("a.txt", "a\n", "Add a");
+    test.commit_file("b.txt", "b\n", "Add b");
+    let c = test.commit_file("a.txt", "a fixed\n", "Fix a");
+    let d = test.commit_file("d.txt", "d\n", "Add d");
+    let git_repo = test.git_repo();
+
+    let steps = vec![
+        PlanStep::Pick {
+            oid: d.to_string(),
+            message: Some("Add d file\n".to_string()),
+        },
+        pick(a),
+        PlanStep::Fold {
+            oid: c.to_string(),
+            message: None,
+        },
+    ];
+    let result = git_repo
+        .execute_plan(&base.to_string(), &steps, &d.to_string())
+        .unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+
+    assert_eq!(summaries(&test.repo, 3), ["Initial", "Add d file", "Add a"]);
+    assert_eq!(
+        file_at_head(&test.repo, "a.txt").as_deref(),
+        Some("a fixed\n")
+    );
+    assert_eq!(file_at_head(&test.repo, "b.txt"), None);
+}
+
+#[test]
+fn plan_keeps_unchanged_prefix() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "Initial");
+    let a = test.commit_file("a.txt", "a\n", "Add a");
+    let b = test.commit_file("b.txt", "b\n", "Add b");
+    let git_repo = test.git_repo();
+
+    git_repo
+        .execute_plan(&base.to_string(), &[pick(a)], &b.to_string())
+        .unwrap();
+
+    assert_eq!(git_repo.head_oid().unwrap(), a.to_string());
+}
+
+#[test]
+fn plan_stops_on_conflict_and_continues() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("a.txt", "line1\n", "Initial");
+    let b = test.commit_file("a.txt", "line1\nline2\n", "Add line2");
+    let c = test.commit_file("a.txt", "line1\nline2\nline3\n", "Add line3");
+    let git_repo = test.git_repo();
+
+    // Swapping the two commits makes the later one conflict.
+    let result = git_repo
+        .execute_plan(&base.to_string(), &[pick(c), pick(b)], &c.to_string())
+        .unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+    assert_eq!(state.operation_label, "Plan");
+    assert_eq!(state.conflicting_commit_oid, c.to_string());
+    assert_eq!(state.conflicting_files, ["a.txt"]);
+    assert_eq!(state.remaining_oids, [b.to_string()]);
+
+    let still = git_repo.rebase_continue(&state).unwrap();
+    match still {
+        RebaseOutcome::Conflict(s) => assert!(s.still_unresolved),
+        RebaseOutcome::Complete => panic!("expected unresolved conflict"),
+    }
+
+    let workdir = test.repo.workdir().unwrap();
+    std::fs::write(workdir.join("a.txt"), "line1\nline3\n").unwrap();
+    let mut index = test.repo.index().unwrap();
+    index.read(true).unwrap();
+    index
+        .conflict_remove(std::path::Path::new("a.txt"))
+        .unwrap();
+    index.add_path(std::path::Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected second conflict"),
+    };
+    assert_eq!(state.conflicting_commit_oid, b.to_string());
+
+    std::fs::write(workdir.join("a.txt"), "line1\nline2\nline3\n").unwrap();
+    let mut index = test.repo.index().unwrap();
+    index.read(true).unwrap();
+    index
+        .conflict_remove(std::path::Path::new("a.txt"))
+        .unwrap();
+    index.add_path(std::path::Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    assert_eq!(
+        summaries(&test.repo, 3),
+        ["Initial", "Add line3", "Add line2"]
+    );
+}
+
+#[test]
+fn plan_abort_restores_branch() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("a.txt", "line1\n", "Initial");
+    let b = test.commit_file("a.txt", "line1\nline2\n", "Add line2");
+    let c = test.commit_file("a.txt", "line1\nline2\nline3\n", "Add line3");
+    let git_repo = test.git_repo();
+
+    let result = git_repo
+        .execute_plan(&base.to_string(), &[pick(c), pick(b)], &c.to_string())
+        .unwrap();
+    let RebaseOutcome::Conflict(state) = result else {
+        panic!("expected Conflict");
+    };
+
+    git_repo.rebase_abort(&state).unwrap();
+
+    assert_eq!(git_repo.head_oid().unwrap(), c.to_string());
+}
+
+#[test]
+fn plan_skip_drops_conflicting_pick() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("a.txt", "line1\n", "Initial");
+    let b = test.commit_file("a.txt", "line1\nline2\n", "Add line2");
+    let c = test.commit_file("a.txt", "line1\nline2\nline3\n", "Add line3");
+    let git_repo = test.git_repo();
+
+    let result = git_repo
+        .execute_plan(&base.to_string(), &[pick(c), pick(b)], &c.to_string())
+        .unwrap();
+    let RebaseOutcome::Conflict(state) = result else {
+        panic!("expected Conflict");
+    };
+
+    let result = git_repo.rebase_skip(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    assert_eq!(summaries(&test.repo, 2), ["Initial", "Add line2"]);
+    assert_eq!(
+        file_at_head(&test.repo, "a.txt").as_deref(),
+        Some("line1\nline2\n")
+    );
+}
+
+#[test]
+fn edit_stop_amends_staged_changes_on_continue() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "Initial");
+    let a = test.commit_file("a.txt", "a\n", "Add a");
+    let b = test.commit_file("b.txt", "b\n", "Add b");
+    let git_repo = test.git_repo();
+
+    let steps = vec![pick(a), PlanStep::Stop, pick(b)];
+    let result = git_repo
+        .execute_plan(&base.to_string(), &steps, &b.to_string())
+        .unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected a stop"),
+    };
+    assert_eq!(
+        state.plan_context.as_ref().map(|c| &c.step),
+        Some(&PlanStep::Stop)
+    );
+    assert_eq!(git_repo.head_oid().unwrap(), a.to_string());
+
+    // Unstaged changes block the continue.
+    let workdir = test.repo.workdir().unwrap();
+    std::fs::write(workdir.join("a.txt"), "a amended\n").unwrap();
+    let result = git_repo.rebase_continue(&state).unwrap();
+    match result {
+        RebaseOutcome::Conflict(s) => {
+            assert!(s.still_unresolved);
+            assert_eq!(s.conflicting_files, ["a.txt"]);
+        }
+        RebaseOutcome::Complete => panic!("expected unstaged changes to block"),
+    }
+
+    // The plan checked out HEAD through its own handle.
+    let mut index = test.repo.index().unwrap();
+    index.read(true).unwrap();
+    index.add_path(std::path::Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    assert_eq!(summaries(&test.repo, 3), ["Initial", "Add a", "Add b"]);
+    assert_eq!(
+        file_at_head(&test.repo, "a.txt").as_deref(),
+        Some("a amended\n")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_exec_stops_with_its_output() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "Initial");
+    let a = test.commit_file("a.txt", "a\n", "Add a");
+    let b = test.commit_file("b.txt", "b\n", "Add b");
+    let git_repo = test.git_repo();
+
+    let steps = vec![
+        pick(b),
+        PlanStep::Exec("test -f b.txt".to_string()),
+        PlanStep::Exec("echo 'a.txt missing' >&2; test -f a.txt".to_string()),
+        pick(a),
+    ];
+    let result = git_repo
+        .execute_plan(&base.to_string(), &steps, &b.to_string())
+        .unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected exec failure"),
+    };
+    let ctx = state.plan_context.as_ref().unwrap();
+    assert!(matches!(ctx.step, PlanStep::Exec(_)));
+    assert_eq!(ctx.failure.as_deref(), Some("a.txt missing"));
+    assert_eq!(ctx.remaining, [pick(a)]);
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    assert_eq!(summaries(&test.repo, 3), ["Initial", "Add b", "Add a"]);
+}
@@ -0,0 +1,151 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for edit sessions applied as one plan.
+
+mod common;
+
+use git_tailor::repo::{GitRepo, RebaseOutcome};
+use git_tailor::session::{EditSession, SessionOp};
+
+fn summaries(repo: &git2::Repository, count: usize) -> Vec<String> {
+    let mut commit = repo.head().unwrap().peel_to_commit().unwrap();
+    let mut summaries = Vec::new();
+    for i in 0..count {
+        summaries.push(commit.summary().unwrap_or("").to_string());
+        if i + 1 < count {
+            commit = commit.parent(0).unwrap();
+        }
+    }
+    summaries.reverse();
+    summaries
+}
+
+fn stack(git_repo: &impl GitRepo, head: git2::Oid, base: git2::Oid) -> EditSession {
+    let commits = git_repo
+        .list_commits(&head.to_string(), &base.to_string())
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.oid != base.to_string())
+        .collect();
+    EditSession::new(commits)
+}
+
+#[test]
+fn session_applies_queued_operations_in_one_rewrite() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "Initial");
+    let a = test.commit_file("a.txt", "a\n", "Add a");
+    let b = test.commit_file("b.txt", "b\n", "Add b");
+    let c = test.commit_file("a.txt", "a fixed\n", "Fix a");
+    let d = test.commit_file("d.txt", "d\n", "Add d");
+    let git_repo = test.git_repo();
+
+    let mut session = stack(&git_repo, d, base);
+    let ops = [
+        SessionOp::Move {
+            oid: d.to_string(),
+            newer: false,
+        },
+        SessionOp::Squash {
+            source: c.to_string(),
+            target: a.to_string(),
+            message: "Add a, fixed\n".to_string(),
+        },
+        SessionOp::Drop { oid: b.to_string() },
+        SessionOp::Reword {
+            oid: d.to_string(),
+            message: "Add d file\n".to_string(),
+        },
+    ];
+    for op in ops {
+        session.apply(op).unwrap();
+    }
+    assert_eq!(session.first_rewritten(), Some(a.to_string().as_str()));
+
+    let reflog_before = test.repo.reflog("HEAD").unwrap().len();
+    let result = git_repo
+        .execute_plan(&base.to_string(), &session.plan(), &d.to_string())
+        .unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+
+    assert_eq!(
+        summaries(&test.repo, 3),
+        ["Initial", "Add a, fixed", "Add d file"]
+    );
+    let tree = test.repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(tree.get_name("b.txt").is_none());
+    let a_blob = tree
+        .get_name("a.txt")
+        .unwrap()
+        .to_object(&test.repo)
+        .unwrap();
+    assert_eq!(a_blob.as_blob().unwrap().content(), b"a fixed\n");
+
+    let branch = test.repo.head().unwrap().name().unwrap().to_string();
+    let reflog = test.repo.reflog(&branch).unwrap();
+    assert_eq!(reflog.get(0).unwrap().message(), Some("git-tailor: plan"));
+    assert_eq!(test.repo.reflog("HEAD").unwrap().len(), reflog_before + 1);
+}
+
+#[test]
+fn session_conflict_resumes_the_rest_of_the_plan() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("a.txt", "line1\n", "Initial");
+    let b = test.commit_file("a.txt", "line1\nline2\n", "Add line2");
+    let c = test.commit_file("a.txt", "line1\nline2\nline3\n", "Add line3");
+    let d = test.commit_file("d.txt", "d\n", "Add d");
+    let git_repo = test.git_repo();
+
+    let mut session = stack(&git_repo, d, base);
+    session
+        .apply(SessionOp::Move {
+            oid: c.to_string(),
+            newer: false,
+        })
+        .unwrap();
+    session
+        .apply(SessionOp::Reword {
+            oid: d.to_string(),
+            message: "Add d file\n".to_string(),
+        })
+        .unwrap();
+
+    let result = git_repo
+        .execute_plan(&base.to_string(), &session.plan(), &d.to_string())
+        .unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+    assert_eq!(state.conflicting_commit_oid, c.to_string());
+    assert_eq!(state.remaining_oids, [b.to_string(), d.to_string()]);
+
+    // Resolve by taking the final content; the rest of the plan then runs.
+    let workdir = test.repo.workdir().unwrap();
+    std::fs::write(workdir.join("a.txt"), "line1\nline2\nline3\n").unwrap();
+    git_repo.stage_file("a.txt").unwrap();
+    let result = git_repo.rebase_continue(&state).unwrap();
+    let state = match result {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected the moved-back pick to conflict"),
+    };
+    assert_eq!(state.conflicting_commit_oid, b.to_string());
+    std::fs::write(workdir.join("a.txt"), "line1\nline2\nline3\n").unwrap();
+    git_repo.stage_file("a.txt").unwrap();
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+
+    assert_eq!(summaries(&test.repo, 3)[2], "Add d file");
+}
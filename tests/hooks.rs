@@ -0,0 +1,335 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+mod common;
+
+use git_tailor::repo::{GitRepo, RebaseOutcome};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+/// Install an executable hook script into `dir`.
+fn install_hook(dir: &Path, name: &str, body: &str) {
+    std::fs::create_dir_all(dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{body}")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn hooks_dir(test: &common::TestRepo) -> std::path::PathBuf {
+    test.repo.path().join("hooks")
+}
+
+fn message_of(repo: &git2::Repository, oid: &str) -> String {
+    repo.find_commit(git2::Oid::from_str(oid).unwrap())
+        .unwrap()
+        .message()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn commit_msg_hook_edits_reworded_message() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    install_hook(
+        &hooks_dir(&test),
+        "commit-msg",
+        "printf '\\nChange-Id: I1234\\n' >> \"$1\"\n",
+    );
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file\n", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(
+        message_of(&test.repo, &head),
+        "Add b file\n\nChange-Id: I1234\n"
+    );
+}
+
+#[test]
+fn failing_commit_msg_hook_aborts_reword() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    install_hook(
+        &hooks_dir(&test),
+        "commit-msg",
+        "echo 'missing ticket reference' >&2\nexit 1\n",
+    );
+    let git_repo = test.git_repo();
+
+    let err = git_repo
+        .reword_commit(&target.to_string(), "Add b file\n", &target.to_string())
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "commit-msg hook failed: missing ticket reference"
+    );
+    assert_eq!(git_repo.head_oid().unwrap(), target.to_string());
+}
+
+#[test]
+fn no_verify_skips_commit_msg_hook() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    install_hook(&hooks_dir(&test), "commit-msg", "exit 1\n");
+    let mut git_repo = test.git_repo();
+    git_repo.set_verify(false);
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file\n", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(message_of(&test.repo, &head), "Add b file\n");
+}
+
+#[test]
+fn post_rewrite_hook_receives_old_to_new_map() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let descendant = test.commit_file("c.txt", "c\n", "Add c");
+    let output = test.repo.path().join("post-rewrite-output");
+    install_hook(
+        &hooks_dir(&test),
+        "post-rewrite",
+        &format!("echo \"$1\" > '{0}'\ncat >> '{0}'\n", output.display()),
+    );
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file\n", &descendant.to_string())
+        .unwrap();
+
+    let new_head = test
+        .repo
+        .find_commit(git2::Oid::from_str(&git_repo.head_oid().unwrap()).unwrap())
+        .unwrap();
+    let new_target = new_head.parent_id(0).unwrap();
+    let expected = format!(
+        "rebase\n{target} {new_target}\n{descendant} {}\n",
+        new_head.id()
+    );
+    assert_eq!(std::fs::read_to_string(output).unwrap(), expected);
+}
+
+#[test]
+fn failing_pre_commit_hook_aborts_squash() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let source = test.commit_file("c.txt", "c\n", "Add c");
+    install_hook(
+        &hooks_dir(&test),
+        "pre-commit",
+        "echo 'rustfmt check failed' >&2\nexit 1\n",
+    );
+    let git_repo = test.git_repo();
+
+    let err = git_repo
+        .squash_commits(
+            &source.to_string(),
+            &target.to_string(),
+            "Add b and c\n",
+            &source.to_string(),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "pre-commit hook failed: rustfmt check failed"
+    );
+    assert_eq!(git_repo.head_oid().unwrap(), source.to_string());
+}
+
+#[test]
+fn pre_commit_hook_passing_allows_squash() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let source = test.commit_file("c.txt", "c\n", "Add c");
+    install_hook(&hooks_dir(&test), "pre-commit", "exit 0\n");
+    let git_repo = test.git_repo();
+
+    let outcome = git_repo
+        .squash_commits(
+            &source.to_string(),
+            &target.to_string(),
+            "Add b and c\n",
+            &source.to_string(),
+        )
+        .unwrap();
+
+    assert!(matches!(outcome, RebaseOutcome::Complete));
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(message_of(&test.repo, &head), "Add b and c\n");
+}
+
+#[test]
+fn pre_commit_hook_sees_the_tree_being_committed() {
+    let test = common::TestRepo::new();
+    test.commit_file("base.txt", "base\n", "base");
+    let head = test.commit_files(&[("a.txt", "a\n"), ("b.txt", "b\n")], "add a and b");
+    install_hook(
+        &hooks_dir(&test),
+        "pre-commit",
+        "git ls-files | tr '\\n' ' ' >> \"$(git rev-parse --git-dir)/files.log\"\necho >> \"$(git rev-parse --git-dir)/files.log\"\n",
+    );
+    let git_repo = test.git_repo();
+
+    git_repo
+        .split_commit_per_file(&head.to_string(), &head.to_string())
+        .unwrap();
+
+    let log = std::fs::read_to_string(test.repo.path().join("files.log")).unwrap();
+    assert_eq!(log, "a.txt base.txt \na.txt b.txt base.txt \n");
+}
+
+#[test]
+fn pre_commit_hook_changing_the_index_aborts_rewrite() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let source = test.commit_file("c.txt", "c\n", "Add c");
+    install_hook(
+        &hooks_dir(&test),
+        "pre-commit",
+        "blob=$(git hash-object -w --stdin </dev/null)\n\
+         git update-index --add --cacheinfo \"100644,$blob,formatted.txt\"\n",
+    );
+    let git_repo = test.git_repo();
+
+    let err = git_repo
+        .squash_commits(
+            &source.to_string(),
+            &target.to_string(),
+            "Add b and c\n",
+            &source.to_string(),
+        )
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("pre-commit hook changed the commit"),
+        "{err}"
+    );
+    assert_eq!(git_repo.head_oid().unwrap(), source.to_string());
+    assert!(test
+        .repo
+        .index()
+        .unwrap()
+        .get_path(Path::new("formatted.txt"), 0)
+        .is_none());
+}
+
+#[test]
+fn prepare_commit_msg_hook_gets_source_and_oid() {
+    let test = common::TestRepo::new();
+    let target = test.commit_file("a.txt", "a\n", "Initial");
+    install_hook(
+        &hooks_dir(&test),
+        "prepare-commit-msg",
+        "printf '# source: %s %s\\n' \"$2\" \"$3\" >> \"$1\"\n",
+    );
+    let git_repo = test.git_repo();
+
+    let prepared = git_repo
+        .prepare_commit_message("Initial\n", "commit", Some(&target.to_string()))
+        .unwrap();
+
+    assert_eq!(prepared, format!("Initial\n# source: commit {target}\n"));
+}
+
+#[test]
+fn hooks_path_config_is_honored() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let workdir = test.repo.workdir().unwrap().to_path_buf();
+    install_hook(
+        &workdir.join("githooks"),
+        "commit-msg",
+        "printf 'Signed-off-by: Hook\\n' >> \"$1\"\n",
+    );
+    // A hook in the default location must be ignored.
+    install_hook(&hooks_dir(&test), "commit-msg", "exit 1\n");
+    test.set_config("core.hooksPath", "githooks");
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file\n", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(
+        message_of(&test.repo, &head),
+        "Add b file\nSigned-off-by: Hook\n"
+    );
+}
+
+#[test]
+fn bulk_reword_reports_every_rejected_message() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let first = test.commit_file("b.txt", "b\n", "Add b");
+    let second = test.commit_file("c.txt", "c\n", "Add c");
+    install_hook(
+        &hooks_dir(&test),
+        "commit-msg",
+        "grep -q WIP \"$1\" && { echo 'WIP not allowed' >&2; exit 1; }\nexit 0\n",
+    );
+    let git_repo = test.git_repo();
+
+    let err = git_repo
+        .reword_commits(
+            &[
+                (first.to_string(), "WIP b\n".to_string()),
+                (second.to_string(), "WIP c\n".to_string()),
+            ],
+            &second.to_string(),
+        )
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains(&first.to_string()[..10]), "{err}");
+    assert!(err.contains(&second.to_string()[..10]), "{err}");
+    assert!(err.contains("WIP not allowed"), "{err}");
+    assert_eq!(git_repo.head_oid().unwrap(), second.to_string());
+}
+
+#[test]
+fn dry_run_does_not_run_hooks() {
+    let test = common::TestRepo::new();
+    let base = test.commit_file("base.txt", "base\n", "base");
+    let head = test.commit_files(&[("a.txt", "a\n"), ("b.txt", "b\n")], "add a and b");
+    install_hook(&hooks_dir(&test), "pre-commit", "exit 1\n");
+
+    let git_repo = test.git_repo();
+    let sim = git_repo.dry_run().unwrap();
+    sim.split_commit_per_file(&head.to_string(), &head.to_string())
+        .unwrap();
+
+    let tip = sim.simulated_tip().expect("simulated tip");
+    assert_eq!(sim.list_commits(&tip, &base.to_string()).unwrap().len(), 3);
+    assert_eq!(git_repo.head_oid().unwrap(), head.to_string());
+}
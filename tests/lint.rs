@@ -0,0 +1,58 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::lint::{LintConfig, SETTINGS_FILE};
+
+#[test]
+fn no_rules_without_configuration() {
+    let test = common::TestRepo::new();
+    assert!(!LintConfig::from_repo(&test.git_repo()).is_enabled());
+}
+
+#[test]
+fn rules_from_git_config() {
+    let test = common::TestRepo::new();
+    test.set_config("tailor.lint.maxSubjectLength", "50");
+    test.set_config("tailor.lint.blankSecondLine", "true");
+    test.set_config("tailor.lint.conventionalTypes", "feat, fix");
+
+    let config = LintConfig::from_repo(&test.git_repo());
+
+    assert_eq!(config.max_subject_length, Some(50));
+    assert!(config.blank_second_line);
+    assert!(!config.conventional);
+    assert_eq!(config.conventional_types, vec!["feat", "fix"]);
+}
+
+#[test]
+fn git_config_overrides_settings_file() {
+    let test = common::TestRepo::new();
+    std::fs::write(
+        test.repo.workdir().unwrap().join(SETTINGS_FILE),
+        "[lint]\nmax_subject_length = 72\nconventional = true\n",
+    )
+    .unwrap();
+    test.set_config("tailor.lint.maxSubjectLength", "60");
+
+    let config = LintConfig::from_repo(&test.git_repo());
+
+    assert_eq!(config.max_subject_length, Some(60));
+    assert!(config.conventional);
+    assert_eq!(
+        config.check("Add parser\n"),
+        vec!["Subject is not 'type(scope): description'"]
+    );
+}
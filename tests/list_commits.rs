@@ -95,3 +95,18 @@ fn test_list_commits_with_branch_name() {
     assert_eq!(commits[0].oid, c1_str);
     assert_eq!(commits[1].oid, c2_str);
 }
+
+#[test]
+fn test_commit_info_resolves_abbreviated_hash() {
+    let test = common::TestRepo::new();
+
+    let c1 = test.commit_file("file.txt", "first", "First");
+    let c2 = test.commit_file("file.txt", "second", "Second\n\nBody.");
+
+    let info = test.git_repo().commit_info(&c2.to_string()[..7]).unwrap();
+
+    assert_eq!(info.oid, c2.to_string());
+    assert_eq!(info.summary, "Second");
+    assert_eq!(info.parent_oids, [c1.to_string()]);
+    assert!(test.git_repo().commit_info("no-such-commit").is_err());
+}
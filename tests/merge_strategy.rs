@@ -0,0 +1,139 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for settling conflicts with merge strategy options.
+
+mod common;
+
+use git_tailor::repo::{GitRepo, MergeStrategy, RebaseOutcome};
+
+/// Commits where dropping the middle one conflicts on `a.txt`; returns the
+/// OIDs of the commit to drop and of HEAD.
+fn conflicting_drop(test: &common::TestRepo) -> (String, String) {
+    test.commit_file("a.txt", "line1\n", "base");
+    let to_drop = test.commit_file("a.txt", "line1\nline2\n", "add line2");
+    let head = test.commit_file("a.txt", "line1\nline2\nline3\n", "add line3");
+    (to_drop.to_string(), head.to_string())
+}
+
+fn read_file(test: &common::TestRepo, path: &str) -> String {
+    std::fs::read_to_string(test.repo.workdir().unwrap().join(path)).unwrap()
+}
+
+#[test]
+fn strategy_options_parse_like_git() {
+    assert_eq!(
+        MergeStrategy::from_option("ours"),
+        Some(MergeStrategy::Ours)
+    );
+    assert_eq!(
+        MergeStrategy::from_option("theirs"),
+        Some(MergeStrategy::Theirs)
+    );
+    assert_eq!(
+        MergeStrategy::from_option("ignore-whitespace"),
+        Some(MergeStrategy::IgnoreAllSpace)
+    );
+    assert_eq!(
+        MergeStrategy::from_option("ignore-all-space"),
+        Some(MergeStrategy::IgnoreAllSpace)
+    );
+    assert_eq!(
+        MergeStrategy::from_option("ignore-space-change"),
+        Some(MergeStrategy::IgnoreSpaceChange)
+    );
+    assert_eq!(MergeStrategy::from_option("patience"), None);
+}
+
+#[test]
+fn drop_with_theirs_strategy_completes_without_conflict() {
+    let test = common::TestRepo::new();
+    let (to_drop, head) = conflicting_drop(&test);
+
+    let mut git_repo = test.git_repo();
+    git_repo.set_merge_strategy(MergeStrategy::Theirs);
+    let result = git_repo.drop_commit(&to_drop, &head).unwrap();
+
+    assert!(matches!(result, RebaseOutcome::Complete));
+    assert_eq!(read_file(&test, "a.txt"), "line1\nline2\nline3\n");
+}
+
+#[test]
+fn drop_with_ours_strategy_keeps_the_rewritten_base() {
+    let test = common::TestRepo::new();
+    let (to_drop, head) = conflicting_drop(&test);
+
+    let mut git_repo = test.git_repo();
+    git_repo.set_merge_strategy(MergeStrategy::Ours);
+    let result = git_repo.drop_commit(&to_drop, &head).unwrap();
+
+    assert!(matches!(result, RebaseOutcome::Complete));
+    assert_eq!(read_file(&test, "a.txt"), "line1\n");
+}
+
+#[test]
+fn retry_with_theirs_resolves_conflict_and_continues() {
+    let test = common::TestRepo::new();
+    let (to_drop, head) = conflicting_drop(&test);
+
+    let git_repo = test.git_repo();
+    let state = match git_repo.drop_commit(&to_drop, &head).unwrap() {
+        RebaseOutcome::Conflict(state) => state,
+        RebaseOutcome::Complete => panic!("expected Conflict"),
+    };
+
+    let resolved = git_repo.retry_conflicts(MergeStrategy::Theirs).unwrap();
+    assert_eq!(resolved, vec!["a.txt".to_string()]);
+    assert!(git_repo.read_conflicting_files().is_empty());
+    assert_eq!(read_file(&test, "a.txt"), "line1\nline2\nline3\n");
+
+    let result = git_repo.rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete));
+}
+
+#[test]
+fn retry_ignoring_space_change_resolves_whitespace_only_conflict() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "x\nb c\ny\n", "base");
+    let to_drop = test.commit_file("a.txt", "x\nb   c\ny\n", "respace");
+    let head = test.commit_file("a.txt", "x\nB C\ny\n", "capitalize");
+
+    let git_repo = test.git_repo();
+    let result = git_repo
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap();
+    assert!(matches!(result, RebaseOutcome::Conflict(_)));
+
+    let resolved = git_repo
+        .retry_conflicts(MergeStrategy::IgnoreSpaceChange)
+        .unwrap();
+    assert_eq!(resolved, vec!["a.txt".to_string()]);
+    assert_eq!(read_file(&test, "a.txt"), "x\nB C\ny\n");
+}
+
+#[test]
+fn retry_leaves_conflicts_the_strategy_cannot_settle() {
+    let test = common::TestRepo::new();
+    let (to_drop, head) = conflicting_drop(&test);
+
+    let git_repo = test.git_repo();
+    let result = git_repo.drop_commit(&to_drop, &head).unwrap();
+    assert!(matches!(result, RebaseOutcome::Conflict(_)));
+
+    let resolved = git_repo
+        .retry_conflicts(MergeStrategy::IgnoreAllSpace)
+        .unwrap();
+    assert!(resolved.is_empty());
+    assert_eq!(git_repo.read_conflicting_files(), vec!["a.txt".to_string()]);
+}
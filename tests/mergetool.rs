@@ -108,6 +108,98 @@ fn resolve_merge_tool_cmd_returns_none_for_unknown_tool_without_cmd() {
     assert!(mergetool::resolve_merge_tool_cmd(&git_repo).is_none());
 }
 
+#[test]
+fn resolve_merge_tool_cmd_knows_git_builtin_tools() {
+    for name in [
+        "araxis",
+        "bc",
+        "bc3",
+        "bc4",
+        "codecompare",
+        "deltawalker",
+        "diffmerge",
+        "diffuse",
+        "ecmerge",
+        "emerge",
+        "examdiff",
+        "guiffy",
+        "gvimdiff",
+        "gvimdiff1",
+        "gvimdiff2",
+        "gvimdiff3",
+        "kdiff3",
+        "meld",
+        "nvimdiff",
+        "nvimdiff1",
+        "nvimdiff2",
+        "nvimdiff3",
+        "opendiff",
+        "p4merge",
+        "smerge",
+        "tkdiff",
+        "tortoisemerge",
+        "vimdiff",
+        "vimdiff1",
+        "vimdiff2",
+        "vimdiff3",
+        "winmerge",
+        "xxdiff",
+    ] {
+        let test = common::TestRepo::new();
+        test.set_config("merge.tool", name);
+        let git_repo = test.git_repo();
+        let cmd = mergetool::resolve_merge_tool_cmd(&git_repo);
+        assert!(
+            cmd.is_some_and(|cmd| cmd.contains("$MERGED")),
+            "no builtin cmd for {name}"
+        );
+    }
+}
+
+#[test]
+fn resolve_merge_tool_cmd_uses_configured_path() {
+    let test = common::TestRepo::new();
+    test.set_config("merge.tool", "meld");
+    test.set_config("mergetool.meld.path", "/opt/meld app/bin/meld");
+    let git_repo = test.git_repo();
+    let cmd = mergetool::resolve_merge_tool_cmd(&git_repo).unwrap();
+    assert_eq!(cmd, "'/opt/meld app/bin/meld' $LOCAL $MERGED $REMOTE");
+}
+
+#[test]
+fn resolve_merge_tool_prefers_guitool_when_gui_default() {
+    let test = common::TestRepo::new();
+    test.set_config("merge.tool", "vimdiff");
+    test.set_config("merge.guitool", "meld");
+    test.set_config("mergetool.guiDefault", "true");
+    let git_repo = test.git_repo();
+    let tool = mergetool::resolve_merge_tool(&git_repo).unwrap();
+    assert_eq!(tool.name, "meld");
+
+    test.set_config("mergetool.guiDefault", "false");
+    let tool = mergetool::resolve_merge_tool(&git_repo).unwrap();
+    assert_eq!(tool.name, "vimdiff");
+}
+
+#[test]
+fn resolve_merge_tool_reads_exit_code_backup_and_prompt_settings() {
+    let test = common::TestRepo::new();
+    test.set_config("merge.tool", "meld");
+    let git_repo = test.git_repo();
+    let tool = mergetool::resolve_merge_tool(&git_repo).unwrap();
+    assert!(!tool.trust_exit_code);
+    assert!(tool.keep_backup);
+    assert!(!tool.prompt);
+
+    test.set_config("mergetool.meld.trustExitCode", "true");
+    test.set_config("mergetool.keepBackup", "false");
+    test.set_config("mergetool.prompt", "yes");
+    let tool = mergetool::resolve_merge_tool(&git_repo).unwrap();
+    assert!(tool.trust_exit_code);
+    assert!(!tool.keep_backup);
+    assert!(tool.prompt);
+}
+
 // ---------------------------------------------------------------------------
 // read_index_stage / stage_file / read_conflicting_files (via Git2Repo)
 // ---------------------------------------------------------------------------
@@ -228,8 +320,8 @@ fn run_for_all_files_stages_file_and_clears_conflict() {
     let workdir = git_repo.workdir().unwrap();
 
     // Use 'cp $LOCAL $MERGED' as the "merge tool" — takes the ours-side content.
-    let cmd = "cp $LOCAL $MERGED";
-    mergetool::run_for_all_files(cmd, &workdir, &git_repo, &state.conflicting_files)
+    let tool = mergetool::MergeTool::from_cmd("cp", "cp $LOCAL $MERGED");
+    mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files)
         .expect("run_for_all_files should succeed");
 
     // Conflict must be cleared in the index.
@@ -443,3 +535,79 @@ fn stage_file_and_check_content_matches_written_file() {
         "no conflicts should remain after staging"
     );
 }
+
+// ---------------------------------------------------------------------------
+// run_for_all_files — exit code, conflict markers and backups
+// ---------------------------------------------------------------------------
+
+#[test]
+fn untrusted_exit_code_is_ignored_when_markers_are_gone() {
+    let test = common::TestRepo::new();
+    let state = make_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+
+    let tool = mergetool::MergeTool::from_cmd("cp", "cp $LOCAL $MERGED; exit 1");
+    mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files)
+        .expect("resolved file should count despite the exit code");
+    assert!(git_repo.read_conflicting_files().is_empty());
+}
+
+#[test]
+fn untrusted_exit_code_fails_when_markers_remain() {
+    let test = common::TestRepo::new();
+    let state = make_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+
+    let tool = mergetool::MergeTool::from_cmd("true", "true");
+    let err = mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files)
+        .unwrap_err();
+    assert!(
+        format!("{err:#}").contains("conflict markers remain"),
+        "{err:#}"
+    );
+    assert_eq!(git_repo.read_conflicting_files(), ["a.txt"]);
+}
+
+#[test]
+fn trusted_exit_code_fails_on_nonzero_status() {
+    let test = common::TestRepo::new();
+    let state = make_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+
+    let tool = mergetool::MergeTool {
+        trust_exit_code: true,
+        ..mergetool::MergeTool::from_cmd("cp", "cp $LOCAL $MERGED; exit 1")
+    };
+    let err = mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files)
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("exited with"), "{err:#}");
+    assert_eq!(git_repo.read_conflicting_files(), ["a.txt"]);
+}
+
+#[test]
+fn keep_backup_controls_orig_file() {
+    let test = common::TestRepo::new();
+    let state = make_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+    let conflicted = std::fs::read_to_string(workdir.join("a.txt")).unwrap();
+
+    let tool = mergetool::MergeTool::from_cmd("cp", "cp $LOCAL $MERGED");
+    mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files).unwrap();
+    let backup = std::fs::read_to_string(workdir.join("a.txt.orig")).unwrap();
+    assert_eq!(backup, conflicted);
+
+    let test = common::TestRepo::new();
+    let state = make_conflict(&test);
+    let git_repo = test.git_repo();
+    let workdir = git_repo.workdir().unwrap();
+    let tool = mergetool::MergeTool {
+        keep_backup: false,
+        ..mergetool::MergeTool::from_cmd("cp", "cp $LOCAL $MERGED")
+    };
+    mergetool::run_for_all_files(&tool, &workdir, &git_repo, &state.conflicting_files).unwrap();
+    assert!(!workdir.join("a.txt.orig").exists());
+}
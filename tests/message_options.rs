@@ -0,0 +1,57 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::message::{CleanupMode, MessageOptions};
+
+#[test]
+fn defaults_without_configuration() {
+    let test = common::TestRepo::new();
+    let options = MessageOptions::from_repo(&test.git_repo(), Some("Fix parser\n"));
+    assert_eq!(options, MessageOptions::default());
+}
+
+#[test]
+fn reads_comment_char_cleanup_and_template() {
+    let test = common::TestRepo::new();
+    let workdir = test.repo.workdir().unwrap();
+    std::fs::write(workdir.join("template.txt"), "Subject\n\nWhy:\n").unwrap();
+    test.set_config("core.commentChar", ";");
+    test.set_config("commit.cleanup", "scissors");
+    test.set_config("commit.template", "template.txt");
+
+    let options = MessageOptions::from_repo(&test.git_repo(), None);
+
+    assert_eq!(options.comment, ";");
+    assert_eq!(options.cleanup, CleanupMode::Scissors);
+    assert_eq!(options.template.as_deref(), Some("Subject\n\nWhy:\n"));
+    assert_eq!(
+        options.scissors_line(),
+        "; ------------------------ >8 ------------------------"
+    );
+}
+
+#[test]
+fn auto_comment_char_only_adapts_to_existing_message() {
+    let test = common::TestRepo::new();
+    test.set_config("core.commentChar", "auto");
+    let git_repo = test.git_repo();
+
+    let reword = MessageOptions::from_repo(&git_repo, Some("Fix\n\n# heading\n"));
+    let squash = MessageOptions::from_repo(&git_repo, None);
+
+    assert_eq!(reword.comment, ";");
+    assert_eq!(squash.comment, "#");
+}
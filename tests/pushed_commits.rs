@@ -0,0 +1,113 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::repo::{GitRepo, PushedScope};
+
+#[test]
+fn commits_reachable_from_any_remote_are_pushed() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    let pushed = test.commit_file("b.txt", "b\n", "Pushed");
+    test.create_remote_branch("backup", "wip", pushed);
+    let local = test.commit_file("c.txt", "c\n", "Local only");
+
+    let git_repo = test.git_repo();
+    let result = git_repo
+        .find_pushed_commits(
+            &local.to_string(),
+            &base.to_string(),
+            PushedScope::AnyRemote,
+        )
+        .unwrap();
+
+    assert_eq!(result, vec![pushed.to_string()]);
+}
+
+#[test]
+fn push_destination_scope_ignores_other_remotes() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    let first = test.commit_file("b.txt", "b\n", "First");
+    let second = test.commit_file("c.txt", "c\n", "Second");
+    test.create_remote_branch("origin", "master", first);
+    test.create_remote_branch("backup", "master", second);
+    test.set_config("branch.master.remote", "origin");
+
+    let git_repo = test.git_repo();
+    assert_eq!(
+        git_repo.push_destination(),
+        Some("origin/master".to_string())
+    );
+
+    let result = git_repo
+        .find_pushed_commits(
+            &second.to_string(),
+            &base.to_string(),
+            PushedScope::PushDestination,
+        )
+        .unwrap();
+    assert_eq!(result, vec![first.to_string()]);
+}
+
+#[test]
+fn push_remote_overrides_branch_remote() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    test.create_remote_branch("origin", "master", base);
+    test.create_remote_branch("fork", "master", base);
+    test.set_config("branch.master.remote", "origin");
+    test.set_config("remote.pushDefault", "fork");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.push_destination(), Some("fork/master".to_string()));
+}
+
+#[test]
+fn no_push_destination_means_nothing_pushed() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    let head = test.commit_file("b.txt", "b\n", "Local");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.push_destination(), None);
+    let result = git_repo
+        .find_pushed_commits(
+            &head.to_string(),
+            &base.to_string(),
+            PushedScope::PushDestination,
+        )
+        .unwrap();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn protect_pushed_config_values() {
+    assert_eq!(PushedScope::from_config(None), Some(PushedScope::AnyRemote));
+    assert_eq!(
+        PushedScope::from_config(Some("true")),
+        Some(PushedScope::AnyRemote)
+    );
+    assert_eq!(
+        PushedScope::from_config(Some("push")),
+        Some(PushedScope::PushDestination)
+    );
+    assert_eq!(PushedScope::from_config(Some("false")), None);
+    assert_eq!(PushedScope::from_config(Some("off")), None);
+}
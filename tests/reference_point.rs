@@ -14,6 +14,8 @@
 
 mod common;
 
+use git_tailor::repo::GitRepo;
+
 #[test]
 fn test_merge_base_with_branch_name() {
     let test = common::TestRepo::new();
@@ -127,3 +129,147 @@ fn test_merge_base_with_diverged_branches() {
     let merge_base = test.repo.merge_base(a_oid, b_oid).unwrap();
     assert_eq!(merge_base, base);
 }
+
+#[test]
+fn test_default_reference_prefers_upstream() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("file.txt", "base", "Base");
+    test.create_remote_branch("origin", "main", base);
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    test.commit_file("file.txt", "feature", "Feature");
+    test.set_upstream("feature", "origin", "main");
+    test.set_config("tailor.base", "master");
+
+    let git_repo = test.git_repo();
+    assert_eq!(
+        git_repo.default_reference(),
+        Some("origin/main".to_string())
+    );
+}
+
+#[test]
+fn test_default_reference_falls_back_to_origin_head() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("file.txt", "base", "Base");
+    test.create_remote_branch("origin", "trunk", base);
+    test.repo
+        .reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+            true,
+            "test: set origin/HEAD",
+        )
+        .unwrap();
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    test.commit_file("file.txt", "feature", "Feature");
+
+    let git_repo = test.git_repo();
+    assert_eq!(
+        git_repo.default_reference(),
+        Some("origin/trunk".to_string())
+    );
+}
+
+#[test]
+fn test_default_reference_falls_back_to_init_default_branch() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("file.txt", "base", "Base");
+    test.create_branch("trunk", base);
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    test.commit_file("file.txt", "feature", "Feature");
+    test.set_config("init.defaultBranch", "trunk");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.default_reference(), Some("trunk".to_string()));
+}
+
+#[test]
+fn test_default_reference_falls_back_to_tailor_base() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("file.txt", "base", "Base");
+    test.create_tag("v1.0", base);
+    test.commit_file("file.txt", "next", "Next");
+    test.set_config("init.defaultBranch", "does-not-exist");
+    test.set_config("tailor.base", "v1.0");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.default_reference(), Some("v1.0".to_string()));
+}
+
+#[test]
+fn test_default_reference_none_when_nothing_resolves() {
+    let test = common::TestRepo::new();
+
+    test.commit_file("file.txt", "base", "Base");
+    test.set_config("init.defaultBranch", "does-not-exist");
+    test.set_config("tailor.base", "also-missing");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.default_reference(), None);
+}
+
+#[test]
+fn test_fork_point_after_upstream_rewrite() {
+    let test = common::TestRepo::new();
+
+    let a = test.commit_file("file.txt", "a", "A");
+    let b = test.commit_file("file.txt", "b", "B");
+    test.create_remote_branch("origin", "main", a);
+    test.create_remote_branch("origin", "main", b);
+
+    test.create_branch("feature", b);
+    test.checkout("refs/heads/feature");
+    test.commit_file("feature.txt", "feature", "Feature");
+
+    // Upstream drops B and force-pushes a replacement on top of A.
+    test.create_branch("rewrite", a);
+    test.checkout("refs/heads/rewrite");
+    let b_prime = test.commit_file("file.txt", "b'", "B'");
+    test.create_remote_branch("origin", "main", b_prime);
+    test.checkout("refs/heads/feature");
+
+    let git_repo = test.git_repo();
+    assert_eq!(
+        git_repo.find_reference_point("origin/main").unwrap(),
+        a.to_string()
+    );
+    assert_eq!(
+        git_repo.find_fork_point("origin/main").unwrap(),
+        Some(b.to_string())
+    );
+}
+
+#[test]
+fn test_fork_point_equals_merge_base_without_rewrite() {
+    let test = common::TestRepo::new();
+
+    let a = test.commit_file("file.txt", "a", "A");
+    test.create_remote_branch("origin", "main", a);
+    test.create_branch("feature", a);
+    test.checkout("refs/heads/feature");
+    test.commit_file("feature.txt", "feature", "Feature");
+
+    let git_repo = test.git_repo();
+    assert_eq!(
+        git_repo.find_fork_point("origin/main").unwrap(),
+        Some(git_repo.find_reference_point("origin/main").unwrap())
+    );
+}
+
+#[test]
+fn test_fork_point_none_for_plain_commit() {
+    let test = common::TestRepo::new();
+
+    let a = test.commit_file("file.txt", "a", "A");
+    test.commit_file("file.txt", "b", "B");
+
+    let git_repo = test.git_repo();
+    assert_eq!(git_repo.find_fork_point(&a.to_string()).unwrap(), None);
+}
@@ -0,0 +1,145 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for recording and reusing conflict resolutions (rerere).
+
+mod common;
+
+use git_tailor::repo::{ConflictState, GitRepo, RebaseOutcome};
+
+/// Commits whose drop makes the last one conflict. Returns the OIDs of the
+/// commit to drop and of HEAD.
+fn conflicting_stack(test: &common::TestRepo) -> (git2::Oid, git2::Oid) {
+    test.commit_file("a.txt", "line1\n", "base");
+    let to_drop = test.commit_file("a.txt", "line1\nline2\n", "add line2");
+    let head = test.commit_file("a.txt", "line1\nline2\nline3\n", "add line3");
+    (to_drop, head)
+}
+
+fn drop_conflict(test: &common::TestRepo, to_drop: git2::Oid, head: git2::Oid) -> ConflictState {
+    match test
+        .git_repo()
+        .drop_commit(&to_drop.to_string(), &head.to_string())
+        .unwrap()
+    {
+        RebaseOutcome::Conflict(state) => *state,
+        RebaseOutcome::Complete => panic!("expected conflict"),
+    }
+}
+
+fn reset_hard(test: &common::TestRepo, oid: git2::Oid) {
+    let commit = test.repo.find_object(oid, None).unwrap();
+    test.repo
+        .reset(&commit, git2::ResetType::Hard, None)
+        .unwrap();
+}
+
+fn resolve(test: &common::TestRepo, content: &str) {
+    std::fs::write(test.repo.workdir().unwrap().join("a.txt"), content).unwrap();
+    test.git_repo().stage_file("a.txt").unwrap();
+}
+
+fn cache_dirs(test: &common::TestRepo) -> Vec<std::path::PathBuf> {
+    match std::fs::read_dir(test.repo.path().join("rr-cache")) {
+        Ok(entries) => entries.map(|e| e.unwrap().path()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[test]
+fn resolution_is_recorded_and_reused() {
+    let test = common::TestRepo::new();
+    test.set_config("rerere.enabled", "true");
+    let (to_drop, head) = conflicting_stack(&test);
+
+    let state = drop_conflict(&test, to_drop, head);
+    assert!(state.reused_resolutions.is_empty());
+    assert!(test.repo.path().join("MERGE_RR").is_file());
+    let dirs = cache_dirs(&test);
+    assert_eq!(dirs.len(), 1);
+    let preimage = std::fs::read_to_string(dirs[0].join("preimage")).unwrap();
+    assert!(preimage.contains("<<<<<<<\n"), "{preimage}");
+
+    resolve(&test, "line1\nline3\n");
+    let result = test.git_repo().rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+    assert!(!test.repo.path().join("MERGE_RR").exists());
+    assert_eq!(
+        std::fs::read_to_string(dirs[0].join("postimage")).unwrap(),
+        "line1\nline3\n"
+    );
+
+    // The same conflict again is resolved and staged automatically.
+    reset_hard(&test, head);
+    let state = drop_conflict(&test, to_drop, head);
+    assert_eq!(state.reused_resolutions, ["a.txt"]);
+    assert!(state.conflicting_files.is_empty());
+    let workdir = test.repo.workdir().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(workdir.join("a.txt")).unwrap(),
+        "line1\nline3\n"
+    );
+    let result = test.git_repo().rebase_continue(&state).unwrap();
+    assert!(matches!(result, RebaseOutcome::Complete), "{result:?}");
+}
+
+#[test]
+fn reused_resolution_is_not_staged_without_auto_update() {
+    let test = common::TestRepo::new();
+    test.set_config("rerere.enabled", "true");
+    test.set_config("rerere.autoUpdate", "false");
+    let (to_drop, head) = conflicting_stack(&test);
+
+    let state = drop_conflict(&test, to_drop, head);
+    resolve(&test, "line1\nline3\n");
+    test.git_repo().rebase_continue(&state).unwrap();
+
+    reset_hard(&test, head);
+    let state = drop_conflict(&test, to_drop, head);
+    assert_eq!(state.reused_resolutions, ["a.txt"]);
+    assert_eq!(state.conflicting_files, ["a.txt"]);
+    let workdir = test.repo.workdir().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(workdir.join("a.txt")).unwrap(),
+        "line1\nline3\n"
+    );
+}
+
+#[test]
+fn nothing_is_recorded_when_disabled() {
+    let test = common::TestRepo::new();
+    test.set_config("rerere.enabled", "false");
+    let (to_drop, head) = conflicting_stack(&test);
+
+    let state = drop_conflict(&test, to_drop, head);
+    resolve(&test, "line1\nline3\n");
+    test.git_repo().rebase_continue(&state).unwrap();
+
+    assert!(cache_dirs(&test).is_empty());
+    assert!(!test.repo.path().join("MERGE_RR").exists());
+}
+
+#[test]
+fn abort_forgets_unresolved_preimage() {
+    let test = common::TestRepo::new();
+    test.set_config("rerere.enabled", "true");
+    let (to_drop, head) = conflicting_stack(&test);
+
+    let state = drop_conflict(&test, to_drop, head);
+    assert_eq!(cache_dirs(&test).len(), 1);
+
+    test.git_repo().rebase_abort(&state).unwrap();
+    assert!(cache_dirs(&test).is_empty());
+    assert!(!test.repo.path().join("MERGE_RR").exists());
+}
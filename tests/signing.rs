@@ -0,0 +1,199 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+mod common;
+
+use git_tailor::repo::GitRepo;
+use std::os::unix::fs::PermissionsExt;
+
+const FAKE_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----\n\nfake\n-----END PGP SIGNATURE-----\n";
+
+/// Write an executable shell script into the test repository's git dir and
+/// return its path.
+fn write_script(test: &common::TestRepo, name: &str, body: &str) -> String {
+    let path = test.repo.path().join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{body}")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn signature_of(repo: &git2::Repository, oid: &str) -> Option<String> {
+    let oid = git2::Oid::from_str(oid).unwrap();
+    repo.extract_signature(&oid, None)
+        .ok()
+        .map(|(sig, _)| sig.as_str().unwrap().to_string())
+}
+
+#[test]
+fn reword_is_unsigned_without_gpgsign() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert!(signature_of(&test.repo, &head).is_none());
+}
+
+#[test]
+fn reword_signs_with_gpg_program() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    test.commit_file("c.txt", "c\n", "Add c");
+    let args_file = test.repo.path().join("gpg-args");
+    let gpg = write_script(
+        &test,
+        "fake-gpg",
+        &format!(
+            "echo \"$@\" > '{}'\ncat > /dev/null\necho '[GNUPG:] SIG_CREATED D 1 8 00 0 X' >&2\nprintf '%b' '{}'\n",
+            args_file.display(),
+            FAKE_SIGNATURE.replace('\n', "\\n"),
+        ),
+    );
+    test.set_config("commit.gpgsign", "true");
+    test.set_config("gpg.program", &gpg);
+    test.set_config("user.signingkey", "ABCD1234");
+    let git_repo = test.git_repo();
+    let head = git_repo.head_oid().unwrap();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &head)
+        .unwrap();
+
+    // Both the reworded commit and its rebased descendant are signed.
+    let new_head = git_repo.head_oid().unwrap();
+    assert_eq!(
+        signature_of(&test.repo, &new_head).as_deref(),
+        Some(FAKE_SIGNATURE)
+    );
+    let reworded = test
+        .repo
+        .find_commit(git2::Oid::from_str(&new_head).unwrap())
+        .unwrap()
+        .parent_id(0)
+        .unwrap();
+    assert_eq!(
+        signature_of(&test.repo, &reworded.to_string()).as_deref(),
+        Some(FAKE_SIGNATURE)
+    );
+    let args = std::fs::read_to_string(args_file).unwrap();
+    assert_eq!(args.trim(), "--status-fd=2 -bsau ABCD1234");
+}
+
+#[test]
+fn signing_failure_is_reported_and_branch_untouched() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    let gpg = write_script(
+        &test,
+        "fake-gpg",
+        "cat > /dev/null\necho 'gpg: signing failed: No secret key' >&2\nexit 2\n",
+    );
+    test.set_config("commit.gpgsign", "true");
+    test.set_config("gpg.program", &gpg);
+    let git_repo = test.git_repo();
+
+    let err = git_repo
+        .reword_commit(&target.to_string(), "Add b file", &target.to_string())
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("gpg: signing failed: No secret key"),
+        "unexpected error: {err}"
+    );
+    assert_eq!(git_repo.head_oid().unwrap(), target.to_string());
+}
+
+#[test]
+fn reword_signs_commit_with_non_utf8_author() {
+    let test = common::TestRepo::new();
+    let parent = test.commit_file("a.txt", "a\n", "Initial");
+    let tree = test.repo.find_commit(parent).unwrap().tree_id();
+    let mut raw = format!("tree {tree}\nparent {parent}\n").into_bytes();
+    raw.extend_from_slice(b"author Ren\xe9 <rene@example.com> 1700000000 +0000\n");
+    raw.extend_from_slice(b"committer Ren\xe9 <rene@example.com> 1700000000 +0000\n");
+    raw.extend_from_slice(b"encoding ISO-8859-1\n\nAdd b\n");
+    let target = test
+        .repo
+        .odb()
+        .unwrap()
+        .write(git2::ObjectType::Commit, &raw)
+        .unwrap();
+    let branch = test.repo.head().unwrap().name().unwrap().to_string();
+    test.repo.reference(&branch, target, true, "test").unwrap();
+    let gpg = write_script(
+        &test,
+        "fake-gpg",
+        &format!(
+            "cat > /dev/null\necho '[GNUPG:] SIG_CREATED D 1 8 00 0 X' >&2\nprintf '%b' '{}'\n",
+            FAKE_SIGNATURE.replace('\n', "\\n"),
+        ),
+    );
+    test.set_config("commit.gpgsign", "true");
+    test.set_config("gpg.program", &gpg);
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(
+        signature_of(&test.repo, &head).as_deref(),
+        Some(FAKE_SIGNATURE)
+    );
+    let commit = test
+        .repo
+        .find_commit(git2::Oid::from_str(&head).unwrap())
+        .unwrap();
+    assert_eq!(commit.author().name_bytes(), b"Ren\xe9");
+    assert_eq!(commit.message(), Some("Add b file"));
+}
+
+#[test]
+fn reword_signs_with_ssh_keygen() {
+    let test = common::TestRepo::new();
+    test.commit_file("a.txt", "a\n", "Initial");
+    let target = test.commit_file("b.txt", "b\n", "Add b");
+    // ssh-keygen -Y sign -n git -f <key> <file> writes <file>.sig
+    let ssh_keygen = write_script(
+        &test,
+        "fake-ssh-keygen",
+        "for last; do :; done\nprintf 'SSH SIGNATURE for %s\\n' \"$6\" > \"$last.sig\"\n",
+    );
+    test.set_config("commit.gpgsign", "true");
+    test.set_config("gpg.format", "ssh");
+    test.set_config("gpg.ssh.program", &ssh_keygen);
+    test.set_config("user.signingkey", "/home/test/.ssh/id_ed25519");
+    let git_repo = test.git_repo();
+
+    git_repo
+        .reword_commit(&target.to_string(), "Add b file", &target.to_string())
+        .unwrap();
+
+    let head = git_repo.head_oid().unwrap();
+    assert_eq!(
+        signature_of(&test.repo, &head).as_deref(),
+        Some("SSH SIGNATURE for /home/test/.ssh/id_ed25519\n")
+    );
+}
@@ -271,6 +271,31 @@ fn squash_returns_conflict_when_source_and_target_conflict() {
     }
 }
 
+#[test]
+fn squash_conflict_cannot_be_skipped() {
+    let test = common::TestRepo::new();
+
+    let _base = test.commit_file("a.txt", "original\n", "base");
+    let target = test.commit_file("a.txt", "target version\n", "target changes a");
+    let _mid = test.commit_file("a.txt", "mid version\n", "mid changes a");
+    let source = test.commit_file("a.txt", "source version\n", "source changes a");
+
+    let git_repo = test.git_repo();
+    let result = git_repo
+        .squash_commits(
+            &source.to_string(),
+            &target.to_string(),
+            "squashed",
+            &source.to_string(),
+        )
+        .unwrap();
+    let RebaseOutcome::Conflict(state) = result else {
+        panic!("expected Conflict");
+    };
+
+    assert!(git_repo.rebase_skip(&state).is_err());
+}
+
 #[test]
 fn squash_returns_conflict_when_all_three_modify_same_file() {
     let test = common::TestRepo::new();
@@ -249,3 +249,115 @@ fn test_status_bar_long_error() {
     let buffer = terminal.backend().buffer().clone();
     insta::assert_debug_snapshot!(buffer);
 }
+
+#[test]
+fn test_footer_shows_reference_name() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.commits = vec![common::create_test_commit("abc123def456", "Initial commit")];
+    app.selection_index = 0;
+    app.reference_name = "origin/main".to_string();
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_footer_explains_fork_point() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.commits = vec![common::create_test_commit("abc123def456", "Initial commit")];
+    app.selection_index = 0;
+    app.reference_name = "origin/main".to_string();
+    app.fork_point_merge_base = Some("0123456789abcdef".to_string());
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_help_explains_fork_point() {
+    let backend = TestBackend::new(80, 50);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.reference_name = "origin/main".to_string();
+    app.fork_point_merge_base = Some("0123456789abcdef".to_string());
+
+    terminal
+        .draw(|frame| {
+            views::help::render(&app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_commit_list_marks_upstreamed_commits() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.commits = vec![
+        common::create_test_commit("abc123def456", "Already upstream"),
+        common::create_test_commit("def456ghi789", "Only on this branch"),
+    ];
+    app.selection_index = 1;
+    app.upstreamed_oids.insert("abc123def456".to_string());
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_commit_list_marks_lint_warnings() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.commits = vec![
+        common::create_test_commit("abc123def456", "Already upstream"),
+        common::create_test_commit("def456ghi789", "Subject that is far too long"),
+    ];
+    app.selection_index = 1;
+    app.upstreamed_oids.insert("abc123def456".to_string());
+    for commit in &app.commits {
+        app.lint_warnings.insert(
+            commit.oid.clone(),
+            vec!["Subject is not 'type(scope): description'".to_string()],
+        );
+    }
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
@@ -0,0 +1,137 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI tests for the conflict resolution view.
+
+mod common;
+
+use git_tailor::{
+    app::{AppAction, AppMode, AppState, ConflictResolveState},
+    event::KeyCommand,
+    merge3::{self, Resolution},
+    repo::ConflictState,
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn make_resolve_app() -> AppState {
+    let mut app = AppState::with_commits(vec![
+        common::create_test_commit("abc123def456", "Refactor parser module"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+    ]);
+    let conflict = ConflictState {
+        operation_label: "Drop".to_string(),
+        original_branch_oid: "def456ghi789abcdef012".to_string(),
+        new_tip_oid: "aabbccddeeff00112233".to_string(),
+        conflicting_commit_oid: "def456ghi789".to_string(),
+        conflicting_commit_summary: "Add feature X".to_string(),
+        conflicting_files: vec!["src/parser.rs".to_string()],
+        ..Default::default()
+    };
+    let chunks = merge3::merge(
+        "fn parse() {\n    let a = 1;\n}\n\nfn eval() {\n    todo!()\n}\n",
+        "fn parse() {\n    let a = 2;\n}\n\nfn eval() {\n    run()\n}\n",
+        "fn parse() {\n    let a = 3;\n}\n\nfn eval() {\n    walk()\n}\n",
+    );
+    app.mode = AppMode::ConflictResolve(ConflictResolveState::new(
+        conflict,
+        "src/parser.rs".to_string(),
+        chunks,
+    ));
+    app
+}
+
+fn resolve_state(app: &AppState) -> &ConflictResolveState {
+    match &app.mode {
+        AppMode::ConflictResolve(state) => state,
+        _ => panic!("expected ConflictResolve mode"),
+    }
+}
+
+#[test]
+fn test_conflict_resolve_view() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let mut app = make_resolve_app();
+    views::conflict_resolve::handle_key(KeyCommand::TakeTheirs, &mut app);
+
+    terminal
+        .draw(|frame| views::conflict_resolve::render(&app, frame))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_conflict_resolve_picks_and_advances() {
+    let mut app = make_resolve_app();
+    assert_eq!(resolve_state(&app).region_count(), 2);
+
+    views::conflict_resolve::handle_key(KeyCommand::TakeOurs, &mut app);
+    assert_eq!(resolve_state(&app).selected, 1);
+    views::conflict_resolve::handle_key(KeyCommand::TakeBoth, &mut app);
+
+    let resolutions: Vec<_> = resolve_state(&app)
+        .regions()
+        .map(|r| r.resolution.clone())
+        .collect();
+    assert_eq!(
+        resolutions,
+        [Some(Resolution::Ours), Some(Resolution::Both)]
+    );
+    assert_eq!(
+        merge3::merged_text(&resolve_state(&app).chunks).as_deref(),
+        Some("fn parse() {\n    let a = 2;\n}\n\nfn eval() {\n    run()\n    walk()\n}\n")
+    );
+}
+
+#[test]
+fn test_conflict_resolve_confirm_requires_all_regions() {
+    let mut app = make_resolve_app();
+    views::conflict_resolve::handle_key(KeyCommand::TakeOurs, &mut app);
+
+    let action = views::conflict_resolve::handle_key(KeyCommand::Confirm, &mut app);
+    assert!(matches!(action, AppAction::Handled));
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("1 conflict(s) still unresolved")
+    );
+
+    views::conflict_resolve::handle_key(KeyCommand::TakeTheirs, &mut app);
+    let action = views::conflict_resolve::handle_key(KeyCommand::Confirm, &mut app);
+    assert!(matches!(action, AppAction::WriteResolution));
+}
+
+#[test]
+fn test_conflict_resolve_escape_returns_to_dialog() {
+    let mut app = make_resolve_app();
+    views::conflict_resolve::handle_key(KeyCommand::Quit, &mut app);
+    assert!(matches!(app.mode, AppMode::RebaseConflict(_)));
+}
+
+#[test]
+fn test_conflict_dialog_r_opens_resolver() {
+    let mut app = make_resolve_app();
+    views::conflict_resolve::handle_key(KeyCommand::Quit, &mut app);
+
+    let action = views::conflict::handle_conflict_key(KeyCommand::Reword, &mut app);
+    match action {
+        AppAction::PrepareConflictResolve(state) => {
+            assert_eq!(state.conflicting_files, ["src/parser.rs"]);
+        }
+        _ => panic!("expected PrepareConflictResolve"),
+    }
+}
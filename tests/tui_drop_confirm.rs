@@ -17,8 +17,9 @@
 mod common;
 
 use git_tailor::{
-    app::{AppMode, AppState, PendingDrop},
-    repo::ConflictState,
+    app::{AppMode, AppState, PendingDrop, StackPreview},
+    fragmap::{FileSpan, FragMap, SpanCluster, TouchKind},
+    repo::{ConflictKind, ConflictState, MergeStrategy},
     views,
 };
 use ratatui::{backend::TestBackend, Terminal};
@@ -111,10 +112,14 @@ fn make_app_in_drop_conflict(conflicting_oid: &str, remaining: Vec<&str>) -> App
         original_branch_oid: "def456ghi789abcdef012".to_string(),
         new_tip_oid: "aabbccddeeff00112233".to_string(),
         conflicting_commit_oid: conflicting_oid.to_string(),
+        conflicting_commit_summary: app
+            .commits
+            .iter()
+            .find(|c| c.oid == conflicting_oid)
+            .map(|c| c.summary.clone())
+            .unwrap_or_default(),
         remaining_oids: remaining.iter().map(|s| s.to_string()).collect(),
-        conflicting_files: vec![],
-        still_unresolved: false,
-        squash_context: None,
+        ..Default::default()
     });
     app
 }
@@ -195,10 +200,9 @@ fn test_drop_conflict_dialog_long_summary() {
         original_branch_oid: "def456ghi789abcdef012".to_string(),
         new_tip_oid: "aabbccddeeff00112233".to_string(),
         conflicting_commit_oid: "abc123def456".to_string(),
+        conflicting_commit_summary: "Refactor the entire parser module to use trait-based dispatching for better extensibility".to_string(),
         remaining_oids: vec!["111111111111".to_string(), "222222222222".to_string()],
-        conflicting_files: vec![],
-        still_unresolved: false,
-        squash_context: None,
+        ..Default::default()
     });
 
     terminal
@@ -228,14 +232,13 @@ fn test_drop_conflict_dialog_with_files() {
         original_branch_oid: "def456ghi789abcdef012".to_string(),
         new_tip_oid: "aabbccddeeff00112233".to_string(),
         conflicting_commit_oid: "abc123def456".to_string(),
-        remaining_oids: vec![],
+        conflicting_commit_summary: "Refactor parser module".to_string(),
         conflicting_files: vec![
             "src/parser/mod.rs".to_string(),
             "src/parser/expr.rs".to_string(),
             "tests/integration.rs".to_string(),
         ],
-        still_unresolved: false,
-        squash_context: None,
+        ..Default::default()
     });
 
     terminal
@@ -265,10 +268,10 @@ fn test_drop_conflict_dialog_still_unresolved_warning() {
         original_branch_oid: "def456ghi789abcdef012".to_string(),
         new_tip_oid: "aabbccddeeff00112233".to_string(),
         conflicting_commit_oid: "abc123def456".to_string(),
-        remaining_oids: vec![],
+        conflicting_commit_summary: "Refactor parser module".to_string(),
         conflicting_files: vec!["src/parser/mod.rs".to_string()],
         still_unresolved: true,
-        squash_context: None,
+        ..Default::default()
     });
 
     terminal
@@ -281,3 +284,143 @@ fn test_drop_conflict_dialog_still_unresolved_warning() {
     let buffer = terminal.backend().buffer().clone();
     insta::assert_debug_snapshot!(buffer);
 }
+
+#[test]
+fn test_drop_conflict_dialog_lists_colliding_commits() {
+    let backend = TestBackend::new(80, 26);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = AppState::new();
+    app.commits = vec![
+        common::create_test_commit("abc123def456", "Refactor parser module"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+        common::create_test_commit("789abcdef012", "Fix parser edge case"),
+    ];
+    let commit_oids: Vec<String> = app.commits.iter().map(|c| c.oid.clone()).collect();
+    let cluster = |path: &str, oids: &[&str]| SpanCluster {
+        spans: vec![FileSpan {
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 10,
+        }],
+        commit_oids: oids.iter().map(|s| s.to_string()).collect(),
+    };
+    app.fragmap = Some(FragMap {
+        commits: commit_oids,
+        clusters: vec![
+            cluster("src/parser.rs", &["abc123def456", "789abcdef012"]),
+            cluster("src/feature.rs", &["def456ghi789", "789abcdef012"]),
+        ],
+        matrix: vec![
+            vec![TouchKind::Modified, TouchKind::None],
+            vec![TouchKind::None, TouchKind::Added],
+            vec![TouchKind::Modified, TouchKind::Modified],
+        ],
+    });
+    app.selection_index = 0;
+    // The fragmap fills in the clusters of the conflicting file.
+    app.enter_rebase_conflict(ConflictState {
+        operation_label: "Drop".to_string(),
+        original_branch_oid: "789abcdef012abcdef012".to_string(),
+        new_tip_oid: "aabbccddeeff00112233".to_string(),
+        conflicting_commit_oid: "789abcdef012".to_string(),
+        conflicting_commit_summary: "Fix parser edge case".to_string(),
+        conflicting_files: vec!["src/parser.rs".to_string()],
+        ..Default::default()
+    });
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+            views::conflict::render_conflict(&app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_drop_conflict_dialog_offers_choices_for_deleted_file() {
+    let backend = TestBackend::new(80, 28);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = make_app_in_drop_conflict("abc123def456", vec![]);
+    if let AppMode::RebaseConflict(state) = &mut app.mode {
+        state.conflicting_files = vec!["src/lib.rs".to_string(), "src/old.rs".to_string()];
+        state.conflict_kinds.insert(
+            "src/old.rs".to_string(),
+            ConflictKind::DeleteModify {
+                deleted_by_us: true,
+            },
+        );
+    }
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+            views::conflict::render_conflict(&app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_drop_conflict_strategy_select() {
+    let backend = TestBackend::new(80, 24);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = make_app_in_drop_conflict("abc123def456", vec![]);
+    if let AppMode::RebaseConflict(state) = &mut app.mode {
+        state.conflicting_files = vec!["src/lib.rs".to_string()];
+    }
+    app.enter_strategy_select();
+    app.strategy_select_down();
+    app.strategy_select_down();
+    assert_eq!(app.selected_merge_strategy(), MergeStrategy::IgnoreAllSpace);
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+            views::strategy_select::render(&app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_drop_preview_shows_conflict_in_stack() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = make_app_in_drop_confirm("abc123def456", "Refactor parser module");
+    let confirm_mode = app.mode.clone();
+    app.enter_preview(StackPreview {
+        label: "drop abc123de".to_string(),
+        commits: vec![
+            common::create_test_commit("def456ghi789", "Add feature X"),
+            common::create_test_commit("123abc456def", "Polish feature X"),
+        ],
+        fragmap: None,
+        conflicting_oid: Some("def456ghi789".to_string()),
+        conflicting_files: vec!["src/parser.rs".to_string()],
+        unapplied_oids: ["123abc456def".to_string()].into_iter().collect(),
+    });
+    assert_eq!(app.commits.len(), 2);
+    assert_eq!(app.selection_index, 0);
+
+    terminal
+        .draw(|frame| views::commit_list::render(&mut app, frame))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+
+    app.leave_preview();
+    assert_eq!(app.mode, confirm_mode);
+    assert_eq!(app.commits[0].summary, "Refactor parser module");
+}
@@ -0,0 +1,78 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI snapshot tests for the dialog shown when a todo plan stops.
+
+mod common;
+
+use git_tailor::{
+    app::{AppMode, AppState},
+    repo::{ConflictState, PlanContext, PlanStep},
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn make_app_stopped_at(step: PlanStep, failure: Option<&str>) -> AppState {
+    let mut app = AppState::new();
+    app.commits = vec![
+        common::create_test_commit("abc123def456", "Refactor parser module"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+    ];
+    app.selection_index = 0;
+    app.mode = AppMode::RebaseConflict(ConflictState {
+        operation_label: "Plan".to_string(),
+        original_branch_oid: "def456ghi789abcdef012".to_string(),
+        new_tip_oid: "abc123def456".to_string(),
+        conflicting_commit_oid: "abc123def456".to_string(),
+        conflicting_commit_summary: "Refactor parser module".to_string(),
+        remaining_oids: vec!["def456ghi789".to_string()],
+        plan_context: Some(PlanContext {
+            step,
+            remaining: vec![PlanStep::Pick {
+                oid: "def456ghi789".to_string(),
+                message: None,
+            }],
+            failure: failure.map(str::to_string),
+        }),
+        ..Default::default()
+    });
+    app
+}
+
+fn render(app: &mut AppState) -> ratatui::buffer::Buffer {
+    let backend = TestBackend::new(80, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(app, frame);
+            views::conflict::render_conflict(app, frame);
+        })
+        .unwrap();
+    terminal.backend().buffer().clone()
+}
+
+#[test]
+fn test_plan_stopped_for_editing_dialog() {
+    let mut app = make_app_stopped_at(PlanStep::Stop, None);
+    insta::assert_debug_snapshot!(render(&mut app));
+}
+
+#[test]
+fn test_plan_failed_exec_dialog() {
+    let mut app = make_app_stopped_at(
+        PlanStep::Exec("cargo test".to_string()),
+        Some("error: could not compile `parser`"),
+    );
+    insta::assert_debug_snapshot!(render(&mut app));
+}
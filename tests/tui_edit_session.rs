@@ -0,0 +1,151 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI tests for queuing operations in an edit session.
+
+mod common;
+
+use git_tailor::{
+    app::{AppAction, AppMode, AppState},
+    event::KeyCommand,
+    repo::PlanStep,
+    session::SessionOp,
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn make_session_app() -> AppState {
+    let mut app = AppState::with_commits(vec![
+        common::create_test_commit("abc123def456", "Add parser"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+        common::create_test_commit("ghi789jkl012", "Fix bug in parser"),
+    ]);
+    app.selection_index = 2;
+    assert_eq!(
+        views::commit_list::handle_key(KeyCommand::EditSession, &mut app),
+        AppAction::Handled
+    );
+    assert_eq!(app.mode, AppMode::EditSession);
+    app
+}
+
+fn order(app: &AppState) -> Vec<&str> {
+    app.commits.iter().map(|c| c.oid.as_str()).collect()
+}
+
+#[test]
+fn test_edit_session_queues_without_rewriting() {
+    let backend = TestBackend::new(80, 8);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let mut app = make_session_app();
+    // Fix up the selected parser fix into the parser commit.
+    views::edit_session::handle_key(KeyCommand::Fixup, &mut app);
+    assert_eq!(
+        app.mode,
+        AppMode::SquashSelect {
+            source_index: 2,
+            is_fixup: true
+        }
+    );
+    app.selection_index = 0;
+    assert_eq!(
+        views::squash_select::handle_key(KeyCommand::Confirm, &mut app),
+        AppAction::RefreshSession
+    );
+    assert_eq!(app.mode, AppMode::EditSession);
+    assert_eq!(order(&app), ["abc123def456", "def456ghi789"]);
+
+    // Move the feature first, then reword it.
+    app.selection_index = 1;
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::MoveCommitUp, &mut app),
+        AppAction::RefreshSession
+    );
+    assert_eq!(order(&app), ["def456ghi789", "abc123def456"]);
+    assert_eq!(app.selection_index, 0);
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::Reword, &mut app),
+        AppAction::QueueReword {
+            commit_oid: "def456ghi789".to_string(),
+            current_message: "Add feature X".to_string(),
+        }
+    );
+    assert!(app.queue_session_op(SessionOp::Reword {
+        oid: "def456ghi789".to_string(),
+        message: "Add feature X\n\nWith a body.\n".to_string(),
+    }));
+
+    terminal
+        .draw(|frame| views::commit_list::render(&mut app, frame))
+        .unwrap();
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_edit_session_undo_discard_and_apply() {
+    let mut app = make_session_app();
+    views::edit_session::handle_key(KeyCommand::Drop, &mut app);
+    assert_eq!(order(&app), ["abc123def456", "def456ghi789"]);
+    assert_eq!(app.selection_index, 1);
+
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::Update, &mut app),
+        AppAction::RefreshSession
+    );
+    assert_eq!(app.commits.len(), 3);
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::Update, &mut app),
+        AppAction::Handled
+    );
+    assert!(app.status_is_error);
+
+    // Applying a session that changes nothing just leaves it.
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::Confirm, &mut app),
+        AppAction::Handled
+    );
+    assert_eq!(app.mode, AppMode::CommitList);
+    assert!(app.session.is_none());
+
+    let mut app = make_session_app();
+    views::edit_session::handle_key(KeyCommand::Drop, &mut app);
+    views::edit_session::handle_key(KeyCommand::Quit, &mut app);
+    assert_eq!(app.mode, AppMode::CommitList);
+    assert_eq!(app.commits.len(), 3);
+    assert_eq!(app.selection_index, 2);
+
+    let mut app = make_session_app();
+    app.selection_index = 1;
+    views::edit_session::handle_key(KeyCommand::Drop, &mut app);
+    assert_eq!(
+        views::edit_session::handle_key(KeyCommand::Confirm, &mut app),
+        AppAction::ExecutePlan {
+            steps: vec![
+                PlanStep::Pick {
+                    oid: "abc123def456".to_string(),
+                    message: None,
+                },
+                PlanStep::Pick {
+                    oid: "ghi789jkl012".to_string(),
+                    message: None,
+                },
+            ],
+            first_oid: "def456ghi789".to_string(),
+        }
+    );
+    // The branch's stack is back until the plan has run.
+    assert_eq!(app.commits.len(), 3);
+}
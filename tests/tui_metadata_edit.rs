@@ -0,0 +1,95 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI tests for the commit metadata editor.
+
+mod common;
+
+use git_tailor::{
+    app::{AppAction, AppMode, AppState, MetadataEditState},
+    event::KeyCommand,
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn edit_app() -> AppState {
+    let mut commit = common::create_test_commit("abc123def456", "Add parser");
+    commit.message = "Add parser\n\nSigned-off-by: Test Author <test@example.com>\n".to_string();
+    let mut app = AppState::with_commits(vec![commit.clone()]);
+    app.enter_metadata_edit(MetadataEditState::new(
+        &commit,
+        vec!["Jane Doe <jane@example.com>".to_string()],
+    ));
+    app
+}
+
+#[test]
+fn test_metadata_edit_dialog() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let app = edit_app();
+
+    terminal
+        .draw(|frame| views::metadata::render(&app, frame))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_metadata_edit_adds_co_author_and_applies() {
+    let mut app = edit_app();
+
+    views::metadata::handle_key(KeyCommand::AddField, &mut app);
+    views::metadata::handle_key(KeyCommand::Complete, &mut app);
+    let action = views::metadata::handle_key(KeyCommand::Confirm, &mut app);
+
+    assert!(matches!(app.mode, AppMode::CommitList));
+    let AppAction::ApplyMetadata {
+        commit_oid,
+        metadata,
+    } = action
+    else {
+        panic!("expected ApplyMetadata, got {action:?}");
+    };
+    assert_eq!(commit_oid, "abc123def456");
+    assert_eq!(metadata.author_name, "Test Author");
+    assert_eq!(metadata.author_time, 1705318200);
+    assert_eq!(
+        metadata.message,
+        "Add parser\n\n\
+         Signed-off-by: Test Author <test@example.com>\n\
+         Co-authored-by: Jane Doe <jane@example.com>\n"
+    );
+}
+
+#[test]
+fn test_metadata_edit_rejects_invalid_author() {
+    let mut app = edit_app();
+
+    for _ in 0.."Test Author <test@example.com>".len() {
+        views::metadata::handle_key(KeyCommand::DeleteBackward, &mut app);
+    }
+    for c in "nobody".chars() {
+        views::metadata::handle_key(KeyCommand::InsertChar(c), &mut app);
+    }
+    let action = views::metadata::handle_key(KeyCommand::Confirm, &mut app);
+
+    assert!(matches!(action, AppAction::Handled));
+    assert!(matches!(app.mode, AppMode::MetadataEdit(_)));
+
+    views::metadata::handle_key(KeyCommand::Quit, &mut app);
+    assert!(matches!(app.mode, AppMode::CommitList));
+}
@@ -0,0 +1,92 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI snapshot tests for pushed-commit markers and the confirmation dialog.
+
+mod common;
+
+use git_tailor::{
+    app::{AppAction, AppMode, AppState, PendingPushed},
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn make_app_with_pushed_commit() -> AppState {
+    let mut app = AppState::new();
+    app.commits = vec![
+        common::create_test_commit("abc123def456", "Refactor parser module"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+    ];
+    app.selection_index = 1;
+    app.pushed_oids.insert("abc123def456".to_string());
+    app
+}
+
+#[test]
+fn test_commit_list_marks_pushed_commits() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = make_app_with_pushed_commit();
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_pushed_confirm_dialog() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+
+    let mut app = make_app_with_pushed_commit();
+    app.mode = AppMode::PushedConfirm(PendingPushed {
+        action: Box::new(AppAction::PrepareReword {
+            commit_oid: "abc123def456".to_string(),
+            current_message: "Refactor parser module".to_string(),
+        }),
+        commit_summaries: vec!["Refactor parser module".to_string()],
+    });
+
+    terminal
+        .draw(|frame| {
+            views::commit_list::render(&mut app, frame);
+            views::pushed::render_pushed_confirm(&app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_pushed_confirm_enter_returns_wrapped_action() {
+    let mut app = make_app_with_pushed_commit();
+    let action = AppAction::PrepareReword {
+        commit_oid: "abc123def456".to_string(),
+        current_message: "Refactor parser module".to_string(),
+    };
+    app.enter_pushed_confirm(action.clone(), vec!["Refactor parser module".to_string()]);
+
+    let result =
+        views::pushed::handle_confirm_key(git_tailor::event::KeyCommand::Confirm, &mut app);
+
+    assert_eq!(result, AppAction::ConfirmedPushed(Box::new(action)));
+    assert_eq!(app.mode, AppMode::CommitList);
+}
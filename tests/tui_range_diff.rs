@@ -0,0 +1,85 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI snapshot tests for the range-diff view.
+
+mod common;
+
+use git_tailor::{
+    app::AppState, range_diff, views, CommitDiff, DeltaStatus, DiffLine, DiffLineKind, FileDiff,
+    Hunk,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn commit_diff(oid: &str, summary: &str, added: &[&str]) -> CommitDiff {
+    CommitDiff {
+        commit: common::create_test_commit(oid, summary),
+        files: vec![FileDiff {
+            old_path: Some("src/lib.rs".to_string()),
+            new_path: Some("src/lib.rs".to_string()),
+            status: DeltaStatus::Modified,
+            hunks: vec![Hunk {
+                old_start: 1,
+                old_lines: 0,
+                new_start: 1,
+                new_lines: added.len() as u32,
+                lines: added
+                    .iter()
+                    .map(|l| DiffLine {
+                        kind: DiffLineKind::Addition,
+                        content: format!("{l}\n"),
+                    })
+                    .collect(),
+            }],
+        }],
+    }
+}
+
+#[test]
+fn test_range_diff_after_squash() {
+    let backend = TestBackend::new(80, 16);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let test = common::TestRepo::new();
+    let git_repo = test.git_repo();
+
+    let old = vec![
+        commit_diff(
+            "aaaa1111bbbb",
+            "Add parser",
+            &["fn parse() {}", "fn lex() {}"],
+        ),
+        commit_diff("cccc2222dddd", "Fix parser typo", &["// fixed"]),
+        commit_diff("eeee3333ffff", "Add tests", &["#[test]"]),
+    ];
+    let new = vec![
+        commit_diff(
+            "1111aaaa2222",
+            "Add parser",
+            &["fn parse() {}", "fn lex() {}", "// fixed"],
+        ),
+        commit_diff("3333bbbb4444", "Add tests", &["#[test]"]),
+    ];
+
+    let mut app = AppState::new();
+    app.enter_range_diff(range_diff::range_diff(&old, &new));
+
+    terminal
+        .draw(|frame| {
+            views::range_diff::render(&git_repo, &mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
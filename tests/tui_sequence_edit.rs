@@ -0,0 +1,139 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI tests for arranging a `git rebase -i` todo list.
+
+mod common;
+
+use git_tailor::{
+    app::{AppAction, AppMode, AppState},
+    event::KeyCommand,
+    todo::{self, TodoCommand},
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn make_sequence_app() -> AppState {
+    let mut app = AppState::with_commits(vec![
+        common::create_test_commit("abc123def456", "Add parser"),
+        common::create_test_commit("def456ghi789", "Add feature X"),
+        common::create_test_commit("ghi789jkl012", "Fix bug in parser"),
+    ]);
+    for commit in &app.commits {
+        app.todo_commands
+            .insert(commit.oid.clone(), TodoCommand::Pick);
+    }
+    app.selection_index = 0;
+    app.mode = AppMode::SequenceEdit;
+    app
+}
+
+fn order(app: &AppState) -> Vec<&str> {
+    app.commits.iter().map(|c| c.oid.as_str()).collect()
+}
+
+#[test]
+fn test_sequence_edit_shows_commands() {
+    let backend = TestBackend::new(80, 8);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let mut app = make_sequence_app();
+    app.todo_commands
+        .insert("def456ghi789".to_string(), TodoCommand::Drop);
+    app.todo_commands
+        .insert("ghi789jkl012".to_string(), TodoCommand::Fixup);
+
+    terminal
+        .draw(|frame| views::commit_list::render(&mut app, frame))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn move_keys_reorder_commits_and_keep_selection() {
+    let mut app = make_sequence_app();
+
+    let action = views::sequence_edit::handle_key(KeyCommand::MoveCommitDown, &mut app);
+    assert_eq!(action, AppAction::RefreshFragmap);
+    assert_eq!(
+        order(&app),
+        ["def456ghi789", "abc123def456", "ghi789jkl012"]
+    );
+    assert_eq!(app.selection_index, 1);
+
+    let action = views::sequence_edit::handle_key(KeyCommand::MoveCommitUp, &mut app);
+    assert_eq!(action, AppAction::RefreshFragmap);
+    let action = views::sequence_edit::handle_key(KeyCommand::MoveCommitUp, &mut app);
+    assert_eq!(action, AppAction::Handled);
+    assert_eq!(
+        order(&app),
+        ["abc123def456", "def456ghi789", "ghi789jkl012"]
+    );
+    assert_eq!(app.selection_index, 0);
+}
+
+#[test]
+fn move_keys_follow_reversed_display() {
+    let mut app = make_sequence_app();
+    app.reverse = true;
+    app.selection_index = 2;
+
+    // Shown newest first, so moving up on screen makes the commit newer.
+    views::sequence_edit::handle_key(KeyCommand::MoveCommitDown, &mut app);
+    assert_eq!(
+        order(&app),
+        ["abc123def456", "ghi789jkl012", "def456ghi789"]
+    );
+    assert_eq!(app.selection_index, 1);
+}
+
+#[test]
+fn command_keys_set_todo_command_of_selection() {
+    let mut app = make_sequence_app();
+    app.selection_index = 2;
+
+    views::sequence_edit::handle_key(KeyCommand::Squash, &mut app);
+    assert_eq!(app.todo_commands["ghi789jkl012"], TodoCommand::Squash);
+    views::sequence_edit::handle_key(KeyCommand::Drop, &mut app);
+    assert_eq!(app.todo_commands["ghi789jkl012"], TodoCommand::Drop);
+    views::sequence_edit::handle_key(KeyCommand::Split, &mut app);
+    assert_eq!(app.todo_commands["ghi789jkl012"], TodoCommand::Pick);
+
+    assert_eq!(
+        views::sequence_edit::handle_key(KeyCommand::Confirm, &mut app),
+        AppAction::WriteTodo
+    );
+    assert_eq!(
+        views::sequence_edit::handle_key(KeyCommand::Quit, &mut app),
+        AppAction::AbortTodo
+    );
+}
+
+#[test]
+fn arranged_todo_is_written_in_list_order() {
+    let mut app = make_sequence_app();
+    app.selection_index = 2;
+    views::sequence_edit::handle_key(KeyCommand::Fixup, &mut app);
+    views::sequence_edit::handle_key(KeyCommand::MoveCommitUp, &mut app);
+
+    assert_eq!(
+        todo::write_todo(&app.commits, &app.todo_commands),
+        Ok("pick abc123def456 Add parser\n\
+            fixup ghi789jkl012 Fix bug in parser\n\
+            pick def456ghi789 Add feature X\n"
+            .to_string())
+    );
+}
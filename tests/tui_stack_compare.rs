@@ -0,0 +1,93 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// TUI snapshot tests for the pushed-stack comparison view.
+
+mod common;
+
+use git_tailor::{
+    app::{AppMode, AppState},
+    event::KeyCommand,
+    stack_compare::{self, StackCommit},
+    views,
+};
+use ratatui::{backend::TestBackend, Terminal};
+
+fn stack_commit(oid: &str, summary: &str, patch_id: &str) -> StackCommit {
+    StackCommit {
+        info: common::create_test_commit(oid, summary),
+        patch_id: Some(patch_id.to_string()),
+    }
+}
+
+fn compare_app() -> AppState {
+    let remote = vec![
+        stack_commit("aaaa1111bbbb", "Add parser", "p1"),
+        stack_commit("cccc2222dddd", "Add lexer", "p2"),
+        stack_commit("eeee3333ffff", "Debug logging", "p3"),
+        stack_commit("9999aaaa0000", "Add tests", "p4"),
+    ];
+    let local = vec![
+        stack_commit("aaaa1111bbbb", "Add parser", "p1"),
+        stack_commit("9999aaaa0000", "Add tests", "p4"),
+        stack_commit("1111cccc2222", "Add lexer", "p2-amended"),
+        stack_commit("3333dddd4444", "Add docs", "p5"),
+    ];
+
+    let mut app = AppState::new();
+    app.enter_stack_compare(
+        "origin/topic".to_string(),
+        stack_compare::compare_stacks(&remote, &local),
+    );
+    app
+}
+
+#[test]
+fn test_stack_compare_summary() {
+    let backend = TestBackend::new(80, 10);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let test = common::TestRepo::new();
+    let git_repo = test.git_repo();
+    let mut app = compare_app();
+
+    terminal
+        .draw(|frame| {
+            views::stack_compare::render(&git_repo, &mut app, frame);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    insta::assert_debug_snapshot!(buffer);
+}
+
+#[test]
+fn test_stack_compare_enter_drills_down_and_esc_goes_back() {
+    let mut app = compare_app();
+
+    views::stack_compare::handle_key(KeyCommand::Confirm, &mut app);
+    assert!(matches!(
+        app.mode,
+        AppMode::StackCompare { detail: true, .. }
+    ));
+
+    views::stack_compare::handle_key(KeyCommand::Quit, &mut app);
+    assert!(matches!(
+        app.mode,
+        AppMode::StackCompare { detail: false, .. }
+    ));
+
+    views::stack_compare::handle_key(KeyCommand::Quit, &mut app);
+    assert_eq!(app.mode, AppMode::CommitList);
+    assert!(app.stack_compare.is_empty());
+}
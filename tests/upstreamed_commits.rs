@@ -0,0 +1,105 @@
+// Copyright 2026 Thomas Johannesson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use git_tailor::repo::GitRepo;
+
+/// Commit `path` with `content` on top of `branch` without touching HEAD,
+/// the index or the working tree.
+fn commit_on_branch(test: &common::TestRepo, branch: &str, path: &str, content: &str, msg: &str) {
+    let repo = &test.repo;
+    let refname = format!("refs/heads/{branch}");
+    let parent = repo
+        .find_reference(&refname)
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    let blob = repo.blob(content.as_bytes()).unwrap();
+    let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+    builder.insert(path, blob, 0o100644).unwrap();
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let sig = git2::Signature::now("Upstream User", "upstream@example.com").unwrap();
+    repo.commit(Some(&refname), &sig, &sig, msg, &tree, &[&parent])
+        .unwrap();
+}
+
+#[test]
+fn cherry_picked_commit_is_reported() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    let picked = test.commit_file("b.txt", "b\n", "Add b");
+    let _ours = test.commit_file("c.txt", "c\n", "Add c");
+
+    // Upstream applies the same change as "Add b" with a different message
+    // and on top of an unrelated commit, so only the patch-id matches.
+    commit_on_branch(&test, "master", "z.txt", "z\n", "Unrelated upstream work");
+    commit_on_branch(
+        &test,
+        "master",
+        "b.txt",
+        "b\n",
+        "Apply b from feature branch",
+    );
+
+    let git_repo = test.git_repo();
+    let head_oid = git_repo.head_oid().unwrap();
+    let upstreamed = git_repo
+        .find_upstreamed_commits("master", &head_oid)
+        .unwrap();
+
+    assert_eq!(upstreamed, vec![picked.to_string()]);
+}
+
+#[test]
+fn nothing_reported_when_upstream_has_no_new_commits() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    test.commit_file("b.txt", "b\n", "Add b");
+
+    let git_repo = test.git_repo();
+    let head_oid = git_repo.head_oid().unwrap();
+    let upstreamed = git_repo
+        .find_upstreamed_commits("master", &head_oid)
+        .unwrap();
+
+    assert!(upstreamed.is_empty());
+}
+
+#[test]
+fn different_change_to_same_file_is_not_reported() {
+    let test = common::TestRepo::new();
+
+    let base = test.commit_file("a.txt", "base\n", "Base");
+    test.create_branch("feature", base);
+    test.checkout("refs/heads/feature");
+    test.commit_file("b.txt", "ours\n", "Add b");
+
+    commit_on_branch(&test, "master", "b.txt", "theirs\n", "Add b");
+
+    let git_repo = test.git_repo();
+    let head_oid = git_repo.head_oid().unwrap();
+    let upstreamed = git_repo
+        .find_upstreamed_commits("master", &head_oid)
+        .unwrap();
+
+    assert!(upstreamed.is_empty());
+}